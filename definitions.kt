@@ -1,3 +1,11 @@
+/// A 2D affine transform, as the top two rows of a 3x3 matrix
+/// 
+/// A point `(x, y)` is transformed to
+/// `(m[0][0] * x + m[0][1] * y + m[0][2], m[1][0] * x + m[1][1] * y + m[1][2])`.
+/// 
+/// [Figma documentation](https://www.figma.com/developers/api#transform-type)
+typealias Transform = List<List<Double>>
+
 /// [Figma documentation](https://www.figma.com/developers/api#color-type)
 @Serializable
 data class Color (
@@ -7,9 +15,30 @@ data class Color (
 	val a: Double
 )
 
+/// A position color pair representing a gradient stop
+/// 
+/// [Figma documentation](https://www.figma.com/developers/api#colorstop-type)
+@Serializable
+data class ColorStop (
+	/// Value between 0 and 1 representing position along gradient axis
+	val position: Double,
+	/// Color attached to corresponding position
+	val color: Color
+)
+
 /// [Figma documentation](https://www.figma.com/developers/api#component-type)
 @Serializable
 data class Component (
+	val key: String,
+	val name: String,
+	val description: String,
+	/// The id of the component set the component is a variant of
+	val componentSetId: String? = null
+)
+
+/// [Figma documentation](https://www.figma.com/developers/api#component-set-type)
+@Serializable
+data class ComponentSet (
 	val key: String,
 	val name: String,
 	val description: String
@@ -47,8 +76,12 @@ data class Effect (
 	val color: Color? = null,
 	/// How far the shadow is projected in the x and y directions
 	val offset: Vector? = null,
+	/// Radius of the blur effect (applies to shadows as well)
+	val radius: Double? = null,
 	/// How far the shadow spreads
-	val spread: Double? = null
+	val spread: Double? = null,
+	/// Whether to show the shadow behind translucent or transparent pixels (applies only to drop shadows)
+	val showShadowBehindNode: Boolean? = null
 )
 
 /// Node type indicates what kind of node you are working with: for example, a FRAME node versus a RECTANGLE node. A node can have additional properties associated with it depending on its node type.
@@ -117,6 +150,7 @@ enum class PaintType(val string: String) {
 /// [Figma documentation](https://www.figma.com/developers/api#blendmode-type)
 @Serializable
 enum class BlendMode(val string: String) {
+	/// Children of a group blend with the layers below the group, as if it wasn't there
 	@SerialName("PASS_THROUGH")
 	PassThrough("PASS_THROUGH"),
 	@SerialName("NORMAL")
@@ -157,6 +191,26 @@ enum class BlendMode(val string: String) {
 	Luminosity("LUMINOSITY"),
 }
 
+/// Image scaling mode
+@Serializable
+enum class ScaleMode(val string: String) {
+	/// Scale the image to cover the node, cropping the overflow
+	@SerialName("FILL")
+	Fill("FILL"),
+	/// Scale the image to fit within the node
+	@SerialName("FIT")
+	Fit("FIT"),
+	/// Repeat the image at its natural size multiplied by the scaling factor
+	@SerialName("TILE")
+	Tile("TILE"),
+	/// Position and crop the image with the image transform
+	@SerialName("STRETCH")
+	Stretch("STRETCH"),
+	/// Plugin API name for `STRETCH`
+	@SerialName("CROP")
+	Crop("CROP"),
+}
+
 /// A solid color, gradient, or image texture that can be applied as fills or strokes
 /// 
 /// [Figma documentation](https://www.figma.com/developers/api#paint-type)
@@ -169,10 +223,20 @@ data class Paint (
 	val opacity: Double? = null,
 	/// Solid color of the paint
 	val color: Color? = null,
-	/// How this node blends with nodes behind it in the scene
-	val blend_mode: BlendMode? = null,
+	/// How this paint blends with the paints below it
+	val blendMode: BlendMode? = null,
 	/// This field contains three vectors, each of which are a position in normalized object space (normalized object space is if the top left corner of the bounding box of the object is (0, 0) and the bottom right is (1,1)). The first position corresponds to the start of the gradient (value 0 for the purposes of calculating gradient stops), the second position is the end of the gradient (value 1), and the third handle position determines the width of the gradient. See image examples below:
-	val gradient_handle_positions: List<Vector>? = null
+	val gradientHandlePositions: List<Vector>? = null,
+	/// Positions of key points along the gradient axis with the colors anchored there. Colors along the gradient are interpolated smoothly between neighboring gradient stops.
+	val gradientStops: List<ColorStop>? = null,
+	/// Image scaling mode
+	val scaleMode: ScaleMode? = null,
+	/// Affine transform applied to the image, only present if scaleMode is `STRETCH`
+	val imageTransform: Transform? = null,
+	/// Amount image is scaled by in tiling, only present if scaleMode is `TILE`
+	val scalingFactor: Double? = null,
+	/// A reference to an image embedded in this node. To download the image using this reference, use the `GET file images` endpoint to retrieve the mapping from image references to image URLs
+	val imageRef: String? = null
 )
 
 /// Individual stroke weights
@@ -201,6 +265,26 @@ enum class StrokeAlign(val string: String) {
 	Center("CENTER"),
 }
 
+/// Winding rule used to determine the inside of a path
+@Serializable
+enum class WindingRule(val string: String) {
+	@SerialName("NONZERO")
+	Nonzero("NONZERO"),
+	@SerialName("EVENODD")
+	Evenodd("EVENODD"),
+}
+
+/// A vector svg path
+/// 
+/// [Figma documentation](https://www.figma.com/developers/api#path-type)
+@Serializable
+data class Path (
+	/// A series of path commands that encodes how to draw the path
+	val path: String,
+	/// The winding rule for the path, either "EVENODD" or "NONZERO"
+	val windingRule: WindingRule
+)
+
 /// Animation easing curves
 /// 
 /// [Figma documentation](https://www.figma.com/developers/api#easingtype-type)
@@ -238,6 +322,126 @@ enum class EasingType(val string: String) {
 	CustomSpring("CUSTOM_SPRING"),
 }
 
+/// What a prototype interaction does
+@Serializable
+enum class ActionType(val string: String) {
+	/// Go back to the previous frame
+	@SerialName("BACK")
+	Back("BACK"),
+	/// Close the overlay
+	@SerialName("CLOSE")
+	Close("CLOSE"),
+	/// Open a URL
+	@SerialName("URL")
+	Url("URL"),
+	/// Go to another node
+	@SerialName("NODE")
+	Node("NODE"),
+	@SerialName("UPDATE_MEDIA_RUNTIME")
+	UpdateMediaRuntime("UPDATE_MEDIA_RUNTIME"),
+	@SerialName("SET_VARIABLE")
+	SetVariable("SET_VARIABLE"),
+	@SerialName("SET_VARIABLE_MODE")
+	SetVariableMode("SET_VARIABLE_MODE"),
+	@SerialName("CONDITIONAL")
+	Conditional("CONDITIONAL"),
+}
+
+/// How a node action moves to its destination
+@Serializable
+enum class Navigation(val string: String) {
+	/// Replace the current frame with the destination
+	@SerialName("NAVIGATE")
+	Navigate("NAVIGATE"),
+	/// Replace the current overlay with the destination
+	@SerialName("SWAP")
+	Swap("SWAP"),
+	/// Open the destination as an overlay
+	@SerialName("OVERLAY")
+	Overlay("OVERLAY"),
+	/// Scroll to the destination within the current frame
+	@SerialName("SCROLL_TO")
+	ScrollTo("SCROLL_TO"),
+	/// Change to another variant of the component
+	@SerialName("CHANGE_TO")
+	ChangeTo("CHANGE_TO"),
+}
+
+/// [Figma documentation](https://www.figma.com/developers/api#action-type)
+@Serializable
+data class Action (
+	val type: ActionType,
+	/// URL to open, if URL type
+	val url: String? = null,
+	/// ID of the node to go to, if NODE type
+	val destinationId: String? = null,
+	/// How to go to the destination, if NODE type
+	val navigation: Navigation? = null
+)
+
+/// The event that triggers a prototype interaction
+@Serializable
+enum class TriggerType(val string: String) {
+	@SerialName("ON_CLICK")
+	OnClick("ON_CLICK"),
+	@SerialName("ON_HOVER")
+	OnHover("ON_HOVER"),
+	@SerialName("ON_PRESS")
+	OnPress("ON_PRESS"),
+	@SerialName("ON_DRAG")
+	OnDrag("ON_DRAG"),
+	@SerialName("AFTER_TIMEOUT")
+	AfterTimeout("AFTER_TIMEOUT"),
+	@SerialName("MOUSE_ENTER")
+	MouseEnter("MOUSE_ENTER"),
+	@SerialName("MOUSE_LEAVE")
+	MouseLeave("MOUSE_LEAVE"),
+	@SerialName("MOUSE_UP")
+	MouseUp("MOUSE_UP"),
+	@SerialName("MOUSE_DOWN")
+	MouseDown("MOUSE_DOWN"),
+	@SerialName("ON_KEY_DOWN")
+	OnKeyDown("ON_KEY_DOWN"),
+	@SerialName("ON_KEY_UP")
+	OnKeyUp("ON_KEY_UP"),
+	@SerialName("ON_MEDIA_HIT")
+	OnMediaHit("ON_MEDIA_HIT"),
+	@SerialName("ON_MEDIA_END")
+	OnMediaEnd("ON_MEDIA_END"),
+}
+
+/// [Figma documentation](https://www.figma.com/developers/api#trigger-type)
+@Serializable
+data class Trigger (
+	val type: TriggerType
+)
+
+/// A prototype interaction
+/// 
+/// [Figma documentation](https://www.figma.com/developers/api#reaction-type)
+@Serializable
+data class Reaction (
+	/// Deprecated in favour of `actions`
+	val action: Action? = null,
+	/// The actions performed, in order
+	val actions: List<Action>? = null,
+	val trigger: Trigger
+)
+
+/// How a mask hides the layers it masks
+@Serializable
+enum class MaskType(val string: String) {
+	/// The mask's opacity, including that of its fills, shows the layers
+	@SerialName("ALPHA")
+	Alpha("ALPHA"),
+	/// The mask's shape shows the layers, ignoring its fills
+	@SerialName("VECTOR")
+	Vector("VECTOR"),
+	/// The brightness of the mask's fills shows the layers
+	@SerialName("LUMINANCE")
+	Luminance("LUMINANCE"),
+}
+
 /// [Figma documentation](https://www.figma.com/developers/api#rectangle-type)
 @Serializable
 data class Rectangle (
@@ -279,6 +483,34 @@ enum class CounterAxisAlignItems(val string: String) {
 	Baseline("BASELINE"),
 }
 
+@Serializable
+enum class LayoutWrap(val string: String) {
+	@SerialName("NO_WRAP")
+	NoWrap("NO_WRAP"),
+	@SerialName("WRAP")
+	Wrap("WRAP"),
+}
+
+@Serializable
+enum class CounterAxisAlignContent(val string: String) {
+	@SerialName("AUTO")
+	Auto("AUTO"),
+	@SerialName("SPACE_BETWEEN")
+	SpaceBetween("SPACE_BETWEEN"),
+}
+
+@Serializable
+enum class GridChildAlign(val string: String) {
+	@SerialName("AUTO")
+	Auto("AUTO"),
+	@SerialName("MIN")
+	Min("MIN"),
+	@SerialName("CENTER")
+	Center("CENTER"),
+	@SerialName("MAX")
+	Max("MAX"),
+}
+
 @Serializable
 enum class LayoutPositioning(val string: String) {
 	@SerialName("ABSOLUTE")
@@ -293,6 +525,8 @@ enum class LayoutMode(val string: String) {
 	Horizontal("HORIZONTAL"),
 	@SerialName("VERTICAL")
 	Vertical("VERTICAL"),
+	@SerialName("GRID")
+	Grid("GRID"),
 }
 
 @Serializable
@@ -305,6 +539,8 @@ data class Styles (
 
 @Serializable
 enum class TextCase(val string: String) {
+	@SerialName("ORIGINAL")
+	Original("ORIGINAL"),
 	@SerialName("UPPER")
 	Upper("UPPER"),
 	@SerialName("LOWER")
@@ -319,6 +555,8 @@ enum class TextCase(val string: String) {
 
 @Serializable
 enum class TextDecoration(val string: String) {
+	@SerialName("NONE")
+	None("NONE"),
 	@SerialName("STRIKETHROUGH")
 	Strikethrough("STRIKETHROUGH"),
 	@SerialName("UNDERLINE")
@@ -336,6 +574,40 @@ enum class TextAutoResize(val string: String) {
 	Truncate("TRUNCATE"),
 }
 
+/// Whether text that doesn't fit is truncated
+@Serializable
+enum class TextTruncation(val string: String) {
+	@SerialName("DISABLED")
+	Disabled("DISABLED"),
+	/// Trailing text is replaced with "…"
+	@SerialName("ENDING")
+	Ending("ENDING"),
+}
+
+/// Horizontal text alignment
+@Serializable
+enum class TextAlignHorizontal(val string: String) {
+	@SerialName("LEFT")
+	Left("LEFT"),
+	@SerialName("RIGHT")
+	Right("RIGHT"),
+	@SerialName("CENTER")
+	Center("CENTER"),
+	@SerialName("JUSTIFIED")
+	Justified("JUSTIFIED"),
+}
+
+/// Vertical text alignment
+@Serializable
+enum class TextAlignVertical(val string: String) {
+	@SerialName("TOP")
+	Top("TOP"),
+	@SerialName("CENTER")
+	Center("CENTER"),
+	@SerialName("BOTTOM")
+	Bottom("BOTTOM"),
+}
+
 /// Type of hyperlink
 @Serializable
 enum class HyperlinkType(val string: String) {
@@ -354,6 +626,17 @@ data class Hyperlink (
 	val nodeId: String? = null
 )
 
+/// The unit of the line height value specified by the user
+@Serializable
+enum class LineHeightUnit(val string: String) {
+	@SerialName("PIXELS")
+	Pixels("PIXELS"),
+	@SerialName("FONT_SIZE_%")
+	FontSizePercent("FONT_SIZE_%"),
+	@SerialName("INTRINSIC_%")
+	IntrinsicPercent("INTRINSIC_%"),
+}
+
 /// Metadata for character formatting
 /// 
 /// [Figma documentation](https://www.figma.com/developers/api#typestyle-type)
@@ -361,8 +644,12 @@ data class Hyperlink (
 data class TypeStyle (
 	/// Font family of text (standard name)
 	val fontFamily: String,
+	/// PostScript font name
+	val fontPostScriptName: String? = null,
 	/// Space between paragraphs in px, 0 if not present
 	val paragraphSpacing: Double? = null,
+	/// Paragraph indentation in px, 0 if not present
+	val paragraphIndent: Double? = null,
 	/// Whether or not text is italicized
 	val italic: Boolean? = null,
 	/// Numeric font weight
@@ -375,10 +662,65 @@ data class TypeStyle (
 	val textDecoration: TextDecoration? = null,
 	/// Dimensions along which text will auto resize, default is that the text does not auto-resize
 	val textAutoResize: TextAutoResize? = null,
+	/// Whether text that doesn't fit its bounds is truncated, default is disabled
+	val textTruncation: TextTruncation? = null,
+	/// The number of lines shown before truncating the text, if truncation is enabled
+	val maxLines: UInt? = null,
+	/// Horizontal text alignment as string enum
+	val textAlignHorizontal: TextAlignHorizontal? = null,
+	/// Vertical text alignment as string enum
+	val textAlignVertical: TextAlignVertical? = null,
+	/// Space between characters in px
+	val letterSpacing: Double? = null,
+	/// A map of OpenType feature flags to 1 or 0, 1 if it is enabled and 0 if it is disabled. Note that some flags aren't reflected here. For example, SMCP (small caps) is still represented by the textCase field.
+	val opentypeFlags: HashMap<String, Double>? = null,
+	/// Link to a URL or frame
+	val hyperlink: Hyperlink? = null,
+	/// Line height in px
+	val lineHeightPx: Double,
+	/// Line height as a percentage of normal line height. This is deprecated; in a future version of the API only lineHeightPx and lineHeightPercentFontSize will be returned.
+	val lineHeightPercent: Double? = null,
+	/// Line height as a percentage of the font size. Only returned when lineHeightPercent is not 100
+	val lineHeightPercentFontSize: Double? = null,
+	/// The unit of the line height value specified by the user.
+	val lineHeightUnit: LineHeightUnit? = null
+)
+
+/// Character formatting that overrides part of a text node's `TypeStyle`. Only the overridden fields are present.
+/// 
+/// [Figma documentation](https://www.figma.com/developers/api#text-props)
+@Serializable
+data class TypeStyleOverride (
+	/// Font family of text (standard name)
+	val fontFamily: String? = null,
+	/// Whether or not text is italicized
+	val italic: Boolean? = null,
+	/// Numeric font weight
+	val fontWeight: Double? = null,
+	/// Font size in px
+	val fontSize: Double? = null,
+	/// Text casing applied to the characters
+	val textCase: TextCase? = null,
+	/// Text decoration applied to the characters
+	val textDecoration: TextDecoration? = null,
 	/// Link to a URL or frame
 	val hyperlink: Hyperlink? = null,
+	/// Space between characters in px
+	val letterSpacing: Double? = null,
+	/// A map of OpenType feature flags to 1 or 0
+	val opentypeFlags: HashMap<String, Double>? = null,
 	/// Line height in px
-	val lineHeightPx: Double
+	val lineHeightPx: Double? = null,
+	/// Line height as a percentage of the font size
+	val lineHeightPercentFontSize: Double? = null,
+	/// The unit of the line height value specified by the user.
+	val lineHeightUnit: LineHeightUnit? = null,
+	/// Paints applied to characters
+	val fills: List<Paint>? = null,
+	/// ID of the fill style the paints come from
+	val inheritFillStyleId: String? = null,
+	/// ID of the text style the font properties come from
+	val inheritTextStyleId: String? = null
 )
 
 @Serializable
@@ -465,6 +807,10 @@ data class Node (
 	val individualStrokeWeights: StrokeWeights? = null,
 	/// Position of stroke relative to vector outline
 	val strokeAlign: StrokeAlign? = null,
+	/// An array of paths representing the object fill. Only returned if `geometry=paths` is requested.
+	val fillGeometry: List<Path>? = null,
+	/// An array of paths representing the object stroke. Only returned if `geometry=paths` is requested.
+	val strokeGeometry: List<Path>? = null,
 	/// An array of floating point numbers describing the pattern of dash length and gap lengths that the vector path follows. For example a value of [1, 2] indicates that the path has a dash of length 1 followed by a gap of length 2, repeated.
 	val strokeDashes: List<Double>? = null,
 	/// Radius of each corner of the node if a single radius is set for all corners
@@ -475,8 +821,26 @@ data class Node (
 	val transitionDuration: Double? = null,
 	/// The easing curve used in the prototyping transition on this node
 	val transitionEasing: EasingType? = null,
+	/// Node ID of node to transition to in prototyping. Deprecated in favour of `reactions`.
+	val transitionNodeID: String? = null,
+	/// The prototype interactions of this node
+	val reactions: List<Reaction>? = null,
 	/// Opacity of the node
 	val opacity: Double? = null,
+	/// How this node blends with nodes behind it in the scene
+	val blendMode: BlendMode? = null,
+	/// Whether this node is a mask for the sibling nodes above it
+	val isMask: Boolean? = null,
+	/// How this node masks, if it is a mask
+	val maskType: MaskType? = null,
+	/// Whether or not this node clips content outside of its bounds
+	val clipsContent: Boolean? = null,
+	/// The transform of the node relative to its parent. Only present if `geometry=paths` is requested.
+	val relativeTransform: Transform? = null,
+	/// The rotation of the node in radians, anticlockwise, if not 0
+	val rotation: Double? = null,
+	/// Width and height of the node before it is transformed. Only present if `geometry=paths` is requested.
+	val size: Vector? = null,
 	/// Bounding box of the node in absolute space coordinates
 	val absoluteBoundingBox: Rectangle? = null,
 	/// The bounds of the rendered node in the file in absolute space coordinates
@@ -491,6 +855,36 @@ data class Node (
 	val counterAxisAlignItems: CounterAxisAlignItems? = null,
 	/// The distance between children of the frame. Can be negative. This property is only applicable for auto-layout frames.
 	val itemSpacing: Double? = null,
+	/// Whether the children of a horizontal auto-layout frame wrap onto new lines when there isn't room. This property is only applicable for auto-layout frames.
+	val layoutWrap: LayoutWrap? = null,
+	/// The distance between wrapped lines of children. Null means it is the same as `itemSpacing`. This property is only applicable for auto-layout frames with wrapping.
+	val counterAxisSpacing: Double? = null,
+	/// Whether wrapped lines of children are packed together or spread apart. This property is only applicable for auto-layout frames with wrapping.
+	val counterAxisAlignContent: CounterAxisAlignContent? = null,
+	/// The number of rows of a grid auto-layout frame
+	val gridRowCount: UInt? = null,
+	/// The number of columns of a grid auto-layout frame
+	val gridColumnCount: UInt? = null,
+	/// The distance between rows of a grid auto-layout frame
+	val gridRowGap: Double? = null,
+	/// The distance between columns of a grid auto-layout frame
+	val gridColumnGap: Double? = null,
+	/// The sizes of the rows of a grid auto-layout frame, as a value for CSS grid-template-rows
+	val gridRowsSizing: String? = null,
+	/// The sizes of the columns of a grid auto-layout frame, as a value for CSS grid-template-columns
+	val gridColumnsSizing: String? = null,
+	/// The index of the row the node starts in, counting from 0. This property is only applicable for direct children of grid auto-layout frames.
+	val gridRowAnchorIndex: UInt? = null,
+	/// The index of the column the node starts in, counting from 0. This property is only applicable for direct children of grid auto-layout frames.
+	val gridColumnAnchorIndex: UInt? = null,
+	/// The number of rows the node spans. This property is only applicable for direct children of grid auto-layout frames.
+	val gridRowSpan: UInt? = null,
+	/// The number of columns the node spans. This property is only applicable for direct children of grid auto-layout frames.
+	val gridColumnSpan: UInt? = null,
+	/// The horizontal alignment of the node within its grid cells. This property is only applicable for direct children of grid auto-layout frames.
+	val gridChildHorizontalAlign: GridChildAlign? = null,
+	/// The vertical alignment of the node within its grid cells. This property is only applicable for direct children of grid auto-layout frames.
+	val gridChildVerticalAlign: GridChildAlign? = null,
 	/// Determines whether a layer's size and position should be determined by auto-layout settings or manually adjustable.
 	val layoutPositioning: LayoutPositioning? = null,
 	/// Whether this layer uses auto-layout to position its children.
@@ -511,12 +905,18 @@ data class Node (
 	val characters: String? = null,
 	/// Style of text including font family and weight
 	val style: TypeStyle? = null,
+	/// Array with same number of elements as characters in text box, each element is a reference to the styleOverrideTable defined below and maps to the corresponding character in the characters field. Elements with value 0 have the default type style
+	val characterStyleOverrides: List<UInt>? = null,
+	/// Map from ID to TypeStyle for looking up style overrides
+	val styleOverrideTable: HashMap<String, TypeStyleOverride>? = null,
 	/// Horizontal and vertical layout contraints for node
 	val constraints: LayoutConstraint? = null,
 	/// Determines if the layer should stretch along the parent’s counter axis. This property is only provided for direct children of auto-layout frames.
 	val layoutAlign: LayoutAlign? = null,
 	/// This property is applicable only for direct children of auto-layout frames, ignored otherwise. Determines whether a layer should stretch along the parent’s primary axis. A 0 corresponds to a fixed size and 1 corresponds to stretch
-	val layoutGrow: Double? = null
+	val layoutGrow: Double? = null,
+	/// ID of component that this instance came from, refers to components table
+	val componentId: String? = null
 )
 
 @Serializable
@@ -545,6 +945,8 @@ data class Style (
 data class File (
 	val document: Node,
 	val components: HashMap<String, Component>,
+	/// Files exported before component sets existed don't have any
+	val componentSets: HashMap<String, ComponentSet>? = null,
 	val styles: HashMap<String, Style>,
 	val name: String,
 	val schemaVersion: UByte,
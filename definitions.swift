@@ -4,6 +4,14 @@
 
 import Foundation
 
+/// A 2D affine transform, as the top two rows of a 3x3 matrix
+/// 
+/// A point `(x, y)` is transformed to
+/// `(m[0][0] * x + m[0][1] * y + m[0][2], m[1][0] * x + m[1][1] * y + m[1][2])`.
+/// 
+/// [Figma documentation](https://www.figma.com/developers/api#transform-type)
+public typealias Transform = [[Double]]
+
 /// [Figma documentation](https://www.figma.com/developers/api#color-type)
 public struct Color: Codable {
 	public let r: Double
@@ -19,11 +27,42 @@ public struct Color: Codable {
 	}
 }
 
+/// A position color pair representing a gradient stop
+/// 
+/// [Figma documentation](https://www.figma.com/developers/api#colorstop-type)
+public struct ColorStop: Codable {
+	/// Value between 0 and 1 representing position along gradient axis
+	public let position: Double
+	/// Color attached to corresponding position
+	public let color: Color
+
+	public init(position: Double, color: Color) {
+		self.position = position
+		self.color = color
+	}
+}
+
 /// [Figma documentation](https://www.figma.com/developers/api#component-type)
 public struct Component: Codable {
 	public let key: String
 	public let name: String
 	public let description: String
+	/// The id of the component set the component is a variant of
+	public let componentSetId: String?
+
+	public init(key: String, name: String, description: String, componentSetId: String?) {
+		self.key = key
+		self.name = name
+		self.description = description
+		self.componentSetId = componentSetId
+	}
+}
+
+/// [Figma documentation](https://www.figma.com/developers/api#component-set-type)
+public struct ComponentSet: Codable {
+	public let key: String
+	public let name: String
+	public let description: String
 
 	public init(key: String, name: String, description: String) {
 		self.key = key
@@ -62,15 +101,21 @@ public struct Effect: Codable {
 	public let color: Color?
 	/// How far the shadow is projected in the x and y directions
 	public let offset: Vector?
+	/// Radius of the blur effect (applies to shadows as well)
+	public let radius: Double?
 	/// How far the shadow spreads
 	public let spread: Double?
+	/// Whether to show the shadow behind translucent or transparent pixels (applies only to drop shadows)
+	public let showShadowBehindNode: Bool?
 
-	public init(type: EffectType, visible: Bool, color: Color?, offset: Vector?, spread: Double?) {
+	public init(type: EffectType, visible: Bool, color: Color?, offset: Vector?, radius: Double?, spread: Double?, showShadowBehindNode: Bool?) {
 		self.type = type
 		self.visible = visible
 		self.color = color
 		self.offset = offset
+		self.radius = radius
 		self.spread = spread
+		self.showShadowBehindNode = showShadowBehindNode
 	}
 }
 
@@ -111,6 +156,7 @@ public enum PaintType: String, Codable {
 /// 
 /// [Figma documentation](https://www.figma.com/developers/api#blendmode-type)
 public enum BlendMode: String, Codable {
+	/// Children of a group blend with the layers below the group, as if it wasn't there
 	case passThrough = "PASS_THROUGH"
 	case normal = "NORMAL"
 	case darken = "DARKEN"
@@ -132,6 +178,20 @@ public enum BlendMode: String, Codable {
 	case luminosity = "LUMINOSITY"
 }
 
+/// Image scaling mode
+public enum ScaleMode: String, Codable {
+	/// Scale the image to cover the node, cropping the overflow
+	case fill = "FILL"
+	/// Scale the image to fit within the node
+	case fit = "FIT"
+	/// Repeat the image at its natural size multiplied by the scaling factor
+	case tile = "TILE"
+	/// Position and crop the image with the image transform
+	case stretch = "STRETCH"
+	/// Plugin API name for `STRETCH`
+	case crop = "CROP"
+}
+
 /// A solid color, gradient, or image texture that can be applied as fills or strokes
 /// 
 /// [Figma documentation](https://www.figma.com/developers/api#paint-type)
@@ -143,18 +203,33 @@ public struct Paint: Codable {
 	public let opacity: Double?
 	/// Solid color of the paint
 	public let color: Color?
-	/// How this node blends with nodes behind it in the scene
-	public let blend_mode: BlendMode?
+	/// How this paint blends with the paints below it
+	public let blendMode: BlendMode?
 	/// This field contains three vectors, each of which are a position in normalized object space (normalized object space is if the top left corner of the bounding box of the object is (0, 0) and the bottom right is (1,1)). The first position corresponds to the start of the gradient (value 0 for the purposes of calculating gradient stops), the second position is the end of the gradient (value 1), and the third handle position determines the width of the gradient. See image examples below:
-	public let gradient_handle_positions: [Vector]?
-
-	public init(type: PaintType, visible: Bool?, opacity: Double?, color: Color?, blend_mode: BlendMode?, gradient_handle_positions: [Vector]?) {
+	public let gradientHandlePositions: [Vector]?
+	/// Positions of key points along the gradient axis with the colors anchored there. Colors along the gradient are interpolated smoothly between neighboring gradient stops.
+	public let gradientStops: [ColorStop]?
+	/// Image scaling mode
+	public let scaleMode: ScaleMode?
+	/// Affine transform applied to the image, only present if scaleMode is `STRETCH`
+	public let imageTransform: Transform?
+	/// Amount image is scaled by in tiling, only present if scaleMode is `TILE`
+	public let scalingFactor: Double?
+	/// A reference to an image embedded in this node. To download the image using this reference, use the `GET file images` endpoint to retrieve the mapping from image references to image URLs
+	public let imageRef: String?
+
+	public init(type: PaintType, visible: Bool?, opacity: Double?, color: Color?, blendMode: BlendMode?, gradientHandlePositions: [Vector]?, gradientStops: [ColorStop]?, scaleMode: ScaleMode?, imageTransform: Transform?, scalingFactor: Double?, imageRef: String?) {
 		self.type = type
 		self.visible = visible
 		self.opacity = opacity
 		self.color = color
-		self.blend_mode = blend_mode
-		self.gradient_handle_positions = gradient_handle_positions
+		self.blendMode = blendMode
+		self.gradientHandlePositions = gradientHandlePositions
+		self.gradientStops = gradientStops
+		self.scaleMode = scaleMode
+		self.imageTransform = imageTransform
+		self.scalingFactor = scalingFactor
+		self.imageRef = imageRef
 	}
 }
 
@@ -186,6 +261,27 @@ public enum StrokeAlign: String, Codable {
 	case center = "CENTER"
 }
 
+/// Winding rule used to determine the inside of a path
+public enum WindingRule: String, Codable {
+	case nonzero = "NONZERO"
+	case evenodd = "EVENODD"
+}
+
+/// A vector svg path
+/// 
+/// [Figma documentation](https://www.figma.com/developers/api#path-type)
+public struct Path: Codable {
+	/// A series of path commands that encodes how to draw the path
+	public let path: String
+	/// The winding rule for the path, either "EVENODD" or "NONZERO"
+	public let windingRule: WindingRule
+
+	public init(path: String, windingRule: WindingRule) {
+		self.path = path
+		self.windingRule = windingRule
+	}
+}
+
 /// Animation easing curves
 /// 
 /// [Figma documentation](https://www.figma.com/developers/api#easingtype-type)
@@ -209,6 +305,107 @@ public enum EasingType: String, Codable {
 	case customSpring = "CUSTOM_SPRING"
 }
 
+/// What a prototype interaction does
+public enum ActionType: String, Codable {
+	/// Go back to the previous frame
+	case back = "BACK"
+	/// Close the overlay
+	case close = "CLOSE"
+	/// Open a URL
+	case url = "URL"
+	/// Go to another node
+	case node = "NODE"
+	case updateMediaRuntime = "UPDATE_MEDIA_RUNTIME"
+	case setVariable = "SET_VARIABLE"
+	case setVariableMode = "SET_VARIABLE_MODE"
+	case conditional = "CONDITIONAL"
+}
+
+/// How a node action moves to its destination
+public enum Navigation: String, Codable {
+	/// Replace the current frame with the destination
+	case navigate = "NAVIGATE"
+	/// Replace the current overlay with the destination
+	case swap = "SWAP"
+	/// Open the destination as an overlay
+	case overlay = "OVERLAY"
+	/// Scroll to the destination within the current frame
+	case scrollTo = "SCROLL_TO"
+	/// Change to another variant of the component
+	case changeTo = "CHANGE_TO"
+}
+
+/// [Figma documentation](https://www.figma.com/developers/api#action-type)
+public struct Action: Codable {
+	public let type: ActionType
+	/// URL to open, if URL type
+	public let url: String?
+	/// ID of the node to go to, if NODE type
+	public let destinationId: String?
+	/// How to go to the destination, if NODE type
+	public let navigation: Navigation?
+
+	public init(type: ActionType, url: String?, destinationId: String?, navigation: Navigation?) {
+		self.type = type
+		self.url = url
+		self.destinationId = destinationId
+		self.navigation = navigation
+	}
+}
+
+/// The event that triggers a prototype interaction
+public enum TriggerType: String, Codable {
+	case onClick = "ON_CLICK"
+	case onHover = "ON_HOVER"
+	case onPress = "ON_PRESS"
+	case onDrag = "ON_DRAG"
+	case afterTimeout = "AFTER_TIMEOUT"
+	case mouseEnter = "MOUSE_ENTER"
+	case mouseLeave = "MOUSE_LEAVE"
+	case mouseUp = "MOUSE_UP"
+	case mouseDown = "MOUSE_DOWN"
+	case onKeyDown = "ON_KEY_DOWN"
+	case onKeyUp = "ON_KEY_UP"
+	case onMediaHit = "ON_MEDIA_HIT"
+	case onMediaEnd = "ON_MEDIA_END"
+}
+
+/// [Figma documentation](https://www.figma.com/developers/api#trigger-type)
+public struct Trigger: Codable {
+	public let type: TriggerType
+
+	public init(type: TriggerType) {
+		self.type = type
+	}
+}
+
+/// A prototype interaction
+/// 
+/// [Figma documentation](https://www.figma.com/developers/api#reaction-type)
+public struct Reaction: Codable {
+	/// Deprecated in favour of `actions`
+	public let action: Action?
+	/// The actions performed, in order
+	public let actions: [Action]?
+	public let trigger: Trigger
+
+	public init(action: Action?, actions: [Action]?, trigger: Trigger) {
+		self.action = action
+		self.actions = actions
+		self.trigger = trigger
+	}
+}
+
+/// How a mask hides the layers it masks
+public enum MaskType: String, Codable {
+	/// The mask's opacity, including that of its fills, shows the layers
+	case alpha = "ALPHA"
+	/// The mask's shape shows the layers, ignoring its fills
+	case vector = "VECTOR"
+	/// The brightness of the mask's fills shows the layers
+	case luminance = "LUMINANCE"
+}
+
 /// [Figma documentation](https://www.figma.com/developers/api#rectangle-type)
 public struct Rectangle: Codable {
 	public let x: Double?
@@ -243,6 +440,23 @@ public enum CounterAxisAlignItems: String, Codable {
 	case baseline = "BASELINE"
 }
 
+public enum LayoutWrap: String, Codable {
+	case noWrap = "NO_WRAP"
+	case wrap = "WRAP"
+}
+
+public enum CounterAxisAlignContent: String, Codable {
+	case auto = "AUTO"
+	case spaceBetween = "SPACE_BETWEEN"
+}
+
+public enum GridChildAlign: String, Codable {
+	case auto = "AUTO"
+	case min = "MIN"
+	case center = "CENTER"
+	case max = "MAX"
+}
+
 public enum LayoutPositioning: String, Codable {
 	case absolute = "ABSOLUTE"
 }
@@ -251,6 +465,7 @@ public enum LayoutMode: String, Codable {
 	case none = "NONE"
 	case horizontal = "HORIZONTAL"
 	case vertical = "VERTICAL"
+	case grid = "GRID"
 }
 
 public struct Styles: Codable {
@@ -268,6 +483,7 @@ public struct Styles: Codable {
 }
 
 public enum TextCase: String, Codable {
+	case original = "ORIGINAL"
 	case upper = "UPPER"
 	case lower = "LOWER"
 	case title = "TITLE"
@@ -276,6 +492,7 @@ public enum TextCase: String, Codable {
 }
 
 public enum TextDecoration: String, Codable {
+	case none = "NONE"
 	case strikethrough = "STRIKETHROUGH"
 	case underline = "UNDERLINE"
 }
@@ -287,6 +504,28 @@ public enum TextAutoResize: String, Codable {
 	case truncate = "TRUNCATE"
 }
 
+/// Whether text that doesn't fit is truncated
+public enum TextTruncation: String, Codable {
+	case disabled = "DISABLED"
+	/// Trailing text is replaced with "…"
+	case ending = "ENDING"
+}
+
+/// Horizontal text alignment
+public enum TextAlignHorizontal: String, Codable {
+	case left = "LEFT"
+	case right = "RIGHT"
+	case center = "CENTER"
+	case justified = "JUSTIFIED"
+}
+
+/// Vertical text alignment
+public enum TextAlignVertical: String, Codable {
+	case top = "TOP"
+	case center = "CENTER"
+	case bottom = "BOTTOM"
+}
+
 /// Type of hyperlink
 public enum HyperlinkType: String, Codable {
 	case url = "URL"
@@ -307,14 +546,25 @@ public struct Hyperlink: Codable {
 	}
 }
 
+/// The unit of the line height value specified by the user
+public enum LineHeightUnit: String, Codable {
+	case pixels = "PIXELS"
+	case fontSizePercent = "FONT_SIZE_%"
+	case intrinsicPercent = "INTRINSIC_%"
+}
+
 /// Metadata for character formatting
 /// 
 /// [Figma documentation](https://www.figma.com/developers/api#typestyle-type)
 public struct TypeStyle: Codable {
 	/// Font family of text (standard name)
 	public let fontFamily: String
+	/// PostScript font name
+	public let fontPostScriptName: String?
 	/// Space between paragraphs in px, 0 if not present
 	public let paragraphSpacing: Double?
+	/// Paragraph indentation in px, 0 if not present
+	public let paragraphIndent: Double?
 	/// Whether or not text is italicized
 	public let italic: Bool?
 	/// Numeric font weight
@@ -327,22 +577,105 @@ public struct TypeStyle: Codable {
 	public let textDecoration: TextDecoration?
 	/// Dimensions along which text will auto resize, default is that the text does not auto-resize
 	public let textAutoResize: TextAutoResize?
+	/// Whether text that doesn't fit its bounds is truncated, default is disabled
+	public let textTruncation: TextTruncation?
+	/// The number of lines shown before truncating the text, if truncation is enabled
+	public let maxLines: UInt32?
+	/// Horizontal text alignment as string enum
+	public let textAlignHorizontal: TextAlignHorizontal?
+	/// Vertical text alignment as string enum
+	public let textAlignVertical: TextAlignVertical?
+	/// Space between characters in px
+	public let letterSpacing: Double?
+	/// A map of OpenType feature flags to 1 or 0, 1 if it is enabled and 0 if it is disabled. Note that some flags aren't reflected here. For example, SMCP (small caps) is still represented by the textCase field.
+	public let opentypeFlags: [String: Double]?
 	/// Link to a URL or frame
 	public let hyperlink: Hyperlink?
 	/// Line height in px
 	public let lineHeightPx: Double
-
-	public init(fontFamily: String, paragraphSpacing: Double?, italic: Bool?, fontWeight: Double, fontSize: Double, textCase: TextCase?, textDecoration: TextDecoration?, textAutoResize: TextAutoResize?, hyperlink: Hyperlink?, lineHeightPx: Double) {
+	/// Line height as a percentage of normal line height. This is deprecated; in a future version of the API only lineHeightPx and lineHeightPercentFontSize will be returned.
+	public let lineHeightPercent: Double?
+	/// Line height as a percentage of the font size. Only returned when lineHeightPercent is not 100
+	public let lineHeightPercentFontSize: Double?
+	/// The unit of the line height value specified by the user.
+	public let lineHeightUnit: LineHeightUnit?
+
+	public init(fontFamily: String, fontPostScriptName: String?, paragraphSpacing: Double?, paragraphIndent: Double?, italic: Bool?, fontWeight: Double, fontSize: Double, textCase: TextCase?, textDecoration: TextDecoration?, textAutoResize: TextAutoResize?, textTruncation: TextTruncation?, maxLines: UInt32?, textAlignHorizontal: TextAlignHorizontal?, textAlignVertical: TextAlignVertical?, letterSpacing: Double?, opentypeFlags: [String: Double]?, hyperlink: Hyperlink?, lineHeightPx: Double, lineHeightPercent: Double?, lineHeightPercentFontSize: Double?, lineHeightUnit: LineHeightUnit?) {
 		self.fontFamily = fontFamily
+		self.fontPostScriptName = fontPostScriptName
 		self.paragraphSpacing = paragraphSpacing
+		self.paragraphIndent = paragraphIndent
 		self.italic = italic
 		self.fontWeight = fontWeight
 		self.fontSize = fontSize
 		self.textCase = textCase
 		self.textDecoration = textDecoration
 		self.textAutoResize = textAutoResize
+		self.textTruncation = textTruncation
+		self.maxLines = maxLines
+		self.textAlignHorizontal = textAlignHorizontal
+		self.textAlignVertical = textAlignVertical
+		self.letterSpacing = letterSpacing
+		self.opentypeFlags = opentypeFlags
 		self.hyperlink = hyperlink
 		self.lineHeightPx = lineHeightPx
+		self.lineHeightPercent = lineHeightPercent
+		self.lineHeightPercentFontSize = lineHeightPercentFontSize
+		self.lineHeightUnit = lineHeightUnit
+	}
+}
+
+/// Character formatting that overrides part of a text node's `TypeStyle`. Only the overridden fields are present.
+/// 
+/// [Figma documentation](https://www.figma.com/developers/api#text-props)
+public struct TypeStyleOverride: Codable {
+	/// Font family of text (standard name)
+	public let fontFamily: String?
+	/// Whether or not text is italicized
+	public let italic: Bool?
+	/// Numeric font weight
+	public let fontWeight: Double?
+	/// Font size in px
+	public let fontSize: Double?
+	/// Text casing applied to the characters
+	public let textCase: TextCase?
+	/// Text decoration applied to the characters
+	public let textDecoration: TextDecoration?
+	/// Link to a URL or frame
+	public let hyperlink: Hyperlink?
+	/// Space between characters in px
+	public let letterSpacing: Double?
+	/// A map of OpenType feature flags to 1 or 0
+	public let opentypeFlags: [String: Double]?
+	/// Line height in px
+	public let lineHeightPx: Double?
+	/// Line height as a percentage of the font size
+	public let lineHeightPercentFontSize: Double?
+	/// The unit of the line height value specified by the user.
+	public let lineHeightUnit: LineHeightUnit?
+	/// Paints applied to characters
+	public let fills: [Paint]?
+	/// ID of the fill style the paints come from
+	public let inheritFillStyleId: String?
+	/// ID of the text style the font properties come from
+	public let inheritTextStyleId: String?
+
+	public init(fontFamily: String?, italic: Bool?, fontWeight: Double?, fontSize: Double?, textCase: TextCase?, textDecoration: TextDecoration?, hyperlink: Hyperlink?, letterSpacing: Double?, opentypeFlags: [String: Double]?, lineHeightPx: Double?, lineHeightPercentFontSize: Double?, lineHeightUnit: LineHeightUnit?, fills: [Paint]?, inheritFillStyleId: String?, inheritTextStyleId: String?) {
+		self.fontFamily = fontFamily
+		self.italic = italic
+		self.fontWeight = fontWeight
+		self.fontSize = fontSize
+		self.textCase = textCase
+		self.textDecoration = textDecoration
+		self.hyperlink = hyperlink
+		self.letterSpacing = letterSpacing
+		self.opentypeFlags = opentypeFlags
+		self.lineHeightPx = lineHeightPx
+		self.lineHeightPercentFontSize = lineHeightPercentFontSize
+		self.lineHeightUnit = lineHeightUnit
+		self.fills = fills
+		self.inheritFillStyleId = inheritFillStyleId
+		self.inheritTextStyleId = inheritTextStyleId
 	}
 }
 
@@ -415,6 +748,10 @@ public struct Node: Codable {
 	public let individualStrokeWeights: StrokeWeights?
 	/// Position of stroke relative to vector outline
 	public let strokeAlign: StrokeAlign?
+	/// An array of paths representing the object fill. Only returned if `geometry=paths` is requested.
+	public let fillGeometry: [Path]?
+	/// An array of paths representing the object stroke. Only returned if `geometry=paths` is requested.
+	public let strokeGeometry: [Path]?
 	/// An array of floating point numbers describing the pattern of dash length and gap lengths that the vector path follows. For example a value of [1, 2] indicates that the path has a dash of length 1 followed by a gap of length 2, repeated.
 	public let strokeDashes: [Double]?
 	/// Radius of each corner of the node if a single radius is set for all corners
@@ -425,8 +762,26 @@ public struct Node: Codable {
 	public let transitionDuration: Double?
 	/// The easing curve used in the prototyping transition on this node
 	public let transitionEasing: EasingType?
+	/// Node ID of node to transition to in prototyping. Deprecated in favour of `reactions`.
+	public let transitionNodeID: String?
+	/// The prototype interactions of this node
+	public let reactions: [Reaction]?
 	/// Opacity of the node
 	public let opacity: Double?
+	/// How this node blends with nodes behind it in the scene
+	public let blendMode: BlendMode?
+	/// Whether this node is a mask for the sibling nodes above it
+	public let isMask: Bool?
+	/// How this node masks, if it is a mask
+	public let maskType: MaskType?
+	/// Whether or not this node clips content outside of its bounds
+	public let clipsContent: Bool?
+	/// The transform of the node relative to its parent. Only present if `geometry=paths` is requested.
+	public let relativeTransform: Transform?
+	/// The rotation of the node in radians, anticlockwise, if not 0
+	public let rotation: Double?
+	/// Width and height of the node before it is transformed. Only present if `geometry=paths` is requested.
+	public let size: Vector?
 	/// Bounding box of the node in absolute space coordinates
 	public let absoluteBoundingBox: Rectangle?
 	/// The bounds of the rendered node in the file in absolute space coordinates
@@ -441,6 +796,36 @@ public struct Node: Codable {
 	public let counterAxisAlignItems: CounterAxisAlignItems?
 	/// The distance between children of the frame. Can be negative. This property is only applicable for auto-layout frames.
 	public let itemSpacing: Double?
+	/// Whether the children of a horizontal auto-layout frame wrap onto new lines when there isn't room. This property is only applicable for auto-layout frames.
+	public let layoutWrap: LayoutWrap?
+	/// The distance between wrapped lines of children. Null means it is the same as `itemSpacing`. This property is only applicable for auto-layout frames with wrapping.
+	public let counterAxisSpacing: Double?
+	/// Whether wrapped lines of children are packed together or spread apart. This property is only applicable for auto-layout frames with wrapping.
+	public let counterAxisAlignContent: CounterAxisAlignContent?
+	/// The number of rows of a grid auto-layout frame
+	public let gridRowCount: UInt32?
+	/// The number of columns of a grid auto-layout frame
+	public let gridColumnCount: UInt32?
+	/// The distance between rows of a grid auto-layout frame
+	public let gridRowGap: Double?
+	/// The distance between columns of a grid auto-layout frame
+	public let gridColumnGap: Double?
+	/// The sizes of the rows of a grid auto-layout frame, as a value for CSS grid-template-rows
+	public let gridRowsSizing: String?
+	/// The sizes of the columns of a grid auto-layout frame, as a value for CSS grid-template-columns
+	public let gridColumnsSizing: String?
+	/// The index of the row the node starts in, counting from 0. This property is only applicable for direct children of grid auto-layout frames.
+	public let gridRowAnchorIndex: UInt32?
+	/// The index of the column the node starts in, counting from 0. This property is only applicable for direct children of grid auto-layout frames.
+	public let gridColumnAnchorIndex: UInt32?
+	/// The number of rows the node spans. This property is only applicable for direct children of grid auto-layout frames.
+	public let gridRowSpan: UInt32?
+	/// The number of columns the node spans. This property is only applicable for direct children of grid auto-layout frames.
+	public let gridColumnSpan: UInt32?
+	/// The horizontal alignment of the node within its grid cells. This property is only applicable for direct children of grid auto-layout frames.
+	public let gridChildHorizontalAlign: GridChildAlign?
+	/// The vertical alignment of the node within its grid cells. This property is only applicable for direct children of grid auto-layout frames.
+	public let gridChildVerticalAlign: GridChildAlign?
 	/// Determines whether a layer's size and position should be determined by auto-layout settings or manually adjustable.
 	public let layoutPositioning: LayoutPositioning?
 	/// Whether this layer uses auto-layout to position its children.
@@ -461,14 +846,20 @@ public struct Node: Codable {
 	public let characters: String?
 	/// Style of text including font family and weight
 	public let style: TypeStyle?
+	/// Array with same number of elements as characters in text box, each element is a reference to the styleOverrideTable defined below and maps to the corresponding character in the characters field. Elements with value 0 have the default type style
+	public let characterStyleOverrides: [UInt32]?
+	/// Map from ID to TypeStyle for looking up style overrides
+	public let styleOverrideTable: [String: TypeStyleOverride]?
 	/// Horizontal and vertical layout contraints for node
 	public let constraints: LayoutConstraint?
 	/// Determines if the layer should stretch along the parent’s counter axis. This property is only provided for direct children of auto-layout frames.
 	public let layoutAlign: LayoutAlign?
 	/// This property is applicable only for direct children of auto-layout frames, ignored otherwise. Determines whether a layer should stretch along the parent’s primary axis. A 0 corresponds to a fixed size and 1 corresponds to stretch
 	public let layoutGrow: Double?
+	/// ID of component that this instance came from, refers to components table
+	public let componentId: String?
 
-	public init(id: String, name: String, visible: Bool?, type: NodeType, children: [Node]?, backgroundColor: Color?, fills: [Paint]?, strokes: [Paint]?, strokeWeight: Double?, individualStrokeWeights: StrokeWeights?, strokeAlign: StrokeAlign?, strokeDashes: [Double]?, cornerRadius: Double?, rectangleCornerRadii: [Double]?, transitionDuration: Double?, transitionEasing: EasingType?, opacity: Double?, absoluteBoundingBox: Rectangle?, absoluteRenderBounds: Rectangle?, primaryAxisSizingMode: AxisSizingMode?, counterAxisSizingMode: AxisSizingMode?, primaryAxisAlignItems: PrimaryAxisAlignItems?, counterAxisAlignItems: CounterAxisAlignItems?, itemSpacing: Double?, layoutPositioning: LayoutPositioning?, layoutMode: LayoutMode?, paddingLeft: Double?, paddingRight: Double?, paddingTop: Double?, paddingBottom: Double?, effects: [Effect]?, styles: Styles?, characters: String?, style: TypeStyle?, constraints: LayoutConstraint?, layoutAlign: LayoutAlign?, layoutGrow: Double?) {
+	public init(id: String, name: String, visible: Bool?, type: NodeType, children: [Node]?, backgroundColor: Color?, fills: [Paint]?, strokes: [Paint]?, strokeWeight: Double?, individualStrokeWeights: StrokeWeights?, strokeAlign: StrokeAlign?, fillGeometry: [Path]?, strokeGeometry: [Path]?, strokeDashes: [Double]?, cornerRadius: Double?, rectangleCornerRadii: [Double]?, transitionDuration: Double?, transitionEasing: EasingType?, transitionNodeID: String?, reactions: [Reaction]?, opacity: Double?, blendMode: BlendMode?, isMask: Bool?, maskType: MaskType?, clipsContent: Bool?, relativeTransform: Transform?, rotation: Double?, size: Vector?, absoluteBoundingBox: Rectangle?, absoluteRenderBounds: Rectangle?, primaryAxisSizingMode: AxisSizingMode?, counterAxisSizingMode: AxisSizingMode?, primaryAxisAlignItems: PrimaryAxisAlignItems?, counterAxisAlignItems: CounterAxisAlignItems?, itemSpacing: Double?, layoutWrap: LayoutWrap?, counterAxisSpacing: Double?, counterAxisAlignContent: CounterAxisAlignContent?, gridRowCount: UInt32?, gridColumnCount: UInt32?, gridRowGap: Double?, gridColumnGap: Double?, gridRowsSizing: String?, gridColumnsSizing: String?, gridRowAnchorIndex: UInt32?, gridColumnAnchorIndex: UInt32?, gridRowSpan: UInt32?, gridColumnSpan: UInt32?, gridChildHorizontalAlign: GridChildAlign?, gridChildVerticalAlign: GridChildAlign?, layoutPositioning: LayoutPositioning?, layoutMode: LayoutMode?, paddingLeft: Double?, paddingRight: Double?, paddingTop: Double?, paddingBottom: Double?, effects: [Effect]?, styles: Styles?, characters: String?, style: TypeStyle?, characterStyleOverrides: [UInt32]?, styleOverrideTable: [String: TypeStyleOverride]?, constraints: LayoutConstraint?, layoutAlign: LayoutAlign?, layoutGrow: Double?, componentId: String?) {
 		self.id = id
 		self.name = name
 		self.visible = visible
@@ -480,12 +871,23 @@ public struct Node: Codable {
 		self.strokeWeight = strokeWeight
 		self.individualStrokeWeights = individualStrokeWeights
 		self.strokeAlign = strokeAlign
+		self.fillGeometry = fillGeometry
+		self.strokeGeometry = strokeGeometry
 		self.strokeDashes = strokeDashes
 		self.cornerRadius = cornerRadius
 		self.rectangleCornerRadii = rectangleCornerRadii
 		self.transitionDuration = transitionDuration
 		self.transitionEasing = transitionEasing
+		self.transitionNodeID = transitionNodeID
+		self.reactions = reactions
 		self.opacity = opacity
+		self.blendMode = blendMode
+		self.isMask = isMask
+		self.maskType = maskType
+		self.clipsContent = clipsContent
+		self.relativeTransform = relativeTransform
+		self.rotation = rotation
+		self.size = size
 		self.absoluteBoundingBox = absoluteBoundingBox
 		self.absoluteRenderBounds = absoluteRenderBounds
 		self.primaryAxisSizingMode = primaryAxisSizingMode
@@ -493,6 +895,21 @@ public struct Node: Codable {
 		self.primaryAxisAlignItems = primaryAxisAlignItems
 		self.counterAxisAlignItems = counterAxisAlignItems
 		self.itemSpacing = itemSpacing
+		self.layoutWrap = layoutWrap
+		self.counterAxisSpacing = counterAxisSpacing
+		self.counterAxisAlignContent = counterAxisAlignContent
+		self.gridRowCount = gridRowCount
+		self.gridColumnCount = gridColumnCount
+		self.gridRowGap = gridRowGap
+		self.gridColumnGap = gridColumnGap
+		self.gridRowsSizing = gridRowsSizing
+		self.gridColumnsSizing = gridColumnsSizing
+		self.gridRowAnchorIndex = gridRowAnchorIndex
+		self.gridColumnAnchorIndex = gridColumnAnchorIndex
+		self.gridRowSpan = gridRowSpan
+		self.gridColumnSpan = gridColumnSpan
+		self.gridChildHorizontalAlign = gridChildHorizontalAlign
+		self.gridChildVerticalAlign = gridChildVerticalAlign
 		self.layoutPositioning = layoutPositioning
 		self.layoutMode = layoutMode
 		self.paddingLeft = paddingLeft
@@ -503,9 +920,12 @@ public struct Node: Codable {
 		self.styles = styles
 		self.characters = characters
 		self.style = style
+		self.characterStyleOverrides = characterStyleOverrides
+		self.styleOverrideTable = styleOverrideTable
 		self.constraints = constraints
 		self.layoutAlign = layoutAlign
 		self.layoutGrow = layoutGrow
+		self.componentId = componentId
 	}
 }
 
@@ -536,14 +956,17 @@ public struct Style: Codable {
 public struct File: Codable {
 	public let document: Node
 	public let components: [String: Component]
+	/// Files exported before component sets existed don't have any
+	public let componentSets: [String: ComponentSet]?
 	public let styles: [String: Style]
 	public let name: String
 	public let schemaVersion: UInt8
 	public let version: String
 
-	public init(document: Node, components: [String: Component], styles: [String: Style], name: String, schemaVersion: UInt8, version: String) {
+	public init(document: Node, components: [String: Component], componentSets: [String: ComponentSet]?, styles: [String: Style], name: String, schemaVersion: UInt8, version: String) {
 		self.document = document
 		self.components = components
+		self.componentSets = componentSets
 		self.styles = styles
 		self.name = name
 		self.schemaVersion = schemaVersion
@@ -0,0 +1,356 @@
+use std::fmt::Display;
+
+use lightningcss::{traits::Parse, values::color::CssColor};
+
+use crate::intermediate_node::{CSSVariablesMap, IntermediateNode, IntermediateNodeType};
+
+/// Text whose contrast with its background is below what WCAG AA requires
+#[derive(Debug)]
+pub struct ContrastWarning {
+    pub figma_id: String,
+    pub figma_name: String,
+    pub color: String,
+    pub background: String,
+    pub ratio: f64,
+    pub minimum: f64,
+}
+
+impl Display for ContrastWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Node {} ({}) has a contrast ratio of {:.2}:1 between {} and {}, below WCAG AA's {}:1",
+            self.figma_id, self.figma_name, self.ratio, self.color, self.background, self.minimum
+        )
+    }
+}
+
+/// A colour and the CSS it came from
+#[derive(Clone, Copy)]
+struct Color<'a> {
+    css: &'a str,
+    rgb: [f64; 3],
+}
+
+/// Widget roles for `div`s, by the first word of their name
+const WIDGET_ROLES: &[(&str, &str)] = &[
+    ("alert", "alert"),
+    ("checkbox", "checkbox"),
+    ("dialog", "dialog"),
+    ("modal", "dialog"),
+    ("progress", "progressbar"),
+    ("radio", "radio"),
+    ("slider", "slider"),
+    ("switch", "switch"),
+    ("tab", "tab"),
+    ("tabs", "tablist"),
+    ("toggle", "switch"),
+    ("tooltip", "tooltip"),
+];
+
+/// Roles of widgets that are interacted with, rather than containing them
+const INTERACTIVE_ROLES: &[&str] = &["checkbox", "radio", "slider", "switch", "tab"];
+
+fn is_interactive(node: &IntermediateNode<'_>) -> bool {
+    node.href.is_some()
+        || matches!(node.element.as_deref(), Some("button" | "input"))
+        || node
+            .accessibility
+            .role
+            .as_deref()
+            .is_some_and(|role| INTERACTIVE_ROLES.contains(&role))
+}
+
+fn figma_name<'a>(node: &'a IntermediateNode<'_>) -> Option<&'a str> {
+    node.figma.as_ref().map(|f| f.name.as_ref())
+}
+
+/// Resolve the CSS variables in a colour to their values
+fn resolve_variable<'a>(value: &'a str, css_variables: &'a CSSVariablesMap) -> &'a str {
+    value
+        .strip_prefix("var(")
+        .and_then(|v| v.strip_suffix(')'))
+        .and_then(|name| css_variables.values().find(|v| v.name == name))
+        .and_then(|v| v.value.as_deref())
+        .unwrap_or(value)
+}
+
+/// Parse a solid colour, blended onto the background if it is translucent
+fn parse_color<'a>(
+    css: &'a str,
+    background: [f64; 3],
+    css_variables: &CSSVariablesMap,
+) -> Option<Color<'a>> {
+    let color = CssColor::parse_string(resolve_variable(css, css_variables)).ok()?;
+    if color == CssColor::CurrentColor {
+        return None;
+    }
+    let CssColor::RGBA(rgba) = color.to_rgb() else {
+        return None;
+    };
+    let alpha = f64::from(rgba.alpha) / 255.0;
+    let blend = |c: u8, b: f64| f64::from(c) / 255.0 * alpha + b * (1.0 - alpha);
+    Some(Color {
+        css,
+        rgb: [
+            blend(rgba.red, background[0]),
+            blend(rgba.green, background[1]),
+            blend(rgba.blue, background[2]),
+        ],
+    })
+}
+
+/// [Relative luminance](https://www.w3.org/TR/WCAG21/#dfn-relative-luminance)
+fn relative_luminance([r, g, b]: [f64; 3]) -> f64 {
+    let linear = |c: f64| {
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// [Contrast ratio](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio)
+fn contrast_ratio(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// The contrast WCAG AA requires. Large text is at least 18pt, or 14pt if bold.
+fn minimum_contrast(node: &IntermediateNode<'_>) -> f64 {
+    let figma = node.figma.as_ref();
+    let size = figma.and_then(|f| f.font_size).unwrap_or(0.0);
+    let weight = figma.and_then(|f| f.font_weight).unwrap_or(400.0);
+    if size >= 24.0 || size >= 18.66 && weight >= 700.0 {
+        3.0
+    } else {
+        4.5
+    }
+}
+
+/// Whether the first `[x, y, width, height]` box contains the second
+fn contains(outer: [f64; 4], inner: [f64; 4]) -> bool {
+    let [x, y, width, height] = outer;
+    let [inner_x, inner_y, inner_width, inner_height] = inner;
+    x <= inner_x
+        && y <= inner_y
+        && inner_x + inner_width <= x + width
+        && inner_y + inner_height <= y + height
+}
+
+/**
+Describe the nodes for assistive technology.
+
+Vectors are decorative and hidden, unless they are a link themselves, in which
+case their name becomes their title. Links and buttons without text are
+labelled with their name. `div`s named after a widget, such as `Checkbox`, get
+its role.
+ */
+pub fn annotate_accessibility(node: &mut IntermediateNode<'_>) {
+    annotate(node, false)
+}
+
+fn annotate(node: &mut IntermediateNode<'_>, in_interactive: bool) {
+    if node.element.is_none() && node.href.is_none() && node.accessibility.role.is_none() {
+        let first_word = figma_name(node)
+            .and_then(|name| name.split(|c: char| !c.is_alphanumeric()).next())
+            .unwrap_or("")
+            .to_lowercase();
+        node.accessibility.role = WIDGET_ROLES
+            .iter()
+            .find(|(word, _)| *word == first_word)
+            .map(|(_, role)| role.to_string());
+    }
+    let interactive = is_interactive(node);
+    let name = figma_name(node).map(str::to_string);
+
+    match &mut node.node_type {
+        IntermediateNodeType::Vector { .. } => {
+            if interactive && !in_interactive {
+                node.accessibility.title = name;
+                node.accessibility.role = Some("img".to_string());
+            } else {
+                node.accessibility.hidden = true;
+            }
+        }
+        IntermediateNodeType::Frame { children } => {
+            for child in children.iter_mut() {
+                annotate(child, in_interactive || interactive);
+            }
+            if interactive && node.first_text().is_none() && node.accessibility.label.is_none() {
+                node.accessibility.label = name;
+            }
+        }
+        IntermediateNodeType::Text { .. } => {}
+    }
+}
+
+/**
+Find text that doesn't contrast enough with its background for WCAG AA.
+
+The background is the nearest ancestor's, or white if none have one. An
+absolutely positioned sibling drawn below the text, whose box contains the
+text's box, is taken to be its background too. Text on backgrounds that aren't a solid colour, such as
+gradients, isn't checked.
+ */
+pub fn contrast_warnings(
+    node: &IntermediateNode<'_>,
+    css_variables: &CSSVariablesMap,
+) -> Vec<ContrastWarning> {
+    let mut warnings = vec![];
+    let white = Color {
+        css: "#fff",
+        rgb: [1.0, 1.0, 1.0],
+    };
+    let black = Color {
+        css: "#000",
+        rgb: [0.0, 0.0, 0.0],
+    };
+    check_contrast(node, Some(black), Some(white), css_variables, &mut warnings);
+    warnings
+}
+
+/// The colour and background are None if they aren't known
+fn check_contrast<'a>(
+    node: &'a IntermediateNode<'_>,
+    mut color: Option<Color<'a>>,
+    mut background: Option<Color<'a>>,
+    css_variables: &CSSVariablesMap,
+    warnings: &mut Vec<ContrastWarning>,
+) {
+    if let Some(css) = node.frame_appearance.background.as_deref() {
+        background = background.and_then(|b| parse_color(css, b.rgb, css_variables));
+    }
+    if let Some(css) = node.appearance.color.as_deref() {
+        color = background.and_then(|b| parse_color(css, b.rgb, css_variables));
+    }
+
+    match &node.node_type {
+        IntermediateNodeType::Frame { children } => {
+            // Absolutely positioned siblings are often backgrounds for the
+            // siblings drawn over them
+            let mut sibling_backgrounds: Vec<([f64; 4], &str)> = vec![];
+            for child in children.iter() {
+                let bounds = child.figma.as_ref().and_then(|f| f.bounds);
+                let child_background = sibling_backgrounds
+                    .iter()
+                    .filter(|(sibling_bounds, _)| {
+                        bounds.is_some_and(|bounds| contains(*sibling_bounds, bounds))
+                    })
+                    .fold(background, |background, (_, css)| {
+                        background.and_then(|b| parse_color(css, b.rgb, css_variables))
+                    });
+                check_contrast(child, color, child_background, css_variables, warnings);
+                if let (Some(_), Some(bounds), Some(css)) = (
+                    child.location.inset.as_ref(),
+                    bounds,
+                    child.frame_appearance.background.as_deref(),
+                ) {
+                    sibling_backgrounds.push((bounds, css));
+                }
+            }
+        }
+        IntermediateNodeType::Text { runs, .. } => {
+            let Some(background) = background else {
+                return;
+            };
+            let run_colors = runs
+                .iter()
+                .filter_map(|run| run.appearance.color.as_deref())
+                .filter_map(|css| parse_color(css, background.rgb, css_variables));
+            for color in color.into_iter().chain(run_colors) {
+                let ratio = contrast_ratio(color.rgb, background.rgb);
+                let minimum = minimum_contrast(node);
+                if ratio < minimum {
+                    warnings.push(ContrastWarning {
+                        figma_id: node
+                            .figma
+                            .as_ref()
+                            .map(|f| f.id.to_string())
+                            .unwrap_or_default(),
+                        figma_name: figma_name(node).unwrap_or("").to_string(),
+                        color: color.css.to_string(),
+                        background: background.css.to_string(),
+                        ratio,
+                        minimum,
+                    });
+                }
+            }
+        }
+        IntermediateNodeType::Vector { .. } => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{contrast_ratio, contrast_warnings};
+    use crate::{figma_node_to_intermediate_node, ConversionOptions};
+
+    /// Black text beside, or on, a black box in a white frame
+    fn warnings_with_box(box_bounds: [f64; 4]) -> Vec<String> {
+        let [x, y, width, height] = box_bounds;
+        let black = serde_json::json!([{ "type": "SOLID", "color": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 1.0 } }]);
+        let node: figma_schema::Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Card",
+            "type": "FRAME",
+            "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 200.0, "height": 100.0 },
+            "fills": [{ "type": "SOLID", "color": { "r": 1.0, "g": 1.0, "b": 1.0, "a": 1.0 } }],
+            "children": [
+                {
+                    "id": "1:2",
+                    "name": "Box",
+                    "type": "RECTANGLE",
+                    "absoluteBoundingBox": { "x": x, "y": y, "width": width, "height": height },
+                    "constraints": { "vertical": "TOP", "horizontal": "LEFT" },
+                    "fills": black
+                },
+                {
+                    "id": "1:3",
+                    "name": "Label",
+                    "type": "TEXT",
+                    "characters": "Label",
+                    "absoluteBoundingBox": { "x": 10.0, "y": 40.0, "width": 100.0, "height": 20.0 },
+                    "constraints": { "vertical": "TOP", "horizontal": "LEFT" },
+                    "fills": black,
+                    "style": {
+                        "fontFamily": "Inter",
+                        "fontWeight": 400.0,
+                        "fontSize": 16.0,
+                        "lineHeightPx": 20.0
+                    }
+                }
+            ]
+        }))
+        .unwrap();
+        let mut css_variables = Default::default();
+        let intermediate_node = figma_node_to_intermediate_node(
+            &node,
+            &mut css_variables,
+            &ConversionOptions::default(),
+        );
+        contrast_warnings(&intermediate_node, &css_variables)
+            .into_iter()
+            .map(|warning| warning.figma_id)
+            .collect()
+    }
+
+    #[test]
+    fn sibling_backgrounds_must_cover_the_text() {
+        assert!(warnings_with_box([180.0, 0.0, 20.0, 20.0]).is_empty());
+        assert!(warnings_with_box([0.0, 30.0, 200.0, 20.0]).is_empty());
+        assert_eq!(warnings_with_box([0.0, 30.0, 200.0, 40.0]), ["1:3"]);
+    }
+
+    #[test]
+    fn contrast_ratios() {
+        assert_eq!(contrast_ratio([0.0; 3], [1.0; 3]), 21.0);
+        assert_eq!(contrast_ratio([1.0; 3], [1.0; 3]), 1.0);
+        // #767676 is the lightest grey that passes AA on white
+        let grey = 118.0 / 255.0;
+        let ratio = contrast_ratio([grey; 3], [1.0; 3]);
+        assert!((4.5..4.6).contains(&ratio), "{ratio}");
+    }
+}
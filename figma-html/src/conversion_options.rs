@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use crate::FontMapping;
+
+/// Options for converting Figma nodes into intermediate nodes
+#[derive(Debug)]
+pub struct ConversionOptions {
+    /// Prepended to image refs to make the URLs of image fills
+    pub image_url_prefix: String,
+    /// Where nodes are exported, by node id. Hyperlinks and prototype
+    /// interactions going to these nodes link to them.
+    pub node_links: HashMap<String, NodeLink>,
+    /// How Figma fonts are named in CSS
+    pub fonts: FontMapping,
+}
+
+/// Where a node is exported, for linking to it
+#[derive(Debug, Clone)]
+pub struct NodeLink {
+    /// URL of the page the node is on
+    pub page: String,
+    /// The `id` of the node's element, if the node isn't the whole page
+    pub anchor: Option<String>,
+}
+
+impl NodeLink {
+    pub fn href(&self) -> String {
+        match self.anchor.as_deref() {
+            Some(anchor) => format!("{}#{anchor}", self.page),
+            None => self.page.clone(),
+        }
+    }
+}
+
+impl Default for ConversionOptions {
+    fn default() -> Self {
+        Self {
+            image_url_prefix: "images/".to_string(),
+            node_links: HashMap::new(),
+            fonts: FontMapping::default(),
+        }
+    }
+}
+
+impl ConversionOptions {
+    pub fn image_url(&self, image_ref: &str) -> String {
+        format!("{}{}", self.image_url_prefix, image_ref)
+    }
+
+    /// The URL of an exported node
+    pub fn node_href(&self, node_id: &str) -> Option<String> {
+        self.node_links.get(node_id).map(NodeLink::href)
+    }
+}
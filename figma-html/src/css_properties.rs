@@ -1,28 +1,60 @@
-use std::cmp::Ordering;
+use std::{borrow::Cow, cmp::Ordering};
 
-use figma_schema::{Color, EffectType, Node, NodeType, Rectangle, TextCase};
+use figma_schema::{
+    ActionType, BlendMode, Color, Effect, EffectType, Hyperlink, LineHeightUnit, Navigation, Node,
+    NodeType, Paint, PaintType, Rectangle, TextAutoResize, TextCase, TextTruncation, Transform,
+    TriggerType, TypeStyle, Vector,
+};
 
-use super::CSSVariablesMap;
+use super::{
+    intermediate_node::{Font, Length, LengthUnit, LineHeight},
+    CSSVariablesMap, ConversionOptions, FigmaFont, FontMapping,
+};
+
+mod gradient;
+mod image;
+mod mask;
+
+pub use mask::{mask_clip_path, mask_layers};
 
 /// Get values for given CSS properties
 ///
 /// The CSS values are not optimized, but can be made so by use of another tool like `lightningcss`.
 pub trait CssProperties {
-    fn background(&self, css_variables: &mut CSSVariablesMap) -> Option<String>;
+    fn background(
+        &self,
+        css_variables: &mut CSSVariablesMap,
+        options: &ConversionOptions,
+    ) -> Option<String>;
     fn box_shadow(&self) -> Option<String>;
-    fn font(&self, css_variables: &mut CSSVariablesMap) -> Option<String>;
+    fn filter(&self) -> Option<String>;
+    fn backdrop_filter(&self) -> Option<String>;
+    fn font(
+        &self,
+        css_variables: &mut CSSVariablesMap,
+        options: &ConversionOptions,
+    ) -> Option<Font>;
 }
 
 pub fn fills_color(node: &Node, css_variables: &mut CSSVariablesMap) -> Option<String> {
-    let color_value = node
-        .fills()
+    let color_value = paints_color(node.fills())?;
+    style_value(
+        css_variables,
+        node.styles.as_ref().and_then(|s| s.fill.as_deref()),
+        color_value,
+    )
+}
+
+/// The color of the first visible solid paint, including the paint's opacity
+pub fn paints_color(paints: &[Paint]) -> Option<String> {
+    paints
         .iter()
         .filter(|paint| paint.visible() && paint.opacity() != 0.0)
-        .flat_map(|paint| paint.color())
+        .flat_map(|paint| paint.color_with_opacity())
         .flat_map(|c| c.to_option_rgb_string())
         .next()
         .or_else(|| {
-            node.fills()
+            paints
                 .iter()
                 .filter(|paint| paint.visible())
                 .flat_map(|paint| paint.color())
@@ -34,25 +66,323 @@ pub fn fills_color(node: &Node, css_variables: &mut CSSVariablesMap) -> Option<S
                     .to_rgb_string()
                 })
                 .next()
-        })?;
+        })
+}
 
-    match node.styles.as_ref().and_then(|s| s.fill.as_deref()) {
+/// Use the style's CSS variable in place of the value if there is one
+pub fn style_value(
+    css_variables: &mut CSSVariablesMap,
+    style_ref: Option<&str>,
+    value: String,
+) -> Option<String> {
+    match style_ref {
         Some(s_ref) => match css_variables.get_mut(s_ref) {
             Some(v) => {
-                v.value = Some(color_value);
+                v.value = Some(value);
                 Some(format!("var({})", v.name))
             }
-            None => Some(color_value),
+            None => Some(value),
         },
-        None => Some(color_value),
+        None => Some(value),
+    }
+}
+
+/// Use the text style's CSS variable for the font if there is one
+pub fn style_font(
+    css_variables: &mut CSSVariablesMap,
+    style_ref: Option<&str>,
+    font: Font,
+) -> Font {
+    match style_ref.and_then(|s_ref| css_variables.get_mut(s_ref)) {
+        Some(v) => {
+            v.value = Some(font.to_string());
+            Font {
+                variable: Some(v.name.clone()),
+                ..font
+            }
+        }
+        None => font,
     }
 }
 
+/// The CSS font for a type style, with its family and weight mapped
+pub fn type_style_font(font_style: &TypeStyle, fonts: &FontMapping) -> Font {
+    let font = FigmaFont::from_type_style(font_style);
+    Font {
+        italic: matches!(font_style.italic, Some(true)),
+        small_caps: matches!(
+            font_style.text_case,
+            Some(TextCase::SmallCaps | TextCase::SmallCapsForced)
+        ),
+        weight: fonts.font_weight(&font),
+        size: Length::Value {
+            unit: LengthUnit::Px,
+            value: font_style.font_size,
+        },
+        line_height: type_style_line_height(font_style),
+        family: fonts.font_family(&font),
+        variable: None,
+    }
+}
+
+/**
+The line height in the unit the designer chose.
+
+A percentage of the font size is unitless so that it scales with the font. CSS
+can't express a percentage of the font's intrinsic line height other than 100%
+(`normal`), so other values use the pixel height.
+ */
+fn type_style_line_height(font_style: &TypeStyle) -> LineHeight {
+    match (
+        font_style.line_height_unit,
+        font_style.line_height_percent_font_size,
+        font_style.line_height_percent,
+    ) {
+        (Some(LineHeightUnit::FontSizePercent), Some(percent), _) => {
+            LineHeight::Number(percent / 100.0)
+        }
+        (Some(LineHeightUnit::IntrinsicPercent), _, None | Some(100.0)) => LineHeight::Normal,
+        _ => LineHeight::Length(Length::Value {
+            unit: LengthUnit::Px,
+            value: font_style.line_height_px,
+        }),
+    }
+}
+
+/// The OpenType feature flags as a `font-feature-settings` value
+pub fn type_style_font_feature_settings(font_style: &TypeStyle) -> Option<String> {
+    let flags = font_style.opentype_flags.as_ref()?;
+    if flags.is_empty() {
+        return None;
+    }
+    Some(
+        flags
+            .iter()
+            .map(|(tag, value)| format!(r#""{}" {value}"#, tag.to_lowercase()))
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/**
+The number of lines a text node is cut short at with an ellipsis, if it is
+truncated.
+
+Without `maxLines` it is as many lines as fit in the node's height.
+ */
+pub fn text_max_lines(node: &Node, parent: Option<&Node>) -> Option<u32> {
+    let style = node.style.as_ref()?;
+    if !matches!(style.text_truncation, Some(TextTruncation::Ending))
+        && !matches!(style.text_auto_resize, Some(TextAutoResize::Truncate))
+    {
+        return None;
+    }
+    style.max_lines.or_else(|| {
+        let height = layout_box(node, parent)?.height?;
+        // Allow for rounding errors in the height
+        let lines = (height / style.line_height_px + 0.01).floor();
+        Some((lines as u32).max(1))
+    })
+}
+
+/// Where a hyperlink points. Links to nodes that aren't exported point to `#`.
+pub fn hyperlink_href<'a>(
+    hyperlink: &'a Hyperlink,
+    options: &ConversionOptions,
+) -> Option<Cow<'a, str>> {
+    if let Some(url) = hyperlink.url.as_deref() {
+        return Some(Cow::Borrowed(url));
+    }
+    let node_id = hyperlink.node_id.as_deref()?;
+    Some(
+        options
+            .node_href(node_id)
+            .map_or(Cow::Borrowed("#"), Cow::Owned),
+    )
+}
+
+/**
+Where a click on the node goes in the prototype, if it goes to a URL or an
+exported node.
+
+Interactions that change the component's variant, open overlays or go to nodes
+that aren't exported are left out, as a link can't show them.
+ */
+pub fn prototype_href<'a>(node: &'a Node, options: &ConversionOptions) -> Option<Cow<'a, str>> {
+    let reaction_href = node
+        .reactions()
+        .iter()
+        .filter(|reaction| reaction.trigger.r#type == TriggerType::OnClick)
+        .flat_map(|reaction| reaction.actions())
+        .find_map(|action| match action.r#type {
+            ActionType::Url => action.url.as_deref().map(Cow::Borrowed),
+            ActionType::Node
+                if matches!(
+                    action.navigation,
+                    None | Some(Navigation::Navigate | Navigation::ScrollTo)
+                ) =>
+            {
+                action
+                    .destination_id
+                    .as_deref()
+                    .and_then(|id| options.node_href(id))
+                    .map(Cow::Owned)
+            }
+            _ => None,
+        });
+    if node.reactions.is_some() {
+        return reaction_href;
+    }
+    node.transition_node_id
+        .as_deref()
+        .and_then(|id| options.node_href(id))
+        .map(Cow::Owned)
+}
+
+/**
+Get the fills as a CSS background value.
+
+A single solid fill is the same as `fills_color`. Otherwise the fills are
+converted to layers. Figma lists fills bottom to top, whereas CSS lists
+backgrounds top to bottom. Only the bottom CSS layer may be a plain color, so
+solid fills above it are converted into single color gradients.
+ */
+pub fn fills_background(
+    node: &Node,
+    css_variables: &mut CSSVariablesMap,
+    options: &ConversionOptions,
+) -> Option<String> {
+    let visible_fills = visible_fills(node);
+    if visible_fills.len() <= 1 && visible_fills.iter().all(|p| p.r#type == PaintType::Solid) {
+        return fills_color(node, css_variables);
+    }
+
+    let mut layers = fill_layers(node, options);
+    if let (Some((bottom_layer, _)), Some(PaintType::Solid)) =
+        (layers.last_mut(), visible_fills.first().map(|p| p.r#type))
+    {
+        if let Some(color) = visible_fills[0]
+            .color_with_opacity()
+            .and_then(|c| c.to_option_rgb_string())
+        {
+            *bottom_layer = color;
+        }
+    }
+    if layers.is_empty() {
+        return None;
+    }
+    style_value(
+        css_variables,
+        node.styles.as_ref().and_then(|s| s.fill.as_deref()),
+        layers
+            .into_iter()
+            .map(|(layer, _)| layer)
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/**
+The blend modes of the fill layers, as a `background-blend-mode` value.
+
+Fills blend with the fills below them. CSS can't blend the bottom fill with
+what is behind the node, so its blend mode has no effect.
+ */
+pub fn fills_background_blend_mode(node: &Node, options: &ConversionOptions) -> Option<String> {
+    let layers = fill_layers(node, options);
+    if layers.len() <= 1
+        || layers
+            .iter()
+            .all(|(_, p)| p.blend_mode.and_then(blend_mode_value).is_none())
+    {
+        return None;
+    }
+    Some(
+        layers
+            .iter()
+            .map(|(_, p)| p.blend_mode.and_then(blend_mode_value).unwrap_or("normal"))
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+fn visible_fills(node: &Node) -> Vec<&Paint> {
+    node.fills()
+        .iter()
+        .filter(|paint| paint.visible() && paint.opacity() != 0.0)
+        .collect()
+}
+
+/// The fills as CSS image layers, top first, with their paints
+fn fill_layers<'a>(node: &'a Node, options: &ConversionOptions) -> Vec<(String, &'a Paint)> {
+    let size = absolute_bounding_box(node)
+        .and_then(|b| Some((b.width?, b.height?)))
+        .unwrap_or((1.0, 1.0));
+    visible_fills(node)
+        .iter()
+        .rev()
+        .filter_map(|paint| {
+            let layer = match paint.r#type {
+                PaintType::Solid => paint
+                    .color_with_opacity()
+                    .and_then(|c| c.to_option_rgb_string())
+                    .map(|c| format!("linear-gradient({c}, {c})")),
+                PaintType::GradientLinear
+                | PaintType::GradientRadial
+                | PaintType::GradientAngular
+                | PaintType::GradientDiamond => gradient::gradient(paint, size),
+                PaintType::Image => image::image(paint, options),
+            }?;
+            Some((layer, *paint))
+        })
+        .collect()
+}
+
+/// The CSS equivalent of a blend mode, or `None` for normal blending. CSS has
+/// no linear burn or dodge, so the nearest modes are used instead.
+pub fn blend_mode_value(blend_mode: BlendMode) -> Option<&'static str> {
+    match blend_mode {
+        BlendMode::PassThrough | BlendMode::Normal => None,
+        BlendMode::Darken => Some("darken"),
+        BlendMode::Multiply | BlendMode::LinearBurn => Some("multiply"),
+        BlendMode::ColorBurn => Some("color-burn"),
+        BlendMode::Lighten => Some("lighten"),
+        BlendMode::Screen | BlendMode::LinearDodge => Some("screen"),
+        BlendMode::ColorDodge => Some("color-dodge"),
+        BlendMode::Overlay => Some("overlay"),
+        BlendMode::SoftLight => Some("soft-light"),
+        BlendMode::HardLight => Some("hard-light"),
+        BlendMode::Difference => Some("difference"),
+        BlendMode::Exclusion => Some("exclusion"),
+        BlendMode::Hue => Some("hue"),
+        BlendMode::Saturation => Some("saturation"),
+        BlendMode::Color => Some("color"),
+        BlendMode::Luminosity => Some("luminosity"),
+    }
+}
+
+/**
+Whether the node's children are isolated from what is behind it, when they
+would otherwise blend with it.
+
+A group or frame with pass through blending lets its children blend with what
+is behind it, like a `div` does. One with normal blending keeps them to itself.
+ */
+pub fn isolates_children(node: &Node) -> bool {
+    fn blends_through(node: &Node) -> bool {
+        match node.blend_mode {
+            Some(BlendMode::PassThrough) => node.enabled_children().any(blends_through),
+            blend_mode => blend_mode.and_then(blend_mode_value).is_some(),
+        }
+    }
+    node.blend_mode == Some(BlendMode::Normal) && node.enabled_children().any(blends_through)
+}
+
 pub fn stroke_color(node: &Node) -> Option<String> {
     node.strokes()
         .iter()
         .filter(|p| p.visible() && p.opacity() != 0.0)
-        .flat_map(|stroke| stroke.color())
+        .flat_map(|stroke| stroke.color_with_opacity())
         .flat_map(|color| color.to_option_rgb_string())
         .next()
 }
@@ -91,34 +421,181 @@ pub fn absolute_bounding_box(node: &Node) -> Option<Rectangle> {
     })
 }
 
+/**
+The linear part of the node's transform relative to its parent, if it is
+rotated, flipped or skewed.
+
+Nodes whose size before the transform isn't known aren't treated as
+transformed. Without `geometry=paths` the size is worked out from the bounding
+box, which isn't possible when the transform makes it ambiguous, as at 45°.
+ */
+pub fn node_transform(node: &Node) -> Option<Transform> {
+    let transform = match (node.relative_transform.as_ref(), node.rotation) {
+        (Some(transform), _) => transform.linear(),
+        (None, Some(rotation)) => Transform::rotation(rotation),
+        (None, None) => return None,
+    };
+    if transform.is_identity() {
+        return None;
+    }
+    untransformed_size(node, &transform).map(|_| transform)
+}
+
+/// The size of the node before the transform, which is relative to the page
+fn untransformed_size(node: &Node, transform: &Transform) -> Option<(f64, f64)> {
+    if let Some(size) = node.size.as_ref() {
+        return Some((size.x, size.y));
+    }
+    let bounds = absolute_bounding_box(node)?;
+    let (width, height) = (bounds.width?, bounds.height?);
+    // The bounding box's size is the sum of the transformed sides' extents
+    let [[a, c, _], [b, d, _]] = transform.0.map(|row| row.map(f64::abs));
+    let determinant = a * d - b * c;
+    if determinant.abs() < 0.01 {
+        return None;
+    }
+    Some((
+        (d * width - c * height) / determinant,
+        (a * height - b * width) / determinant,
+    ))
+}
+
+/**
+The box the node is laid out in before it is transformed.
+
+This is the bounding box unless the node or its parent is transformed. Then it
+is the node's size, centred where the bounding box is, in the coordinates of
+the parent before it is transformed. Only the parent's transform is taken into
+account, not those of further ancestors.
+ */
+pub fn layout_box(node: &Node, parent: Option<&Node>) -> Option<Rectangle> {
+    let bounds = absolute_bounding_box(node)?;
+    let transform = node_transform(node);
+    let parent_transform = parent.and_then(node_transform);
+    let absolute_transform = match (parent_transform, transform) {
+        (None, None) => return Some(bounds),
+        (Some(parent), Some(node)) => parent.multiply(&node),
+        (Some(transform), None) | (None, Some(transform)) => transform,
+    };
+    let Some((width, height)) = untransformed_size(node, &absolute_transform) else {
+        return Some(bounds);
+    };
+    let mut center = Vector {
+        x: bounds.x? + bounds.width? / 2.0,
+        y: bounds.y? + bounds.height? / 2.0,
+    };
+    if let (Some(parent), Some(parent_transform)) = (parent, parent_transform) {
+        let parent_bounds = absolute_bounding_box(parent)?;
+        let parent_center = Vector {
+            x: parent_bounds.x? + parent_bounds.width? / 2.0,
+            y: parent_bounds.y? + parent_bounds.height? / 2.0,
+        };
+        let offset = parent_transform.invert()?.apply(&Vector {
+            x: center.x - parent_center.x,
+            y: center.y - parent_center.y,
+        });
+        center = Vector {
+            x: parent_center.x + offset.x,
+            y: parent_center.y + offset.y,
+        };
+    }
+    Some(Rectangle {
+        x: Some(center.x - width / 2.0),
+        y: Some(center.y - height / 2.0),
+        width: Some(width),
+        height: Some(height),
+    })
+}
+
+/// Whether a node's fills are drawn as its background. Text and vectors use
+/// their fills as colors instead.
+pub fn fills_as_background(node: &Node) -> bool {
+    !matches!(
+        node.r#type,
+        NodeType::Text | NodeType::Vector | NodeType::BooleanOperation
+    )
+}
+
+/**
+The opacity of a node, including the opacity of an image fill.
+
+CSS can't make one background layer translucent, so an image fill's opacity is
+only kept when the image is all the node draws, by applying it to the whole
+node. Otherwise it is dropped.
+ */
+pub fn node_opacity(node: &Node) -> Option<f64> {
+    let image_opacity = match visible_fills(node).as_slice() {
+        [paint]
+            if paint.r#type == PaintType::Image
+                && fills_as_background(node)
+                && node.enabled_children().next().is_none()
+                && !node.strokes().iter().any(|p| p.visible())
+                && !node.effects().iter().any(|e| e.visible) =>
+        {
+            paint.opacity()
+        }
+        _ => 1.0,
+    };
+    if image_opacity == 1.0 {
+        node.opacity
+    } else {
+        Some(node.opacity() * image_opacity)
+    }
+}
+
+/// Text and vectors aren't boxes, so their shadows need to follow their shape
+fn shadows_as_filter(node: &Node) -> bool {
+    matches!(
+        node.r#type,
+        NodeType::Text | NodeType::Vector | NodeType::BooleanOperation
+    )
+}
+
+/// Figma's blur radius is twice CSS's standard deviation
+fn blur(effect: &Effect) -> Option<String> {
+    let radius = effect.radius();
+    if radius == 0.0 {
+        None
+    } else {
+        Some(format!("blur({}px)", radius / 2.0))
+    }
+}
+
 impl CssProperties for Node {
-    fn background(&self, css_variables: &mut CSSVariablesMap) -> Option<String> {
-        match self.r#type {
-            NodeType::Text | NodeType::Vector | NodeType::BooleanOperation => None,
-            _ => fills_color(self, css_variables).or_else(|| {
-                self.background_color()
-                    .and_then(|c| c.to_option_rgb_string())
-            }),
+    fn background(
+        &self,
+        css_variables: &mut CSSVariablesMap,
+        options: &ConversionOptions,
+    ) -> Option<String> {
+        if !fills_as_background(self) {
+            return None;
         }
+        fills_background(self, css_variables, options).or_else(|| {
+            self.background_color()
+                .and_then(|c| c.to_option_rgb_string())
+        })
     }
 
     fn box_shadow(&self) -> Option<String> {
+        if shadows_as_filter(self) {
+            return None;
+        }
         let shadows = itertools::join(
-            self.effects
-                .as_deref()
-                .unwrap_or(&[])
-                .iter()
-                .filter(|e| e.r#type == EffectType::InnerShadow)
-                .filter(|e| e.visible)
-                .filter_map(|e| {
-                    let x_offset = e.offset.as_ref()?.x;
-                    let y_offset = e.offset.as_ref()?.y;
-                    let spread = e.spread();
-                    let color = e.color.as_ref().and_then(|c| c.to_option_rgb_string())?;
-                    Some(format!(
-                        "inset {x_offset}px {y_offset}px {spread}px {color}"
-                    ))
-                }),
+            self.effects().iter().filter(|e| e.visible).filter_map(|e| {
+                let inset = match e.r#type {
+                    EffectType::InnerShadow => "inset ",
+                    EffectType::DropShadow if !e.show_shadow_behind_node() => "",
+                    _ => return None,
+                };
+                let x_offset = e.offset.as_ref()?.x;
+                let y_offset = e.offset.as_ref()?.y;
+                let blur = e.radius();
+                let spread = e.spread();
+                let color = e.color.as_ref().and_then(|c| c.to_option_rgb_string())?;
+                Some(format!(
+                    "{inset}{x_offset}px {y_offset}px {blur}px {spread}px {color}"
+                ))
+            }),
             ", ",
         );
 
@@ -129,38 +606,354 @@ impl CssProperties for Node {
         }
     }
 
-    fn font(&self, css_variables: &mut CSSVariablesMap) -> Option<String> {
-        let font_style = self.style.as_ref()?;
+    fn filter(&self) -> Option<String> {
+        let shadows_as_filter = shadows_as_filter(self);
+        let filters = itertools::join(
+            self.effects()
+                .iter()
+                .filter(|e| e.visible)
+                .filter_map(|e| match e.r#type {
+                    // drop-shadow follows the shape of the content, unlike
+                    // box-shadow which follows the box and is never drawn
+                    // behind it. drop-shadow cannot spread.
+                    EffectType::DropShadow if shadows_as_filter || e.show_shadow_behind_node() => {
+                        let x_offset = e.offset.as_ref()?.x;
+                        let y_offset = e.offset.as_ref()?.y;
+                        let blur = e.radius();
+                        let color = e.color.as_ref().and_then(|c| c.to_option_rgb_string())?;
+                        Some(format!(
+                            "drop-shadow({x_offset}px {y_offset}px {blur}px {color})"
+                        ))
+                    }
+                    EffectType::LayerBlur => blur(e),
+                    _ => None,
+                }),
+            " ",
+        );
 
-        let style = if matches!(font_style.italic, Some(true)) {
-            "italic"
-        } else {
-            ""
-        };
-        let variant = if matches!(
-            font_style.text_case,
-            Some(TextCase::SmallCaps | TextCase::SmallCapsForced)
-        ) {
-            "small-caps"
+        if filters.is_empty() {
+            None
         } else {
-            ""
-        };
-        let weight = font_style.font_weight;
-        let size = font_style.font_size;
-        let line_height = font_style.line_height_px;
-        let family = &font_style.font_family;
-
-        let font_value = format!(r#"{style} {variant} {weight} {size}px/{line_height}px "{family}""#);
-
-        match self.styles.as_ref().and_then(|s| s.text.as_deref()) {
-            Some(s_ref) => match css_variables.get_mut(s_ref) {
-                Some(v) => {
-                    v.value = Some(font_value);
-                    Some(format!("var({})", v.name))
+            Some(filters)
+        }
+    }
+
+    fn backdrop_filter(&self) -> Option<String> {
+        self.effects()
+            .iter()
+            .filter(|e| e.visible && e.r#type == EffectType::BackgroundBlur)
+            .find_map(blur)
+    }
+
+    fn font(
+        &self,
+        css_variables: &mut CSSVariablesMap,
+        options: &ConversionOptions,
+    ) -> Option<Font> {
+        let font = type_style_font(self.style.as_ref()?, &options.fonts);
+        Some(style_font(
+            css_variables,
+            self.styles.as_ref().and_then(|s| s.text.as_deref()),
+            font,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        isolates_children, node_opacity, paints_color, type_style_font,
+        type_style_font_feature_settings, CssProperties,
+    };
+    use crate::FontMapping;
+    use figma_schema::{Node, TypeStyle};
+
+    #[test]
+    fn image_fill_opacity() {
+        let photo: Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Photo",
+            "type": "RECTANGLE",
+            "opacity": 0.8,
+            "fills": [{ "type": "IMAGE", "imageRef": "abc", "opacity": 0.5 }]
+        }))
+        .unwrap();
+        assert_eq!(node_opacity(&photo), Some(0.4));
+
+        // The opacity would apply to the children too
+        let card: Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Card",
+            "type": "FRAME",
+            "fills": [{ "type": "IMAGE", "imageRef": "abc", "opacity": 0.5 }],
+            "children": [{ "id": "1:2", "name": "Title", "type": "RECTANGLE" }]
+        }))
+        .unwrap();
+        assert_eq!(node_opacity(&card), None);
+
+        // Text doesn't draw image fills
+        let text: Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Title",
+            "type": "TEXT",
+            "fills": [{ "type": "IMAGE", "imageRef": "abc", "opacity": 0.5 }]
+        }))
+        .unwrap();
+        assert_eq!(node_opacity(&text), None);
+    }
+
+    #[test]
+    fn shadows_of_boxes() {
+        let rectangle: Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Card",
+            "type": "RECTANGLE",
+            "effects": [
+                {
+                    "type": "DROP_SHADOW",
+                    "visible": true,
+                    "color": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 0.5 },
+                    "offset": { "x": 1.0, "y": 2.0 },
+                    "radius": 4.0,
+                    "spread": 3.0
+                },
+                {
+                    "type": "INNER_SHADOW",
+                    "visible": true,
+                    "color": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 0.5 },
+                    "offset": { "x": 1.0, "y": 2.0 },
+                    "radius": 4.0
+                },
+                {
+                    "type": "DROP_SHADOW",
+                    "visible": false,
+                    "color": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 0.5 },
+                    "offset": { "x": 1.0, "y": 2.0 },
+                    "radius": 4.0
                 }
-                None => Some(font_value),
-            },
-            None => Some(font_value),
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            rectangle.box_shadow().as_deref(),
+            Some("1px 2px 4px 3px rgb(0, 0, 0, 0.5), inset 1px 2px 4px 0px rgb(0, 0, 0, 0.5)")
+        );
+        assert_eq!(rectangle.filter(), None);
+    }
+
+    #[test]
+    fn shadows_behind_boxes_are_filters() {
+        let rectangle: Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Card",
+            "type": "RECTANGLE",
+            "effects": [{
+                "type": "DROP_SHADOW",
+                "visible": true,
+                "color": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 0.5 },
+                "offset": { "x": 1.0, "y": 2.0 },
+                "radius": 4.0,
+                "showShadowBehindNode": true
+            }]
+        }))
+        .unwrap();
+        assert_eq!(rectangle.box_shadow(), None);
+        assert_eq!(
+            rectangle.filter().as_deref(),
+            Some("drop-shadow(1px 2px 4px rgb(0, 0, 0, 0.5))")
+        );
+    }
+
+    #[test]
+    fn shadows_of_text_and_vectors_follow_their_shape() {
+        for node_type in ["TEXT", "VECTOR"] {
+            let shape: Node = serde_json::from_value(serde_json::json!({
+                "id": "1:1",
+                "name": "Shape",
+                "type": node_type,
+                "effects": [
+                    {
+                        "type": "DROP_SHADOW",
+                        "visible": true,
+                        "color": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 0.5 },
+                        "offset": { "x": 1.0, "y": 2.0 },
+                        "radius": 4.0,
+                        "spread": 3.0
+                    },
+                    { "type": "LAYER_BLUR", "visible": true, "radius": 8.0 }
+                ]
+            }))
+            .unwrap();
+            assert_eq!(shape.box_shadow(), None);
+            assert_eq!(
+                shape.filter().as_deref(),
+                Some("drop-shadow(1px 2px 4px rgb(0, 0, 0, 0.5)) blur(4px)")
+            );
         }
     }
+
+    #[test]
+    fn background_blur() {
+        let rectangle: Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Glass",
+            "type": "RECTANGLE",
+            "effects": [{ "type": "BACKGROUND_BLUR", "visible": true, "radius": 10.0 }]
+        }))
+        .unwrap();
+        assert_eq!(rectangle.backdrop_filter().as_deref(), Some("blur(5px)"));
+        assert_eq!(rectangle.filter(), None);
+    }
+
+    #[test]
+    fn line_height_units() {
+        let font = |style: serde_json::Value| {
+            let style: TypeStyle = serde_json::from_value(style).unwrap();
+            type_style_font(&style, &FontMapping::default()).to_string()
+        };
+        assert_eq!(
+            font(serde_json::json!({
+                "fontFamily": "Inter",
+                "fontWeight": 400.0,
+                "fontSize": 16.0,
+                "lineHeightPx": 24.0
+            })),
+            r#"  400 16px/24px "Inter""#
+        );
+        assert_eq!(
+            font(serde_json::json!({
+                "fontFamily": "Inter",
+                "fontWeight": 400.0,
+                "fontSize": 16.0,
+                "lineHeightPx": 24.0,
+                "lineHeightUnit": "FONT_SIZE_%",
+                "lineHeightPercentFontSize": 150.0
+            })),
+            r#"  400 16px/1.5 "Inter""#
+        );
+        assert_eq!(
+            font(serde_json::json!({
+                "fontFamily": "Inter",
+                "fontWeight": 400.0,
+                "fontSize": 16.0,
+                "lineHeightPx": 24.0,
+                "lineHeightUnit": "INTRINSIC_%"
+            })),
+            r#"  400 16px/normal "Inter""#
+        );
+        // Other percentages of the intrinsic height fall back to pixels
+        assert_eq!(
+            font(serde_json::json!({
+                "fontFamily": "Inter",
+                "fontWeight": 400.0,
+                "fontSize": 16.0,
+                "lineHeightPx": 24.0,
+                "lineHeightUnit": "INTRINSIC_%",
+                "lineHeightPercent": 120.0
+            })),
+            r#"  400 16px/24px "Inter""#
+        );
+        assert_eq!(
+            font(serde_json::json!({
+                "fontFamily": "Inter",
+                "fontWeight": 400.0,
+                "fontSize": 16.0,
+                "lineHeightPx": 24.0,
+                "italic": true,
+                "textCase": "SMALL_CAPS"
+            })),
+            r#"italic small-caps 400 16px/24px "Inter""#
+        );
+    }
+
+    #[test]
+    fn font_feature_settings() {
+        let style: TypeStyle = serde_json::from_value(serde_json::json!({
+            "fontFamily": "Inter",
+            "fontWeight": 400.0,
+            "fontSize": 16.0,
+            "lineHeightPx": 24.0,
+            "opentypeFlags": { "LIGA": 0, "TNUM": 1 }
+        }))
+        .unwrap();
+        assert_eq!(
+            type_style_font_feature_settings(&style).as_deref(),
+            Some(r#""liga" 0, "tnum" 1"#)
+        );
+
+        let style: TypeStyle = serde_json::from_value(serde_json::json!({
+            "fontFamily": "Inter",
+            "fontWeight": 400.0,
+            "fontSize": 16.0,
+            "lineHeightPx": 24.0,
+            "opentypeFlags": {}
+        }))
+        .unwrap();
+        assert_eq!(type_style_font_feature_settings(&style), None);
+    }
+
+    #[test]
+    fn groups_isolate_children_that_blend() {
+        let multiply = serde_json::json!({
+            "id": "1:3",
+            "name": "Shade",
+            "type": "RECTANGLE",
+            "blendMode": "MULTIPLY"
+        });
+        let group = |blend_mode: &str, children: serde_json::Value| -> Node {
+            serde_json::from_value(serde_json::json!({
+                "id": "1:1",
+                "name": "Group",
+                "type": "GROUP",
+                "blendMode": blend_mode,
+                "children": children
+            }))
+            .unwrap()
+        };
+
+        // Pass through lets the children blend with what is behind the group
+        assert!(!isolates_children(&group(
+            "PASS_THROUGH",
+            serde_json::json!([multiply])
+        )));
+        assert!(isolates_children(&group(
+            "NORMAL",
+            serde_json::json!([multiply])
+        )));
+        // Through a pass through group within it
+        assert!(isolates_children(&group(
+            "NORMAL",
+            serde_json::json!([{
+                "id": "1:2",
+                "name": "Inner",
+                "type": "GROUP",
+                "blendMode": "PASS_THROUGH",
+                "children": [multiply]
+            }])
+        )));
+        // Nothing blends, so nothing needs isolating
+        assert!(!isolates_children(&group(
+            "NORMAL",
+            serde_json::json!([{
+                "id": "1:2",
+                "name": "Card",
+                "type": "RECTANGLE",
+                "blendMode": "NORMAL"
+            }])
+        )));
+    }
+
+    #[test]
+    fn paint_opacity_is_in_the_color() {
+        let paints = serde_json::from_value::<Vec<figma_schema::Paint>>(serde_json::json!([
+            { "type": "SOLID", "visible": false, "color": { "r": 0.0, "g": 0.0, "b": 1.0, "a": 1.0 } },
+            { "type": "SOLID", "opacity": 0.0, "color": { "r": 0.0, "g": 1.0, "b": 0.0, "a": 1.0 } },
+            { "type": "SOLID", "opacity": 0.5, "color": { "r": 1.0, "g": 0.0, "b": 0.0, "a": 0.8 } }
+        ]))
+        .unwrap();
+        assert_eq!(
+            paints_color(&paints).as_deref(),
+            Some("rgb(255, 0, 0, 0.4)")
+        );
+    }
 }
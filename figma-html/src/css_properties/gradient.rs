@@ -0,0 +1,213 @@
+use figma_schema::{Color, ColorStop, Paint, PaintType};
+
+type Point = (f64, f64);
+
+/// Figma's default handle positions, a top to bottom gradient
+const DEFAULT_HANDLES: [Point; 3] = [(0.5, 0.0), (0.5, 1.0), (0.0, 0.0)];
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn length((x, y): Point) -> f64 {
+    x.hypot(y)
+}
+
+/// CSS angle of a direction. `0deg` points up and angles increase clockwise.
+fn css_angle((x, y): Point) -> f64 {
+    x.atan2(-y).to_degrees().rem_euclid(360.0)
+}
+
+fn stops(paint: &Paint, position: impl Fn(f64) -> f64) -> String {
+    itertools::join(
+        paint
+            .gradient_stops()
+            .iter()
+            .map(|ColorStop { position: p, color }| {
+                let color = Color {
+                    alpha: color.alpha * paint.opacity(),
+                    ..color.clone()
+                };
+                format!("{} {}%", color.to_rgb_string(), round(position(*p) * 100.0))
+            }),
+        ", ",
+    )
+}
+
+/**
+Linear gradients in Figma are defined by a start and end handle, with the
+third handle giving the direction of the lines of constant colour.
+
+CSS gradient lines always pass through the centre of the box and have a length
+chosen so the corners get the first and last colours. So the Figma stops are
+projected onto the CSS gradient line.
+ */
+fn linear_gradient(paint: &Paint, [start, end, width_handle]: [Point; 3], size: Point) -> String {
+    let along = (end.0 - start.0, end.1 - start.1);
+    let across = (width_handle.0 - start.0, width_handle.1 - start.1);
+    let mut direction = (-across.1, across.0);
+    if length(direction) == 0.0 {
+        direction = along;
+    }
+    let direction_length = length(direction);
+    if direction_length == 0.0 {
+        direction = (0.0, 1.0);
+    } else {
+        direction = (
+            direction.0 / direction_length,
+            direction.1 / direction_length,
+        );
+    }
+    if direction.0 * along.0 + direction.1 * along.1 < 0.0 {
+        direction = (-direction.0, -direction.1);
+    }
+
+    let gradient_length = (size.0 * direction.0).abs() + (size.1 * direction.1).abs();
+    let start_offset =
+        (start.0 - size.0 / 2.0) * direction.0 + (start.1 - size.1 / 2.0) * direction.1;
+    let span = along.0 * direction.0 + along.1 * direction.1;
+
+    format!(
+        "linear-gradient({}deg, {})",
+        round(css_angle(direction)),
+        stops(paint, |t| if gradient_length == 0.0 {
+            t
+        } else {
+            0.5 + (start_offset + t * span) / gradient_length
+        })
+    )
+}
+
+/**
+Radial gradients have their centre at the first handle and their radii given
+by the second and third handles. Rotated ellipses can't be represented, so the
+radii are assigned to whichever axis they are closest to.
+ */
+fn radial_gradient(paint: &Paint, [centre, end, width_handle]: [Point; 3], size: Point) -> String {
+    let first_radius = (end.0 - centre.0, end.1 - centre.1);
+    let second_radius = (width_handle.0 - centre.0, width_handle.1 - centre.1);
+    let (horizontal, vertical) = if first_radius.0.abs() >= first_radius.1.abs() {
+        (length(first_radius), length(second_radius))
+    } else {
+        (length(second_radius), length(first_radius))
+    };
+    format!(
+        "radial-gradient({}% {}% at {}% {}%, {})",
+        round(horizontal / size.0 * 100.0),
+        round(vertical / size.1 * 100.0),
+        round(centre.0 / size.0 * 100.0),
+        round(centre.1 / size.1 * 100.0),
+        stops(paint, |t| t)
+    )
+}
+
+/**
+Angular gradients sweep clockwise around the first handle, starting in the
+direction of the second handle.
+ */
+fn conic_gradient(paint: &Paint, [centre, start, _]: [Point; 3], size: Point) -> String {
+    format!(
+        "conic-gradient(from {}deg at {}% {}%, {})",
+        round(css_angle((start.0 - centre.0, start.1 - centre.1))),
+        round(centre.0 / size.0 * 100.0),
+        round(centre.1 / size.1 * 100.0),
+        stops(paint, |t| t)
+    )
+}
+
+/**
+Convert a gradient paint to a CSS gradient.
+
+`size` is the width and height of the painted box in pixels. It is needed as
+Figma handle positions are normalised to the box, so angles and distances
+change with the box's aspect ratio.
+
+Returns `None` if the paint isn't a gradient or has no stops.
+ */
+pub fn gradient(paint: &Paint, size: Point) -> Option<String> {
+    if paint.gradient_stops().is_empty() {
+        return None;
+    }
+    let size = (size.0.max(f64::EPSILON), size.1.max(f64::EPSILON));
+    let handles = match paint.gradient_handle_positions.as_ref() {
+        Some([a, b, c]) => [(a.x, a.y), (b.x, b.y), (c.x, c.y)],
+        None => DEFAULT_HANDLES,
+    }
+    .map(|(x, y)| (x * size.0, y * size.1));
+    match paint.r#type {
+        PaintType::GradientLinear => Some(linear_gradient(paint, handles, size)),
+        PaintType::GradientRadial => Some(radial_gradient(paint, handles, size)),
+        // CSS has no diamond gradient. A radial gradient with the same
+        // handles is the closest approximation.
+        PaintType::GradientDiamond => Some(radial_gradient(paint, handles, size)),
+        PaintType::GradientAngular => Some(conic_gradient(paint, handles, size)),
+        PaintType::Solid | PaintType::Image => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paint(r#type: &str, handles: [Point; 3]) -> Paint {
+        serde_json::from_value(serde_json::json!({
+            "type": r#type,
+            "gradientHandlePositions": handles.map(|(x, y)| serde_json::json!({"x": x, "y": y})),
+            "gradientStops": [
+                {"position": 0.0, "color": {"r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0}},
+                {"position": 1.0, "color": {"r": 0.0, "g": 0.0, "b": 1.0, "a": 1.0}}
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn linear_top_to_bottom() {
+        assert_eq!(
+            gradient(
+                &paint("GRADIENT_LINEAR", [(0.5, 0.0), (0.5, 1.0), (0.0, 0.0)]),
+                (100.0, 50.0)
+            )
+            .as_deref(),
+            Some("linear-gradient(180deg, rgb(255, 0, 0, 1) 0%, rgb(0, 0, 255, 1) 100%)")
+        );
+    }
+
+    #[test]
+    fn linear_left_to_right_inset() {
+        assert_eq!(
+            gradient(
+                &paint("GRADIENT_LINEAR", [(0.25, 0.5), (0.75, 0.5), (0.25, 0.0)]),
+                (100.0, 50.0)
+            )
+            .as_deref(),
+            Some("linear-gradient(90deg, rgb(255, 0, 0, 1) 25%, rgb(0, 0, 255, 1) 75%)")
+        );
+    }
+
+    #[test]
+    fn radial_centred() {
+        assert_eq!(
+            gradient(
+                &paint("GRADIENT_RADIAL", [(0.5, 0.5), (1.0, 0.5), (0.5, 1.0)]),
+                (100.0, 50.0)
+            )
+            .as_deref(),
+            Some(
+                "radial-gradient(50% 50% at 50% 50%, rgb(255, 0, 0, 1) 0%, rgb(0, 0, 255, 1) 100%)"
+            )
+        );
+    }
+
+    #[test]
+    fn angular_starting_right() {
+        assert_eq!(
+            gradient(
+                &paint("GRADIENT_ANGULAR", [(0.5, 0.5), (1.0, 0.5), (0.5, 1.0)]),
+                (100.0, 100.0)
+            )
+            .as_deref(),
+            Some("conic-gradient(from 90deg at 50% 50%, rgb(255, 0, 0, 1) 0%, rgb(0, 0, 255, 1) 100%)")
+        );
+    }
+}
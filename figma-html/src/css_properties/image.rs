@@ -0,0 +1,131 @@
+use figma_schema::{Paint, ScaleMode, Transform};
+
+use crate::ConversionOptions;
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn css_url(url: &str) -> String {
+    format!(
+        r#"url("{}")"#,
+        url.replace('\\', "\\\\").replace('"', "\\\"")
+    )
+}
+
+/**
+Convert an image paint to a CSS background layer.
+
+The image is referenced by URL built from the image ref. The image itself needs
+to be fetched separately using Figma's `GET file images` endpoint.
+
+Returns `None` if the paint has no image ref.
+ */
+pub fn image(paint: &Paint, options: &ConversionOptions) -> Option<String> {
+    let url = css_url(&options.image_url(paint.image_ref.as_deref()?));
+    Some(match paint.scale_mode() {
+        ScaleMode::Fill => format!("{url} center / cover no-repeat"),
+        ScaleMode::Fit => format!("{url} center / contain no-repeat"),
+        ScaleMode::Tile => {
+            let scaling_factor = paint.scaling_factor();
+            if scaling_factor == 1.0 || scaling_factor <= 0.0 {
+                url
+            } else {
+                // The natural size of the image isn't known, but a lower
+                // resolution displays the image at a larger size.
+                format!("image-set({url} {}x)", 1.0 / scaling_factor)
+            }
+        }
+        ScaleMode::Stretch | ScaleMode::Crop => match paint.image_transform {
+            // The transform maps the node's normalised space onto the
+            // visible portion of the image. Rotations aren't supported.
+            Some(Transform([[scale_x, _, offset_x], [_, scale_y, offset_y]]))
+                if scale_x > 0.0 && scale_y > 0.0 =>
+            {
+                // A background-position percentage aligns that point of the
+                // image with the same point of the box
+                let position = |scale: f64, offset: f64| {
+                    if scale == 1.0 {
+                        0.0
+                    } else {
+                        round(offset / (1.0 - scale) * 100.0)
+                    }
+                };
+                format!(
+                    "{url} {}% {}% / {}% {}% no-repeat",
+                    position(scale_x, offset_x),
+                    position(scale_y, offset_y),
+                    round(100.0 / scale_x),
+                    round(100.0 / scale_y)
+                )
+            }
+            _ => format!("{url} 0 0 / 100% 100% no-repeat"),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::image;
+    use crate::ConversionOptions;
+
+    fn css(paint: serde_json::Value) -> String {
+        image(
+            &serde_json::from_value(paint).unwrap(),
+            &ConversionOptions::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn fill_and_fit() {
+        assert_eq!(
+            css(serde_json::json!({ "type": "IMAGE", "imageRef": "abc" })),
+            r#"url("images/abc") center / cover no-repeat"#
+        );
+        assert_eq!(
+            css(serde_json::json!({ "type": "IMAGE", "imageRef": "abc", "scaleMode": "FIT" })),
+            r#"url("images/abc") center / contain no-repeat"#
+        );
+    }
+
+    #[test]
+    fn tile() {
+        assert_eq!(
+            css(serde_json::json!({ "type": "IMAGE", "imageRef": "abc", "scaleMode": "TILE" })),
+            r#"url("images/abc")"#
+        );
+        assert_eq!(
+            css(serde_json::json!({
+                "type": "IMAGE",
+                "imageRef": "abc",
+                "scaleMode": "TILE",
+                "scalingFactor": 0.5
+            })),
+            r#"image-set(url("images/abc") 2x)"#
+        );
+    }
+
+    #[test]
+    fn stretch_crops_with_the_image_transform() {
+        assert_eq!(
+            css(serde_json::json!({
+                "type": "IMAGE",
+                "imageRef": "abc",
+                "scaleMode": "STRETCH",
+                "imageTransform": [[0.5, 0.0, 0.25], [0.0, 1.0, 0.0]]
+            })),
+            r#"url("images/abc") 50% 0% / 200% 100% no-repeat"#
+        );
+        assert_eq!(
+            css(serde_json::json!({ "type": "IMAGE", "imageRef": "abc", "scaleMode": "STRETCH" })),
+            r#"url("images/abc") 0 0 / 100% 100% no-repeat"#
+        );
+    }
+
+    #[test]
+    fn no_image_ref() {
+        let paint = serde_json::from_value(serde_json::json!({ "type": "IMAGE" })).unwrap();
+        assert_eq!(image(&paint, &ConversionOptions::default()), None);
+    }
+}
@@ -0,0 +1,57 @@
+use figma_schema::{MaskType, Node, NodeType, PaintType, WindingRule};
+
+use super::{fill_layers, visible_fills};
+use crate::ConversionOptions;
+
+/**
+The shape of a mask as a `clip-path` value, relative to the mask's own box.
+
+Masks with fill geometry, which is only present with `geometry=paths`, clip to
+their paths. Otherwise ellipses clip to an ellipse and anything else to its box
+with its rounded corners.
+ */
+pub fn mask_clip_path(mask: &Node) -> String {
+    let geometry = mask.fill_geometry();
+    if let Some(first) = geometry.first() {
+        let fill_rule = match first.winding_rule {
+            WindingRule::Nonzero => "",
+            WindingRule::Evenodd => "evenodd, ",
+        };
+        let paths = itertools::join(geometry.iter().map(|p| p.path.as_str()), " ");
+        return format!(r#"path({fill_rule}"{paths}")"#);
+    }
+    match (mask.r#type, mask.rectangle_corner_radii()) {
+        (NodeType::Ellipse, _) => "ellipse(50% 50%)".to_string(),
+        (_, Some([nw, ne, se, sw])) => format!("inset(0 round {nw}px {ne}px {se}px {sw}px)"),
+        (_, None) => "inset(0)".to_string(),
+    }
+}
+
+/**
+The fills of a mask as `mask` layers, if they are needed on top of its shape.
+
+Vector masks only use the shape. Alpha masks only need their fills if none of
+them are opaque solid colours.
+ */
+pub fn mask_layers(mask: &Node, options: &ConversionOptions) -> Option<String> {
+    let mode = match mask.mask_type() {
+        MaskType::Vector => return None,
+        MaskType::Alpha => "",
+        MaskType::Luminance => " luminance",
+    };
+    if mode.is_empty()
+        && visible_fills(mask).iter().any(|p| {
+            p.r#type == PaintType::Solid && p.color_with_opacity().is_some_and(|c| c.alpha >= 1.0)
+        })
+    {
+        return None;
+    }
+    let layers = fill_layers(mask, options);
+    if layers.is_empty() {
+        return None;
+    }
+    Some(itertools::join(
+        layers.iter().map(|(layer, _)| format!("{layer}{mode}")),
+        ", ",
+    ))
+}
@@ -0,0 +1,463 @@
+use std::collections::HashMap;
+
+use figma_schema::{File, NodeType as FigmaNodeType};
+use serde::{Deserialize, Serialize};
+
+use crate::intermediate_node::{IntermediateNode, IntermediateNodeType};
+
+/**
+Rules for choosing the HTML elements of nodes, so that the output keeps the
+semantics the designer meant.
+
+Rules are tried in order and the first to match a node sets its element. Nodes
+that no rule matches can still be inferred from the structure: text larger
+than the body text becomes a heading, and repeated siblings become a list.
+
+The rules can be read from JSON, for example:
+
+```json
+{
+  "rules": [
+    { "component": "Button*", "element": "button" },
+    { "name": "Nav", "element": "nav" }
+  ],
+  "headings": true,
+  "listMinItems": 3
+}
+```
+ */
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ElementRules {
+    pub rules: Vec<ElementRule>,
+    /// Make text larger than the body text into `h1` to `h6`, by size
+    pub headings: bool,
+    /// Make a frame with at least this many repeated children into a `ul`.
+    /// Children repeat when they are instances of the same component or
+    /// share a name.
+    pub list_min_items: Option<usize>,
+}
+
+/**
+A rule matching nodes to an element.
+
+The patterns match the whole name, with `*` matching any characters. All the
+given conditions must match.
+ */
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElementRule {
+    /// Pattern for the node's name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Pattern for the name of the component an instance is of, or of the
+    /// component set the component is a variant in. Components and their
+    /// variants match by their own names.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_type: Option<FigmaNodeType>,
+    pub element: String,
+}
+
+impl Default for ElementRules {
+    fn default() -> Self {
+        let patterns = [
+            ("Button", "button"),
+            ("Button/*", "button"),
+            ("Input", "input"),
+            ("Text input", "input"),
+            ("Header", "header"),
+            ("Footer", "footer"),
+            ("Nav*", "nav"),
+        ];
+        // Instances are often renamed, so their components' names are matched too
+        let rules = patterns
+            .into_iter()
+            .flat_map(|(pattern, element)| {
+                [
+                    ElementRule {
+                        name: Some(pattern.to_string()),
+                        component: None,
+                        node_type: None,
+                        element: element.to_string(),
+                    },
+                    ElementRule {
+                        name: None,
+                        component: Some(pattern.to_string()),
+                        node_type: None,
+                        element: element.to_string(),
+                    },
+                ]
+            })
+            .collect();
+        Self {
+            rules,
+            headings: true,
+            list_min_items: Some(3),
+        }
+    }
+}
+
+/// Whether the pattern matches all of the text, with `*` matching any characters
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let Some(first) = parts.next() else {
+        return text.is_empty();
+    };
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts = parts.collect::<Vec<_>>();
+    let Some(last) = parts.pop() else {
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+impl ElementRule {
+    fn matches(&self, node: &IntermediateNode<'_>, file: &File) -> bool {
+        let Some(figma) = node.figma.as_ref() else {
+            return false;
+        };
+        self.name
+            .as_deref()
+            .is_none_or(|pattern| glob_match(pattern, &figma.name))
+            && self.component.as_deref().is_none_or(|pattern| {
+                let component_id = match figma.r#type {
+                    FigmaNodeType::Component => Some(&figma.id),
+                    _ => figma.component_id.as_ref(),
+                };
+                let Some(component) = component_id.and_then(|id| file.components.get(id.as_ref()))
+                else {
+                    return false;
+                };
+                // A variant's name is its properties, so its set's name is matched
+                let component_set = component
+                    .component_set_id
+                    .as_deref()
+                    .and_then(|id| file.component_sets.get(id));
+                glob_match(pattern, &component.name)
+                    || component_set.is_some_and(|set| glob_match(pattern, &set.name))
+            })
+            && self
+                .node_type
+                .is_none_or(|node_type| node_type == figma.r#type)
+    }
+}
+
+/// Elements that mark a region of the page, which would be announced twice if
+/// nested in themselves
+const LANDMARKS: [&str; 3] = ["header", "footer", "nav"];
+
+fn children_mut<'a, 'b>(node: &'a mut IntermediateNode<'b>) -> &'a mut [IntermediateNode<'b>] {
+    match &mut node.node_type {
+        IntermediateNodeType::Frame { children } => children,
+        _ => &mut [],
+    }
+}
+
+/// Links and SVGs are written as their own elements
+fn can_have_element(node: &IntermediateNode<'_>) -> bool {
+    node.element.is_none()
+        && node.href.is_none()
+        && !matches!(node.node_type, IntermediateNodeType::Vector { .. })
+}
+
+fn collect_font_sizes(node: &IntermediateNode<'_>, sizes: &mut Vec<f64>) {
+    if let IntermediateNodeType::Frame { children } = &node.node_type {
+        for child in children.iter() {
+            collect_font_sizes(child, sizes);
+        }
+    }
+    if let Some(size) = node.figma.as_ref().and_then(|f| f.font_size) {
+        sizes.push(size);
+    }
+}
+
+impl ElementRules {
+    /**
+    Set the elements of a node and its descendants.
+
+    The node's file is used to look up the names of the components that
+    instances are of. Elements that are already set are kept.
+     */
+    pub fn apply(&self, node: &mut IntermediateNode<'_>, file: &File) {
+        self.apply_rules(node, file, &[]);
+        if let Some(min_items) = self.list_min_items {
+            infer_lists(node, min_items.max(2));
+        }
+        if self.headings {
+            let mut sizes = vec![];
+            collect_font_sizes(node, &mut sizes);
+            // The most common size is the body text
+            let mut counts = HashMap::new();
+            for size in sizes.iter() {
+                *counts.entry(size.to_bits()).or_insert(0) += 1;
+            }
+            let body_size = sizes
+                .iter()
+                .copied()
+                .max_by(|a, b| {
+                    counts[&a.to_bits()]
+                        .cmp(&counts[&b.to_bits()])
+                        .then(b.total_cmp(a))
+                })
+                .unwrap_or(0.0);
+            let mut heading_sizes = sizes
+                .into_iter()
+                .filter(|size| *size > body_size)
+                .collect::<Vec<_>>();
+            heading_sizes.sort_by(|a, b| b.total_cmp(a));
+            heading_sizes.dedup();
+            infer_headings(node, &heading_sizes);
+        }
+    }
+
+    /// The landmarks are those the node is within, which it can't be another of
+    fn apply_rules(
+        &self,
+        node: &mut IntermediateNode<'_>,
+        file: &File,
+        landmarks: &[&'static str],
+    ) {
+        if can_have_element(node) {
+            if let Some(rule) = self.rules.iter().find(|rule| {
+                !landmarks.contains(&rule.element.as_str()) && rule.matches(node, file)
+            }) {
+                node.element = Some(rule.element.clone());
+            }
+        }
+        // Interactive elements can't be nested, so leave their content alone
+        if is_interactive(node) {
+            return;
+        }
+        let mut landmarks = landmarks.to_vec();
+        landmarks.extend(
+            LANDMARKS
+                .into_iter()
+                .filter(|landmark| node.element.as_deref() == Some(*landmark)),
+        );
+        for child in children_mut(node) {
+            self.apply_rules(child, file, &landmarks);
+        }
+    }
+}
+
+fn infer_lists(node: &mut IntermediateNode<'_>, min_items: usize) {
+    let can_be_list = can_have_element(node);
+    let children = children_mut(node);
+    for child in children.iter_mut() {
+        infer_lists(child, min_items);
+    }
+    let repeated = children.len() >= min_items
+        && children.iter().all(can_have_element)
+        && children.windows(2).all(|pair| {
+            let (Some(a), Some(b)) = (pair[0].figma.as_ref(), pair[1].figma.as_ref()) else {
+                return false;
+            };
+            match (&a.component_id, &b.component_id) {
+                (Some(a), Some(b)) => a == b,
+                _ => a.name == b.name,
+            }
+        });
+    if repeated && can_be_list {
+        for child in children.iter_mut() {
+            child.element = Some("li".to_string());
+        }
+        node.element = Some("ul".to_string());
+    }
+}
+
+fn is_interactive(node: &IntermediateNode<'_>) -> bool {
+    node.href.is_some() || matches!(node.element.as_deref(), Some("button" | "input"))
+}
+
+/// Headings can't be within links or buttons, so those are skipped
+fn infer_headings(node: &mut IntermediateNode<'_>, heading_sizes: &[f64]) {
+    if is_interactive(node) {
+        return;
+    }
+    let font_size = node.figma.as_ref().and_then(|f| f.font_size);
+    if let Some(level) = font_size.and_then(|size| heading_sizes.iter().position(|s| *s == size)) {
+        if can_have_element(node) {
+            node.element = Some(format!("h{}", (level + 1).min(6)));
+        }
+    }
+    for child in children_mut(node) {
+        infer_headings(child, heading_sizes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{glob_match, ElementRules};
+    use crate::{
+        figma_node_to_intermediate_node, intermediate_node::IntermediateNodeType, ConversionOptions,
+    };
+
+    fn file(
+        components: serde_json::Value,
+        component_sets: serde_json::Value,
+    ) -> figma_schema::File {
+        serde_json::from_value(serde_json::json!({
+            "name": "Test",
+            "document": { "id": "0:0", "name": "Document", "type": "DOCUMENT" },
+            "components": components,
+            "componentSets": component_sets,
+            "styles": {},
+            "schemaVersion": 0,
+            "version": "1"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn glob_patterns() {
+        assert!(glob_match("Button", "Button"));
+        assert!(!glob_match("Button", "Buttons"));
+        assert!(glob_match("Button*", "Button / Primary"));
+        assert!(glob_match("*Input", "Text Input"));
+        assert!(glob_match("Nav*Item", "Nav / Item"));
+        assert!(!glob_match("Nav*Item", "Nav / Items"));
+        assert!(glob_match("*", ""));
+    }
+
+    #[test]
+    fn instances_match_their_components_names() {
+        let node: figma_schema::Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Actions",
+            "type": "FRAME",
+            "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 100.0, "height": 40.0 },
+            "children": [
+                {
+                    "id": "1:2",
+                    "name": "Save",
+                    "type": "INSTANCE",
+                    "componentId": "2:1",
+                    "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 50.0, "height": 40.0 }
+                },
+                {
+                    "id": "1:3",
+                    "name": "Cancel",
+                    "type": "INSTANCE",
+                    "componentId": "2:2",
+                    "absoluteBoundingBox": { "x": 50.0, "y": 0.0, "width": 50.0, "height": 40.0 }
+                }
+            ]
+        }))
+        .unwrap();
+        let file = file(
+            serde_json::json!({
+                "2:1": { "key": "a", "name": "Button", "description": "" },
+                "2:2": { "key": "b", "name": "Link", "description": "" }
+            }),
+            serde_json::json!({}),
+        );
+        let mut intermediate_node = figma_node_to_intermediate_node(
+            &node,
+            &mut Default::default(),
+            &ConversionOptions::default(),
+        );
+        ElementRules::default().apply(&mut intermediate_node, &file);
+        let IntermediateNodeType::Frame { children } = &intermediate_node.node_type else {
+            panic!("{:?}", intermediate_node.node_type);
+        };
+        assert_eq!(children[0].element.as_deref(), Some("button"));
+        assert_eq!(children[1].element, None);
+    }
+
+    #[test]
+    fn landmarks_are_not_nested_in_themselves() {
+        let node: figma_schema::Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Header",
+            "type": "FRAME",
+            "children": [
+                { "id": "1:2", "name": "Header", "type": "FRAME" },
+                {
+                    "id": "1:3",
+                    "name": "Nav",
+                    "type": "FRAME",
+                    "children": [{ "id": "1:4", "name": "Navigation", "type": "FRAME" }]
+                }
+            ]
+        }))
+        .unwrap();
+        let mut intermediate_node = figma_node_to_intermediate_node(
+            &node,
+            &mut Default::default(),
+            &ConversionOptions::default(),
+        );
+        ElementRules::default().apply(
+            &mut intermediate_node,
+            &file(serde_json::json!({}), serde_json::json!({})),
+        );
+        let IntermediateNodeType::Frame { children } = &intermediate_node.node_type else {
+            panic!("{:?}", intermediate_node.node_type);
+        };
+        let IntermediateNodeType::Frame {
+            children: nav_children,
+        } = &children[1].node_type
+        else {
+            panic!("{:?}", children[1].node_type);
+        };
+        assert_eq!(intermediate_node.element.as_deref(), Some("header"));
+        assert_eq!(children[0].element, None);
+        assert_eq!(children[1].element.as_deref(), Some("nav"));
+        assert_eq!(nav_children[0].element, None);
+    }
+
+    #[test]
+    fn variants_match_their_component_sets_names() {
+        let file = file(
+            serde_json::json!({
+                "2:1": {
+                    "key": "a",
+                    "name": "Type=Primary, State=Default",
+                    "description": "",
+                    "componentSetId": "3:1"
+                }
+            }),
+            serde_json::json!({
+                "3:1": { "key": "b", "name": "Button", "description": "" }
+            }),
+        );
+        let variant: figma_schema::Node = serde_json::from_value(serde_json::json!({
+            "id": "2:1",
+            "name": "Type=Primary, State=Default",
+            "type": "COMPONENT",
+            "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 50.0, "height": 40.0 }
+        }))
+        .unwrap();
+        let instance: figma_schema::Node = serde_json::from_value(serde_json::json!({
+            "id": "1:2",
+            "name": "Save",
+            "type": "INSTANCE",
+            "componentId": "2:1",
+            "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 50.0, "height": 40.0 }
+        }))
+        .unwrap();
+        for node in [variant, instance] {
+            let mut intermediate_node = figma_node_to_intermediate_node(
+                &node,
+                &mut Default::default(),
+                &ConversionOptions::default(),
+            );
+            ElementRules::default().apply(&mut intermediate_node, &file);
+            assert_eq!(
+                intermediate_node.element.as_deref(),
+                Some("button"),
+                "{}",
+                node.name
+            );
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::BTreeMap;
+
+use figma_schema::{Node, TypeStyle};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{glob_match, intermediate_node::format_css};
+
+/**
+How the fonts chosen in Figma are named and loaded in CSS.
+
+Rules are tried in order and the first whose family pattern matches a font
+applies to it. Fonts that no rule matches keep their Figma family and weight.
+
+The mapping can be read from JSON, for example:
+
+```json
+{
+  "fonts": [
+    {
+      "family": "GDS Transport Website",
+      "stack": ["GDS Transport Website", "arial", "sans-serif"],
+      "weights": { "700": 600 },
+      "faces": [
+        { "weight": 300, "src": ["fonts/light.woff2", "fonts/light.woff"] },
+        { "weight": 700, "src": ["fonts/bold.woff2", "fonts/bold.woff"] }
+      ]
+    }
+  ]
+}
+```
+ */
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FontMapping {
+    pub fonts: Vec<FontRule>,
+}
+
+/// A rule for the fonts of a Figma font family
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FontRule {
+    /// Pattern for the Figma font family, with `*` matching any characters
+    pub family: String,
+    /// The CSS font families to use in order. The Figma family if empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stack: Vec<String>,
+    /// CSS font weights to use in place of Figma font weights
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub weights: BTreeMap<u16, u16>,
+    /// Where to load the fonts from, as `@font-face` rules
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub faces: Vec<FontFace>,
+}
+
+/**
+The files of one or more of a family's styles.
+
+A face without a weight, italic or PostScript name applies to every weight or
+style of the family.
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FontFace {
+    /// The Figma font weight
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub italic: Option<bool>,
+    /// The PostScript name of the font in Figma, which tells apart styles
+    /// with the same weight, such as condensed ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_script_name: Option<String>,
+    /// URLs of the font files, the preferred first
+    pub src: Vec<String>,
+}
+
+/// A font as chosen in Figma
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FigmaFont {
+    pub family: String,
+    pub weight: u16,
+    pub italic: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_script_name: Option<String>,
+}
+
+impl FigmaFont {
+    pub fn from_type_style(style: &TypeStyle) -> Self {
+        Self {
+            family: style.font_family.clone(),
+            weight: style.font_weight.round() as u16,
+            italic: style.italic == Some(true),
+            post_script_name: style.font_post_script_name.clone(),
+        }
+    }
+}
+
+/// Generic font families, which mustn't be quoted
+const GENERIC_FAMILIES: &[&str] = &[
+    "serif",
+    "sans-serif",
+    "monospace",
+    "cursive",
+    "fantasy",
+    "system-ui",
+    "ui-serif",
+    "ui-sans-serif",
+    "ui-monospace",
+    "ui-rounded",
+    "math",
+    "emoji",
+    "fangsong",
+];
+
+fn quote_family(family: &str) -> String {
+    if GENERIC_FAMILIES.contains(&family) {
+        family.to_string()
+    } else {
+        format!(r#""{}""#, family.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+impl FontMapping {
+    fn rule(&self, font: &FigmaFont) -> Option<&FontRule> {
+        self.fonts
+            .iter()
+            .find(|rule| glob_match(&rule.family, &font.family))
+    }
+
+    /// The CSS `font-family` value of a font
+    pub fn font_family(&self, font: &FigmaFont) -> String {
+        match self.rule(font) {
+            Some(rule) if !rule.stack.is_empty() => {
+                itertools::join(rule.stack.iter().map(|f| quote_family(f)), ", ")
+            }
+            _ => quote_family(&font.family),
+        }
+    }
+
+    /// The CSS `font-weight` value of a font
+    pub fn font_weight(&self, font: &FigmaFont) -> u16 {
+        self.rule(font)
+            .and_then(|rule| rule.weights.get(&font.weight))
+            .copied()
+            .unwrap_or(font.weight)
+    }
+
+    /// The URLs to load a font from, if it has a face
+    pub fn font_src(&self, font: &FigmaFont) -> Option<&[String]> {
+        self.rule(font)?
+            .faces
+            .iter()
+            .find(|face| {
+                face.weight.is_none_or(|w| w == font.weight)
+                    && face.italic.is_none_or(|i| i == font.italic)
+                    && face
+                        .post_script_name
+                        .as_ref()
+                        .is_none_or(|name| font.post_script_name.as_ref() == Some(name))
+            })
+            .map(|face| face.src.as_slice())
+    }
+
+    /**
+    `@font-face` rules for the fonts that have faces.
+
+    The rules are named after the first family in the stack, with the weights
+    they are used with in CSS.
+     */
+    pub fn font_face_rules<'a>(
+        &self,
+        fonts: impl IntoIterator<Item = &'a FigmaFont>,
+        level: u16,
+    ) -> String {
+        let indent = "  ".repeat(level.into());
+        let mut rules = Vec::new();
+        for font in fonts {
+            let Some(src) = self.font_src(font) else {
+                continue;
+            };
+            let family = match self.rule(font).and_then(|rule| rule.stack.first()) {
+                Some(family) => quote_family(family),
+                None => quote_family(&font.family),
+            };
+            let naive_css = format!(
+                "font-family: {family}; font-style: {}; font-weight: {}; src: {};",
+                if font.italic { "italic" } else { "normal" },
+                self.font_weight(font),
+                itertools::join(src.iter().map(|url| format!("url({url:?})")), ", ")
+            );
+            let rule = format!(
+                "{indent}@font-face {{{}}}",
+                format_css(level, &naive_css).unwrap_or_default()
+            );
+            if !rules.contains(&rule) {
+                rules.push(rule);
+            }
+        }
+        rules.join("\n")
+    }
+}
+
+/**
+Find the fonts used by a node and its visible descendants, including the fonts
+of styled parts of text.
+
+Keys are the fonts and values are the ids of the nodes using them.
+ */
+pub fn figma_node_collect_fonts(node: &Node) -> IndexMap<FigmaFont, Vec<&str>> {
+    fn collect<'a>(node: &'a Node, fonts: &mut IndexMap<FigmaFont, Vec<&'a str>>) {
+        if let Some(style) = node.style.as_ref() {
+            let mut ids = node.character_style_overrides().to_vec();
+            ids.sort_unstable();
+            ids.dedup();
+            let styles = std::iter::once(style.clone()).chain(
+                ids.into_iter()
+                    .filter_map(|id| node.style_override(id))
+                    .map(|style_override| style.with_override(style_override)),
+            );
+            for style in styles {
+                let node_ids = fonts.entry(FigmaFont::from_type_style(&style)).or_default();
+                if node_ids.last() != Some(&node.id.as_str()) {
+                    node_ids.push(&node.id);
+                }
+            }
+        }
+        for child in node.enabled_children() {
+            collect(child, fonts);
+        }
+    }
+    let mut fonts = IndexMap::new();
+    collect(node, &mut fonts);
+    fonts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FigmaFont, FontMapping};
+
+    #[test]
+    fn maps_family_and_weight() {
+        let mapping: FontMapping = serde_json::from_str(
+            r#"{
+                "fonts": [{
+                    "family": "GDS*",
+                    "stack": ["GDS Transport Website", "arial", "sans-serif"],
+                    "weights": { "700": 600 },
+                    "faces": [
+                        { "postScriptName": "GDSTransport-BoldCondensed", "src": ["condensed.woff2"] },
+                        { "weight": 700, "src": ["bold.woff2"] }
+                    ]
+                }]
+            }"#,
+        )
+        .unwrap();
+        let bold = FigmaFont {
+            family: "GDS Transport Website".to_string(),
+            weight: 700,
+            italic: false,
+            post_script_name: Some("GDSTransport-Bold".to_string()),
+        };
+        let condensed = FigmaFont {
+            post_script_name: Some("GDSTransport-BoldCondensed".to_string()),
+            ..bold.clone()
+        };
+        let light = FigmaFont {
+            weight: 300,
+            ..bold.clone()
+        };
+        assert_eq!(
+            mapping.font_family(&bold),
+            r#""GDS Transport Website", "arial", sans-serif"#
+        );
+        assert_eq!(mapping.font_weight(&bold), 600);
+        assert_eq!(mapping.font_weight(&light), 300);
+        assert_eq!(mapping.font_src(&light), None);
+        assert_eq!(
+            mapping.font_src(&bold),
+            Some(["bold.woff2".to_string()].as_slice())
+        );
+        assert_eq!(
+            mapping.font_src(&condensed),
+            Some(["condensed.woff2".to_string()].as_slice())
+        );
+        let other = FigmaFont {
+            family: "Inter".to_string(),
+            ..light
+        };
+        assert_eq!(mapping.font_family(&other), r#""Inter""#);
+    }
+}
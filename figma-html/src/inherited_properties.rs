@@ -1,8 +1,8 @@
 use std::borrow::Cow;
 
-use figma_schema::TextCase;
+use figma_schema::{TextAlignHorizontal, TextCase};
 
-use crate::intermediate_node::IntermediateNode;
+use crate::intermediate_node::{Font, IntermediateNode, Length};
 
 #[derive(Default)]
 pub struct InheritedProperties<'a> {
@@ -19,15 +19,24 @@ pub struct InheritedProperties<'a> {
     // width - not inherited
     pub color: Option<Cow<'a, str>>,
     pub fill: Option<Cow<'a, str>>,
-    pub font: Option<Cow<'a, str>>,
+    pub font: Option<Cow<'a, Font>>,
+    pub font_feature_settings: Option<Cow<'a, str>>,
+    pub letter_spacing: Option<Length<'static>>,
     // opacity - not inherited
+    // mix-blend-mode - not inherited
+    // isolation - not inherited
     pub preserve_whitespace: bool,
     pub text_tranform: Option<TextCase>,
     // text-decoration-line - not inherited
-
+    pub text_align: Option<TextAlignHorizontal>,
+    // align-content (vertical text alignment) - not inherited
+    pub text_indent: Option<f64>,
     // background - not inherited
+    // background-blend-mode - not inherited
     // border-radius - not inherited
     // box-shadow - not inherited
+    // filter - not inherited
+    // backdrop-filter - not inherited
     // outline/border - not inherited
     // outline-offset - not inherited
 }
@@ -53,9 +62,27 @@ impl<'a> InheritedProperties<'a> {
                 .clone()
                 .map(Cow::Owned)
                 .or_else(|| inherited.font.as_deref().map(Cow::Borrowed)),
+            font_feature_settings: node
+                .appearance
+                .font_feature_settings
+                .clone()
+                .map(Cow::Owned)
+                .or_else(|| {
+                    inherited
+                        .font_feature_settings
+                        .as_deref()
+                        .map(Cow::Borrowed)
+                }),
+            letter_spacing: node
+                .appearance
+                .letter_spacing
+                .clone()
+                .or_else(|| inherited.letter_spacing.clone()),
             preserve_whitespace: node.appearance.preserve_whitespace
                 || inherited.preserve_whitespace,
             text_tranform: node.appearance.text_tranform.or(inherited.text_tranform),
+            text_align: node.appearance.text_align.or(inherited.text_align),
+            text_indent: node.appearance.text_indent.or(inherited.text_indent),
         }
     }
 }
@@ -1,22 +1,41 @@
 use std::borrow::Cow;
 
 use figma_schema::{
-    AxisSizingMode, CounterAxisAlignItems, LayoutAlign, LayoutConstraint,
-    LayoutConstraintHorizontal, LayoutConstraintVertical, LayoutMode, Node as FigmaNode,
-    NodeType as FigmaNodeType, PrimaryAxisAlignItems, StrokeAlign, StrokeWeights, TextAutoResize,
-    TextCase, TextDecoration, TypeStyle,
+    AxisSizingMode, BlendMode, CounterAxisAlignContent, CounterAxisAlignItems, GridChildAlign,
+    LayoutAlign, LayoutConstraint, LayoutConstraintHorizontal, LayoutConstraintVertical,
+    LayoutMode, LayoutWrap, Node as FigmaNode, NodeType as FigmaNodeType, Path as FigmaPath,
+    PrimaryAxisAlignItems, TextAlignHorizontal, TextAlignVertical, TextAutoResize, TextCase,
+    TextDecoration, TypeStyle, WindingRule,
 };
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
+mod font;
 mod html_formatter;
 mod inset;
+mod jsx_formatter;
 mod length;
-pub use html_formatter::{format_css, HtmlFormatter};
+mod stroke;
+mod stylesheet;
+mod text_run;
+pub use font::{Font, LineHeight};
+pub use html_formatter::{format_css, minified_css_declarations, HtmlFormatter};
 pub use inset::Inset;
-pub use length::Length;
+pub use jsx_formatter::JsxFormatter;
+pub use length::{Length, LengthUnit};
+pub use stroke::{Stroke, StrokeStyle};
+pub use stylesheet::{unmatched_variant_elements, variant_data_attribute, Stylesheet, Variant};
+pub use text_run::TextRun;
 
-use super::css_properties::{absolute_bounding_box, fills_color, stroke_color, CssProperties};
+use super::{
+    css_properties::{
+        absolute_bounding_box, blend_mode_value, fills_background_blend_mode, fills_color,
+        hyperlink_href, isolates_children, layout_box, mask_clip_path, mask_layers, node_opacity,
+        node_transform, prototype_href, stroke_color, text_max_lines,
+        type_style_font_feature_settings, CssProperties,
+    },
+    ConversionOptions,
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CSSVariable {
@@ -27,6 +46,14 @@ pub struct CSSVariable {
 
 pub type CSSVariablesMap<'a> = IndexMap<Cow<'a, str>, CSSVariable>;
 
+/// A node and the CSS variables it uses, for saving and loading intermediate
+/// nodes as JSON
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IntermediateTree<'a> {
+    pub node: IntermediateNode<'a>,
+    pub css_variables: CSSVariablesMap<'a>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub enum AlignItems {
     Stretch,
@@ -55,12 +82,6 @@ pub enum JustifyContent {
     SpaceBetween,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub enum StrokeStyle {
-    Solid,
-    Dashed,
-}
-
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FlexContainer<'a> {
     pub align_items: AlignItems,
@@ -68,6 +89,49 @@ pub struct FlexContainer<'a> {
     pub gap: Length<'a>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub justify_content: Option<JustifyContent>,
+    /// How the children wrap, if they do
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wrap: Option<FlexWrap<'a>>,
+}
+
+/// How the children of a flex container wrap onto lines
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FlexWrap<'a> {
+    /// The gap between lines
+    pub gap: Length<'a>,
+    /// Spread the lines out to fill the container, instead of packing them
+    pub space_between: bool,
+}
+
+/// A grid's tracks, as CSS `grid-template-rows` and `grid-template-columns`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GridContainer<'a> {
+    pub rows: String,
+    pub columns: String,
+    pub row_gap: Length<'a>,
+    pub column_gap: Length<'a>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum GridAlign {
+    Start,
+    Center,
+    End,
+}
+
+/// Where a child of a grid container is placed
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GridArea {
+    /// The first row, counting from 1
+    pub row: u32,
+    /// The first column, counting from 1
+    pub column: u32,
+    pub row_span: u32,
+    pub column_span: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub justify_self: Option<GridAlign>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub align_self: Option<GridAlign>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -79,22 +143,42 @@ pub struct Location<'a> {
     pub flex_grow: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub inset: Option<[Inset; 4]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grid_area: Option<GridArea>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub height: Option<Length<'a>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub width: Option<Length<'a>>,
+    /// The linear part of a rotation, flip or skew as the CSS matrix `a b c
+    /// d`. It is about the node's centre.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform: Option<[f64; 4]>,
+    /// Vertical and horizontal margins in pixels, so that a transformed node
+    /// in a flex-box takes up its transformed bounds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub margin: Option<[f64; 2]>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq)]
 pub struct Appearance {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fill: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub font: Option<String>,
+    pub font: Option<Font>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_feature_settings: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub letter_spacing: Option<Length<'static>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub opacity: Option<f64>,
+    /// How the node blends with what is behind it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mix_blend_mode: Option<BlendMode>,
+    /// Keep the blending of the node's children to within the node
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub isolate: bool,
     /// Whether it is important to apply `white-space: pre-wrap`. All nodes
     /// should support it, but not all nodes need it.
     pub preserve_whitespace: bool,
@@ -102,26 +186,134 @@ pub struct Appearance {
     pub text_tranform: Option<TextCase>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text_decoration_line: Option<TextDecoration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_align: Option<TextAlignHorizontal>,
+    /// Vertical alignment of the text within its box
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_align_vertical: Option<TextAlignVertical>,
+    /// First line indent in pixels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_indent: Option<f64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+fn text_decoration_line_value(t: TextDecoration) -> Cow<'static, str> {
+    Cow::Borrowed(match t {
+        TextDecoration::None => "none",
+        TextDecoration::Strikethrough => "line-through",
+        TextDecoration::Underline => "underline",
+    })
+}
+
+/// Left is the default, so it isn't set
+fn text_align_value(t: TextAlignHorizontal) -> Option<Cow<'static, str>> {
+    match t {
+        TextAlignHorizontal::Left => None,
+        TextAlignHorizontal::Right => Some(Cow::Borrowed("right")),
+        TextAlignHorizontal::Center => Some(Cow::Borrowed("center")),
+        TextAlignHorizontal::Justified => Some(Cow::Borrowed("justify")),
+    }
+}
+
+/// Block containers can align their content with `align-content`
+fn text_align_vertical_value(t: TextAlignVertical) -> Option<Cow<'static, str>> {
+    match t {
+        TextAlignVertical::Top => None,
+        TextAlignVertical::Center => Some(Cow::Borrowed("center")),
+        TextAlignVertical::Bottom => Some(Cow::Borrowed("end")),
+    }
+}
+
+/// Auto leaves the child stretched over its cells
+fn grid_align(a: GridChildAlign) -> Option<GridAlign> {
+    match a {
+        GridChildAlign::Auto => None,
+        GridChildAlign::Min => Some(GridAlign::Start),
+        GridChildAlign::Center => Some(GridAlign::Center),
+        GridChildAlign::Max => Some(GridAlign::End),
+    }
+}
+
+fn grid_align_value(a: GridAlign) -> Cow<'static, str> {
+    Cow::Borrowed(match a {
+        GridAlign::Start => "start",
+        GridAlign::Center => "center",
+        GridAlign::End => "end",
+    })
+}
+
+/// Rotations are written as such, anything else as a matrix
+fn transform_value([a, b, c, d]: [f64; 4]) -> Cow<'static, str> {
+    let is_rotation =
+        (a - d).abs() < 1e-6 && (b + c).abs() < 1e-6 && (a * d - b * c - 1.0).abs() < 1e-6;
+    if is_rotation {
+        let degrees = (b.atan2(a).to_degrees() * 100.0).round() / 100.0;
+        Cow::Owned(format!("rotate({degrees}deg)"))
+    } else {
+        Cow::Owned(format!("matrix({a}, {b}, {c}, {d}, 0, 0)"))
+    }
+}
+
+/// Small caps are part of the font rather than a transform
+fn text_transform_value(t: TextCase) -> Option<Cow<'static, str>> {
+    match t {
+        TextCase::Original => Some(Cow::Borrowed("none")),
+        TextCase::Upper => Some(Cow::Borrowed("uppercase")),
+        TextCase::Lower => Some(Cow::Borrowed("lowercase")),
+        TextCase::Title => Some(Cow::Borrowed("capitalize")),
+        TextCase::SmallCaps => None,
+        TextCase::SmallCapsForced => None,
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct FrameAppearance<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub background: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_blend_mode: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub border_radius: Option<[Length<'a>; 4]>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub box_shadow: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backdrop_filter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub stroke: Option<Stroke>,
+    /// Children are clipped to the node's padding box, and its border radius
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub clips_content: bool,
+    /// The shape of a mask the node's children are masked by
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clip_path: Option<String>,
+    /// The fills of a mask the node's children are masked by
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mask: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum FillRule {
+    NonZero,
+    EvenOdd,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-pub struct Stroke {
-    pub weights: [f64; 4],
-    pub style: StrokeStyle,
-    pub offset: StrokeAlign,
-    pub color: String,
+pub struct VectorPath<'a> {
+    pub path: Cow<'a, str>,
+    pub fill_rule: FillRule,
+}
+
+impl<'a> VectorPath<'a> {
+    fn from_figma_path(path: &'a FigmaPath) -> Self {
+        Self {
+            path: Cow::Borrowed(&path.path),
+            fill_rule: match path.winding_rule {
+                WindingRule::Nonzero => FillRule::NonZero,
+                WindingRule::Evenodd => FillRule::EvenOdd,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -129,13 +321,68 @@ pub struct Figma<'a> {
     pub name: Cow<'a, str>,
     pub id: Cow<'a, str>,
     pub r#type: FigmaNodeType,
+    /// The component an instance is of
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component_id: Option<Cow<'a, str>>,
+    /// Font size of a text node in pixels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<f64>,
+    /// Font weight of a text node, from 100 to 900
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font_weight: Option<f64>,
+    /// The node's absolute bounding box as `[x, y, width, height]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bounds: Option<[f64; 4]>,
+}
+
+/// How the node is presented to assistive technology, such as screen readers
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Accessibility {
+    /// Hidden from assistive technology, for decorative nodes
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    /// The `<title>` of an SVG
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+impl Accessibility {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum IntermediateNodeType<'a> {
-    Vector,
-    Text { text: Cow<'a, str> },
-    Frame { children: Vec<IntermediateNode<'a>> },
+    Vector {
+        /// The SVG viewBox as `[min-x, min-y, width, height]`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        view_box: Option<[f64; 4]>,
+        /// Paths to be filled with the node's fill colour
+        fill_geometry: Vec<VectorPath<'a>>,
+        /// Outlines of the strokes. These are filled with the stroke colour.
+        stroke_geometry: Vec<VectorPath<'a>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stroke: Option<String>,
+    },
+    Text {
+        text: Cow<'a, str>,
+        /// The text split into differently styled runs. Empty if it is
+        /// uniformly styled.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        runs: Vec<TextRun<'a>>,
+        /// The number of lines shown before the text is cut short with an
+        /// ellipsis
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_lines: Option<u32>,
+    },
+    Frame {
+        children: Vec<IntermediateNode<'a>>,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -144,12 +391,22 @@ pub struct IntermediateNode<'a> {
     pub figma: Option<Figma<'a>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub flex_container: Option<FlexContainer<'a>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grid_container: Option<GridContainer<'a>>,
     pub location: Location<'a>,
     pub appearance: Appearance,
     pub frame_appearance: FrameAppearance<'a>,
     pub node_type: IntermediateNodeType<'a>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub href: Option<Cow<'a, str>>,
+    /// The `id` of the node's element, for links to the node
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<String>,
+    /// The HTML element to write the node as, instead of a `div`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub element: Option<String>,
+    #[serde(default, skip_serializing_if = "Accessibility::is_empty")]
+    pub accessibility: Accessibility,
 }
 
 impl<'a> IntermediateNode<'a> {
@@ -157,12 +414,25 @@ impl<'a> IntermediateNode<'a> {
         node: &'a FigmaNode,
         parent: Option<&'a FigmaNode>,
         css_variables: &mut CSSVariablesMap,
+        options: &ConversionOptions,
     ) -> Self {
-        IntermediateNode {
+        let mut intermediate_node = IntermediateNode {
             figma: Some(Figma {
                 name: Cow::Borrowed(&node.name),
                 id: Cow::Borrowed(&node.id),
                 r#type: node.r#type,
+                component_id: node.component_id.as_deref().map(Cow::Borrowed),
+                font_size: match node.r#type {
+                    FigmaNodeType::Text => node.style.as_ref().map(|s| s.font_size),
+                    _ => None,
+                },
+                font_weight: match node.r#type {
+                    FigmaNodeType::Text => node.style.as_ref().map(|s| s.font_weight),
+                    _ => None,
+                },
+                bounds: node
+                    .absolute_bounding_box()
+                    .and_then(|b| Some([b.x?, b.y?, b.width?, b.height?])),
             }),
             flex_container: {
                 let align_items = match node.counter_axis_align_items {
@@ -180,22 +450,47 @@ impl<'a> IntermediateNode<'a> {
                     Some(PrimaryAxisAlignItems::Max) => Some(JustifyContent::FlexEnd),
                     Some(PrimaryAxisAlignItems::SpaceBetween) => Some(JustifyContent::SpaceBetween),
                 };
+                let wrap = match node.layout_wrap {
+                    Some(LayoutWrap::Wrap) => Some(FlexWrap {
+                        gap: Length::new_from_option_pixels(
+                            node.counter_axis_spacing.or(node.item_spacing),
+                        ),
+                        space_between: node.counter_axis_align_content
+                            == Some(CounterAxisAlignContent::SpaceBetween),
+                    }),
+                    _ => None,
+                };
                 match node.layout_mode {
                     Some(LayoutMode::Horizontal) => Some(FlexContainer {
                         align_items,
                         direction: FlexDirection::Row,
                         gap,
                         justify_content,
+                        wrap,
                     }),
                     Some(LayoutMode::Vertical) => Some(FlexContainer {
                         align_items,
                         direction: FlexDirection::Column,
                         gap,
                         justify_content,
+                        wrap,
                     }),
                     _ => None,
                 }
             },
+            grid_container: match node.layout_mode {
+                Some(LayoutMode::Grid) => Some(GridContainer {
+                    rows: node.grid_rows_sizing.clone().unwrap_or_else(|| {
+                        format!("repeat({}, 1fr)", node.grid_row_count.unwrap_or(1))
+                    }),
+                    columns: node.grid_columns_sizing.clone().unwrap_or_else(|| {
+                        format!("repeat({}, 1fr)", node.grid_column_count.unwrap_or(1))
+                    }),
+                    row_gap: Length::new_from_option_pixels(node.grid_row_gap),
+                    column_gap: Length::new_from_option_pixels(node.grid_column_gap),
+                }),
+                _ => None,
+            },
             location: Location {
                 padding: [
                     Length::new_from_option_pixels(node.padding_top),
@@ -225,6 +520,25 @@ impl<'a> IntermediateNode<'a> {
                     _ => None,
                 },
                 inset: Inset::from_figma_node(node, parent),
+                grid_area: match (
+                    parent.and_then(|p| p.layout_mode.as_ref()),
+                    node.grid_row_anchor_index,
+                    node.grid_column_anchor_index,
+                ) {
+                    (Some(LayoutMode::Grid), Some(row), Some(column))
+                        if node.layout_positioning.is_none() =>
+                    {
+                        Some(GridArea {
+                            row: row + 1,
+                            column: column + 1,
+                            row_span: node.grid_row_span.unwrap_or(1),
+                            column_span: node.grid_column_span.unwrap_or(1),
+                            justify_self: node.grid_child_horizontal_align.and_then(grid_align),
+                            align_self: node.grid_child_vertical_align.and_then(grid_align),
+                        })
+                    }
+                    _ => None,
+                },
                 height: match (parent, node) {
                     (
                         Some(FigmaNode {
@@ -277,7 +591,7 @@ impl<'a> IntermediateNode<'a> {
                             ..
                         },
                     ) if counter_axis_sizing_mode != &Some(AxisSizingMode::Fixed) => None,
-                    _ => absolute_bounding_box(node)
+                    _ => layout_box(node, parent)
                         .and_then(|b| b.height)
                         .map(|h| Length::new_from_option_pixels(Some(h))),
                 },
@@ -337,10 +651,29 @@ impl<'a> IntermediateNode<'a> {
                             ..
                         },
                     ) if counter_axis_sizing_mode != &Some(AxisSizingMode::Fixed) => None,
-                    _ => absolute_bounding_box(node)
+                    _ => layout_box(node, parent)
                         .and_then(|b| b.width)
                         .map(|w| Length::new_from_option_pixels(Some(w))),
                 },
+                transform: node_transform(node).map(|t| {
+                    let [[a, c, _], [b, d, _]] = t.0;
+                    [a, b, c, d]
+                }),
+                margin: match parent.and_then(|p| p.layout_mode.as_ref()) {
+                    Some(LayoutMode::Horizontal | LayoutMode::Vertical)
+                        if node.layout_positioning.is_none() =>
+                    {
+                        node_transform(node).and_then(|_| {
+                            let bounds = absolute_bounding_box(node)?;
+                            let size = layout_box(node, parent)?;
+                            Some([
+                                (bounds.height? - size.height?) / 2.0,
+                                (bounds.width? - size.width?) / 2.0,
+                            ])
+                        })
+                    }
+                    _ => None,
+                },
             },
             appearance: Appearance {
                 color: match node.r#type {
@@ -353,10 +686,37 @@ impl<'a> IntermediateNode<'a> {
                     }
                     _ => None,
                 },
-                font: node.font(css_variables),
-                opacity: node.opacity,
-                text_decoration_line: node.style.as_ref().and_then(|s| s.text_decoration),
-                text_tranform: node.style.as_ref().and_then(|s| s.text_case),
+                font: node.font(css_variables, options),
+                font_feature_settings: node
+                    .style
+                    .as_ref()
+                    .and_then(type_style_font_feature_settings),
+                letter_spacing: node
+                    .style
+                    .as_ref()
+                    .and_then(|s| s.letter_spacing)
+                    .filter(|l| *l != 0.0)
+                    .map(|l| Length::new_from_option_pixels(Some(l))),
+                opacity: node_opacity(node),
+                mix_blend_mode: node.blend_mode.filter(|b| blend_mode_value(*b).is_some()),
+                isolate: isolates_children(node),
+                text_decoration_line: node
+                    .style
+                    .as_ref()
+                    .map(TypeStyle::text_decoration)
+                    .filter(|d| *d != TextDecoration::None),
+                text_tranform: node
+                    .style
+                    .as_ref()
+                    .map(TypeStyle::text_case)
+                    .filter(|c| *c != TextCase::Original),
+                text_align: node.style.as_ref().and_then(|s| s.text_align_horizontal),
+                text_align_vertical: node.style.as_ref().and_then(|s| s.text_align_vertical),
+                text_indent: node
+                    .style
+                    .as_ref()
+                    .and_then(|s| s.paragraph_indent)
+                    .filter(|i| *i != 0.0),
                 preserve_whitespace: node
                     .characters
                     .as_deref()
@@ -380,7 +740,13 @@ impl<'a> IntermediateNode<'a> {
                     .unwrap_or(false),
             },
             frame_appearance: FrameAppearance {
-                background: node.background(css_variables),
+                background: node.background(css_variables, options),
+                background_blend_mode: match node.r#type {
+                    FigmaNodeType::Text
+                    | FigmaNodeType::Vector
+                    | FigmaNodeType::BooleanOperation => None,
+                    _ => fills_background_blend_mode(node, options),
+                },
                 border_radius: node
                     .rectangle_corner_radii()
                     .map(|[top, right, bottom, left]| {
@@ -392,65 +758,174 @@ impl<'a> IntermediateNode<'a> {
                         ]
                     }),
                 box_shadow: node.box_shadow(),
-                stroke: {
-                    let style =
-                        if node.stroke_dashes.as_ref().map(|sd| sd.is_empty()) == Some(false) {
-                            StrokeStyle::Dashed
-                        } else {
-                            StrokeStyle::Solid
-                        };
-                    match (
-                        stroke_color(node),
-                        &node.individual_stroke_weights,
-                        node.stroke_weight,
-                        node.stroke_align,
-                    ) {
-                        (
-                            Some(color),
-                            Some(StrokeWeights {
-                                top,
-                                right,
-                                bottom,
-                                left,
-                            }),
-                            _,
-                            Some(offset),
-                        ) => Some(Stroke {
-                            weights: [*top, *right, *bottom, *left],
-                            style,
-                            offset,
-                            color,
-                        }),
-                        (Some(color), _, Some(w), Some(offset)) => Some(Stroke {
-                            weights: [w, w, w, w],
-                            style,
-                            offset,
-                            color,
-                        }),
-                        _ => None,
-                    }
+                filter: node.filter(),
+                backdrop_filter: node.backdrop_filter(),
+                stroke: if matches!(
+                    node.r#type,
+                    FigmaNodeType::Vector | FigmaNodeType::BooleanOperation
+                ) {
+                    // Vector strokes are drawn from the stroke geometry
+                    None
+                } else {
+                    Stroke::from_figma_node(node)
                 },
+                clips_content: node.clips_content == Some(true),
+                clip_path: None,
+                mask: None,
             },
             node_type: match node.r#type {
                 FigmaNodeType::Vector | FigmaNodeType::BooleanOperation => {
-                    IntermediateNodeType::Vector
+                    let stroke = stroke_color(node);
+                    IntermediateNodeType::Vector {
+                        view_box: layout_box(node, parent)
+                            .and_then(|b| Some([0.0, 0.0, b.width?, b.height?])),
+                        fill_geometry: if node.fills().iter().any(|p| p.visible()) {
+                            node.fill_geometry()
+                                .iter()
+                                .map(VectorPath::from_figma_path)
+                                .collect()
+                        } else {
+                            vec![]
+                        },
+                        stroke_geometry: if stroke.is_some() {
+                            node.stroke_geometry()
+                                .iter()
+                                .map(VectorPath::from_figma_path)
+                                .collect()
+                        } else {
+                            vec![]
+                        },
+                        stroke,
+                    }
                 }
                 FigmaNodeType::Text => IntermediateNodeType::Text {
                     text: Cow::Borrowed(node.characters.as_deref().unwrap_or("")),
+                    runs: TextRun::from_figma_node(node, css_variables, options),
+                    max_lines: text_max_lines(node, parent),
                 },
                 _ => IntermediateNodeType::Frame {
-                    children: node
-                        .enabled_children()
-                        .map(|child| Self::from_figma_node(child, Some(node), css_variables))
-                        .collect(),
+                    children: Self::from_figma_children(node, css_variables, options),
                 },
             },
             href: node
                 .style
                 .as_ref()
                 .and_then(|s| s.hyperlink.as_ref())
-                .and_then(|h| h.url.as_deref().or_else(|| h.node_id.as_ref().map(|_| "#")))
-                .map(Cow::Borrowed),
+                .and_then(|hyperlink| hyperlink_href(hyperlink, options)),
+            anchor: options
+                .node_links
+                .get(&node.id)
+                .and_then(|link| link.anchor.clone()),
+            element: None,
+            accessibility: Accessibility::default(),
+        };
+        // Runs that differ in decoration each have their own
+        if let IntermediateNodeType::Text { runs, .. } = &intermediate_node.node_type {
+            if runs
+                .iter()
+                .any(|run| run.appearance.text_decoration_line.is_some())
+            {
+                intermediate_node.appearance.text_decoration_line = None;
+            }
+        }
+        // Links can't be nested, so a node containing links can't be one
+        if intermediate_node.href.is_none() && !intermediate_node.contains_link() {
+            intermediate_node.href = prototype_href(node, options);
+        }
+        intermediate_node
+    }
+
+    /// A mask and the siblings above it are grouped into a wrapper that masks
+    /// them, until the next mask
+    fn from_figma_children(
+        node: &'a FigmaNode,
+        css_variables: &mut CSSVariablesMap,
+        options: &ConversionOptions,
+    ) -> Vec<Self> {
+        let mut children = Vec::new();
+        let mut figma_children = node.enabled_children().peekable();
+        while let Some(child) = figma_children.next() {
+            if child.is_mask() {
+                let masked = std::iter::from_fn(|| figma_children.next_if(|c| !c.is_mask()))
+                    .map(|c| {
+                        // Laid out by the real parent, but placed within the mask's box
+                        let mut masked =
+                            Self::from_figma_node(c, Some(node), css_variables, options);
+                        masked.location.inset = Inset::within_sibling(c, child, node);
+                        masked
+                    })
+                    .collect();
+                children.push(Self::from_figma_mask(
+                    child,
+                    node,
+                    masked,
+                    css_variables,
+                    options,
+                ));
+            } else {
+                children.push(Self::from_figma_node(
+                    child,
+                    Some(node),
+                    css_variables,
+                    options,
+                ));
+            }
+        }
+        children
+    }
+
+    /**
+    A wrapper in place of the mask, which isn't drawn itself. The masked nodes
+    are positioned within the mask's box and clipped to its shape.
+     */
+    fn from_figma_mask(
+        mask: &'a FigmaNode,
+        parent: &'a FigmaNode,
+        masked: Vec<Self>,
+        css_variables: &mut CSSVariablesMap,
+        options: &ConversionOptions,
+    ) -> Self {
+        let mut wrapper = Self::from_figma_node(mask, Some(parent), css_variables, options);
+        wrapper.appearance = Appearance::default();
+        wrapper.frame_appearance = FrameAppearance {
+            clip_path: Some(mask_clip_path(mask)),
+            mask: mask_layers(mask, options),
+            ..FrameAppearance::default()
+        };
+        wrapper.node_type = IntermediateNodeType::Frame { children: masked };
+        if wrapper.contains_link() {
+            wrapper.href = None;
+        }
+        wrapper
+    }
+
+    /// Whether any of the node's descendants, or their text, are links
+    fn contains_link(&self) -> bool {
+        match &self.node_type {
+            IntermediateNodeType::Frame { children } => children
+                .iter()
+                .any(|child| child.href.is_some() || child.contains_link()),
+            IntermediateNodeType::Text { runs, .. } => runs.iter().any(|run| run.href.is_some()),
+            IntermediateNodeType::Vector { .. } => false,
+        }
+    }
+
+    /// The HTML element the node is written as
+    pub fn element_name(&self) -> &str {
+        match (&self.node_type, &self.href, &self.element) {
+            (IntermediateNodeType::Vector { .. }, _, _) => "svg",
+            (_, Some(_), _) => "a",
+            (_, None, Some(element)) => element,
+            (_, None, None) => "div",
+        }
+    }
+
+    /// The first text within the node
+    pub fn first_text(&self) -> Option<&str> {
+        match &self.node_type {
+            IntermediateNodeType::Text { text, .. } => Some(text),
+            IntermediateNodeType::Frame { children } => children.iter().find_map(Self::first_text),
+            IntermediateNodeType::Vector { .. } => None,
         }
     }
 
@@ -462,7 +937,40 @@ impl<'a> IntermediateNode<'a> {
     }
 
     pub fn naive_css_string(&self) -> String {
+        let max_lines = match &self.node_type {
+            IntermediateNodeType::Text { max_lines, .. } => *max_lines,
+            _ => None,
+        };
+        // More than one line is clamped, a single line doesn't wrap
+        let line_clamp = max_lines.filter(|l| *l > 1);
+        let single_line = max_lines == Some(1);
+        let border = self
+            .frame_appearance
+            .stroke
+            .as_ref()
+            .and_then(Stroke::border);
         let properties = &[
+            (
+                "-webkit-box-orient",
+                line_clamp.map(|_| Cow::Borrowed("vertical")),
+            ),
+            (
+                "-webkit-line-clamp",
+                line_clamp.map(|l| Cow::Owned(format!("{l}"))),
+            ),
+            (
+                "align-content",
+                self.appearance
+                    .text_align_vertical
+                    .and_then(text_align_vertical_value)
+                    .or_else(|| {
+                        self.flex_container
+                            .as_ref()
+                            .and_then(|c| c.wrap.as_ref())
+                            .filter(|w| w.space_between)
+                            .map(|_| Cow::Borrowed("space-between"))
+                    }),
+            ),
             (
                 "align-items",
                 self.flex_container
@@ -479,9 +987,21 @@ impl<'a> IntermediateNode<'a> {
                 "align-self",
                 match self.location.align_self {
                     Some(AlignSelf::Stretch) => Some(Cow::Borrowed("stretch")),
-                    _ => None,
+                    _ => self
+                        .location
+                        .grid_area
+                        .as_ref()
+                        .and_then(|a| a.align_self)
+                        .map(grid_align_value),
                 },
             ),
+            (
+                "backdrop-filter",
+                self.frame_appearance
+                    .backdrop_filter
+                    .as_deref()
+                    .map(Cow::Borrowed),
+            ),
             (
                 "background",
                 self.frame_appearance
@@ -497,12 +1017,47 @@ impl<'a> IntermediateNode<'a> {
                     .map(|[nw, ne, se, sw]| Cow::Owned(format!("{nw} {ne} {se} {sw}"))),
             ),
             (
-                "box-shadow",
+                "background-blend-mode",
                 self.frame_appearance
-                    .box_shadow
+                    .background_blend_mode
                     .as_deref()
                     .map(Cow::Borrowed),
             ),
+            (
+                "border-color",
+                border
+                    .as_ref()
+                    .map(|[_, _, color]| Cow::Borrowed(color.as_str())),
+            ),
+            (
+                "border-style",
+                border
+                    .as_ref()
+                    .map(|[_, style, _]| Cow::Borrowed(style.as_str())),
+            ),
+            (
+                "border-width",
+                border
+                    .as_ref()
+                    .map(|[width, _, _]| Cow::Borrowed(width.as_str())),
+            ),
+            (
+                "box-shadow",
+                // Strokes are drawn above the effects
+                match (
+                    self.frame_appearance
+                        .stroke
+                        .as_ref()
+                        .and_then(Stroke::box_shadow),
+                    self.frame_appearance.box_shadow.as_deref(),
+                ) {
+                    (Some(stroke), Some(effects)) => {
+                        Some(Cow::Owned(format!("{stroke}, {effects}")))
+                    }
+                    (Some(stroke), None) => Some(Cow::Owned(stroke)),
+                    (None, effects) => effects.map(Cow::Borrowed),
+                },
+            ),
             ("box-sizing", {
                 let Location {
                     width,
@@ -512,16 +1067,28 @@ impl<'a> IntermediateNode<'a> {
                 } = &self.location;
                 if (top != &Length::Zero || bottom != &Length::Zero) && height.is_some()
                     || (right != &Length::Zero || left != &Length::Zero) && width.is_some()
+                    || border.is_some() && (height.is_some() || width.is_some())
                 {
                     Some(Cow::Borrowed("border-box"))
                 } else {
                     None
                 }
             }),
+            (
+                "clip-path",
+                self.frame_appearance
+                    .clip_path
+                    .as_deref()
+                    .map(Cow::Borrowed),
+            ),
             ("color", self.appearance.color.as_deref().map(Cow::Borrowed)),
             (
                 "display",
-                self.flex_container.as_ref().map(|_| Cow::Borrowed("flex")),
+                match (&self.flex_container, &self.grid_container) {
+                    (Some(_), _) => Some(Cow::Borrowed("flex")),
+                    (None, Some(_)) => Some(Cow::Borrowed("grid")),
+                    (None, None) => line_clamp.map(|_| Cow::Borrowed("-webkit-box")),
+                },
             ),
             (
                 "flex-direction",
@@ -533,21 +1100,80 @@ impl<'a> IntermediateNode<'a> {
                 }),
             ),
             ("fill", self.appearance.fill.as_deref().map(Cow::Borrowed)),
+            (
+                "filter",
+                self.frame_appearance.filter.as_deref().map(Cow::Borrowed),
+            ),
             (
                 "flex-grow",
                 self.location.flex_grow.map(|g| Cow::Owned(format!("{g}"))),
             ),
-            ("font", self.appearance.font.as_deref().map(Cow::Borrowed)),
+            (
+                "flex-wrap",
+                self.flex_container
+                    .as_ref()
+                    .and_then(|c| c.wrap.as_ref())
+                    .map(|_| Cow::Borrowed("wrap")),
+            ),
+            (
+                "font",
+                self.appearance
+                    .font
+                    .as_ref()
+                    .map(|f| Cow::Owned(f.css_value())),
+            ),
+            (
+                "font-feature-settings",
+                self.appearance
+                    .font_feature_settings
+                    .as_deref()
+                    .map(Cow::Borrowed),
+            ),
             (
                 "gap",
-                self.flex_container.as_ref().and_then(|c| {
-                    if c.gap == Length::Zero {
-                        None
-                    } else {
-                        Some(Cow::Owned(format!("{}", c.gap)))
-                    }
+                self.flex_container
+                    .as_ref()
+                    .map(|c| match (&c.wrap, c.direction) {
+                        (None, _) => (&c.gap, &c.gap),
+                        (Some(w), FlexDirection::Row) => (&w.gap, &c.gap),
+                        (Some(w), FlexDirection::Column) => (&c.gap, &w.gap),
+                    })
+                    .or_else(|| {
+                        self.grid_container
+                            .as_ref()
+                            .map(|g| (&g.row_gap, &g.column_gap))
+                    })
+                    .and_then(|(row_gap, column_gap)| {
+                        if row_gap == column_gap {
+                            Some(row_gap)
+                                .filter(|gap| *gap != &Length::Zero)
+                                .map(|gap| Cow::Owned(format!("{gap}")))
+                        } else {
+                            Some(Cow::Owned(format!("{row_gap} {column_gap}")))
+                        }
+                    }),
+            ),
+            (
+                "grid-area",
+                self.location.grid_area.as_ref().map(|a| {
+                    Cow::Owned(format!(
+                        "{} / {} / span {} / span {}",
+                        a.row, a.column, a.row_span, a.column_span
+                    ))
                 }),
             ),
+            (
+                "grid-template-columns",
+                self.grid_container
+                    .as_ref()
+                    .map(|g| Cow::Borrowed(g.columns.as_str())),
+            ),
+            (
+                "grid-template-rows",
+                self.grid_container
+                    .as_ref()
+                    .map(|g| Cow::Borrowed(g.rows.as_str())),
+            ),
             (
                 "height",
                 self.location
@@ -564,6 +1190,10 @@ impl<'a> IntermediateNode<'a> {
                         Cow::Owned(format!("{top} {right} {bottom} {left}"))
                     }),
             ),
+            (
+                "isolation",
+                self.appearance.isolate.then_some(Cow::Borrowed("isolate")),
+            ),
             (
                 "justify-content",
                 self.flex_container.as_ref().and_then(|c| {
@@ -575,37 +1205,62 @@ impl<'a> IntermediateNode<'a> {
                     })
                 }),
             ),
+            (
+                "justify-self",
+                self.location
+                    .grid_area
+                    .as_ref()
+                    .and_then(|a| a.justify_self)
+                    .map(grid_align_value),
+            ),
+            (
+                "letter-spacing",
+                self.appearance
+                    .letter_spacing
+                    .as_ref()
+                    .map(|l| Cow::Owned(format!("{l}"))),
+            ),
+            (
+                "margin",
+                self.location
+                    .margin
+                    .map(|[v, h]| Cow::Owned(format!("{v}px {h}px"))),
+            ),
+            (
+                "mask",
+                self.frame_appearance.mask.as_deref().map(Cow::Borrowed),
+            ),
+            (
+                "mix-blend-mode",
+                self.appearance
+                    .mix_blend_mode
+                    .and_then(blend_mode_value)
+                    .map(Cow::Borrowed),
+            ),
             (
                 "opacity",
                 self.appearance.opacity.map(|o| Cow::Owned(format!("{o}"))),
             ),
             (
                 "outline",
-                self.frame_appearance.stroke.as_ref().and_then(|s| {
-                    // Let top border represent the weight of all the borders
-                    let width = s.weights[0];
-                    if width == 0.0 {
-                        return None;
-                    }
-                    let style = match s.style {
-                        StrokeStyle::Solid => "solid",
-                        StrokeStyle::Dashed => "dashed",
-                    };
-                    let color = &s.color;
-                    Some(Cow::Owned(format!("{width}px {style} {color}")))
-                }),
+                self.frame_appearance
+                    .stroke
+                    .as_ref()
+                    .and_then(Stroke::outline)
+                    .map(Cow::Owned),
             ),
             (
                 "outline-offset",
-                self.frame_appearance.stroke.as_ref().and_then(|s| {
-                    // Let top border represent the weight of all the borders
-                    let width = s.weights[0];
-                    match s.offset {
-                        StrokeAlign::Inside => Some(Cow::Owned(format!("-{width}px"))),
-                        StrokeAlign::Outside => None,
-                        StrokeAlign::Center => Some(Cow::Owned(format!("-{}px", width / 2.0))),
-                    }
-                }),
+                self.frame_appearance
+                    .stroke
+                    .as_ref()
+                    .and_then(Stroke::outline_offset)
+                    .map(Cow::Owned),
+            ),
+            (
+                "overflow",
+                (self.frame_appearance.clips_content || max_lines.is_some())
+                    .then_some(Cow::Borrowed("hidden")),
             ),
             ("padding", {
                 let p = &self.location.padding;
@@ -627,30 +1282,39 @@ impl<'a> IntermediateNode<'a> {
                     None
                 },
             ),
+            (
+                "text-align",
+                self.appearance.text_align.and_then(text_align_value),
+            ),
             (
                 "text-decoration-line",
-                self.appearance.text_decoration_line.map(|t| {
-                    Cow::Borrowed(match t {
-                        TextDecoration::Strikethrough => "line-through",
-                        TextDecoration::Underline => "underline",
-                    })
-                }),
+                self.appearance
+                    .text_decoration_line
+                    .map(text_decoration_line_value),
+            ),
+            (
+                "text-indent",
+                self.appearance
+                    .text_indent
+                    .map(|i| Cow::Owned(format!("{i}px"))),
+            ),
+            (
+                "text-overflow",
+                single_line.then_some(Cow::Borrowed("ellipsis")),
             ),
             (
                 "text-transform",
-                self.appearance.text_tranform.and_then(|t| match t {
-                    TextCase::Upper => Some(Cow::Borrowed("uppercase")),
-                    TextCase::Lower => Some(Cow::Borrowed("lowercase")),
-                    TextCase::Title => Some(Cow::Borrowed("capitalize")),
-                    TextCase::SmallCaps => None,
-                    TextCase::SmallCapsForced => None,
-                }),
+                self.appearance.text_tranform.and_then(text_transform_value),
             ),
+            ("transform", self.location.transform.map(transform_value)),
             (
                 "white-space",
-                self.appearance
-                    .preserve_whitespace
-                    .then_some(Cow::Borrowed("pre-wrap")),
+                match (single_line, self.appearance.preserve_whitespace) {
+                    (true, true) => Some(Cow::Borrowed("pre")),
+                    (true, false) => Some(Cow::Borrowed("nowrap")),
+                    (false, true) => Some(Cow::Borrowed("pre-wrap")),
+                    (false, false) => None,
+                },
             ),
             (
                 "width",
@@ -661,6 +1325,14 @@ impl<'a> IntermediateNode<'a> {
             ),
         ];
         let mut output = String::new();
+        for (name, value) in element_reset_css(self.element_name()) {
+            if !properties.iter().any(|(n, v)| n == name && v.is_some()) {
+                output.push_str(name);
+                output.push_str(": ");
+                output.push_str(value);
+                output.push(';');
+            }
+        }
         for (name, value) in properties.iter() {
             if let Some(v) = value {
                 output.push_str(name);
@@ -672,3 +1344,200 @@ impl<'a> IntermediateNode<'a> {
         output
     }
 }
+
+/// Elements without content, that are written as self-closing tags
+pub fn is_void_element(element: &str) -> bool {
+    matches!(element, "hr" | "img" | "input")
+}
+
+/// Undo the browser's default styles of an element, where they would get in
+/// the way of the node's own
+fn element_reset_css(element: &str) -> &'static [(&'static str, &'static str)] {
+    match element {
+        "button" | "input" => &[
+            ("background", "none"),
+            ("border", "none"),
+            ("box-sizing", "content-box"),
+            ("color", "inherit"),
+            ("display", "block"),
+            ("font", "inherit"),
+            ("padding", "0"),
+            ("text-align", "inherit"),
+        ],
+        "ol" | "ul" => &[("list-style", "none"), ("margin", "0"), ("padding", "0")],
+        "blockquote" | "figure" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "p" => {
+            &[("margin", "0")]
+        }
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntermediateNodeType;
+    use crate::{figma_node_to_intermediate_node, ConversionOptions};
+    use figma_schema::Node;
+
+    /// The CSS of the node and of each of its children
+    fn css(node: serde_json::Value) -> Vec<String> {
+        let node: Node = serde_json::from_value(node).unwrap();
+        let intermediate_node = figma_node_to_intermediate_node(
+            &node,
+            &mut Default::default(),
+            &ConversionOptions::default(),
+        );
+        let mut css = vec![intermediate_node.naive_css_string()];
+        if let IntermediateNodeType::Frame { children } = &intermediate_node.node_type {
+            css.extend(children.iter().map(|child| child.naive_css_string()));
+        }
+        css
+    }
+
+    #[test]
+    fn wrapping_auto_layout() {
+        let rows = css(serde_json::json!({
+            "id": "1:1",
+            "name": "Tags",
+            "type": "FRAME",
+            "layoutMode": "HORIZONTAL",
+            "layoutWrap": "WRAP",
+            "itemSpacing": 8.0,
+            "counterAxisSpacing": 16.0
+        }));
+        assert_eq!(
+            rows,
+            ["display: flex;flex-direction: row;flex-wrap: wrap;gap: 16px 8px;"]
+        );
+
+        let columns = css(serde_json::json!({
+            "id": "1:1",
+            "name": "Tags",
+            "type": "FRAME",
+            "layoutMode": "VERTICAL",
+            "layoutWrap": "WRAP",
+            "itemSpacing": 8.0,
+            "counterAxisAlignContent": "SPACE_BETWEEN"
+        }));
+        assert_eq!(
+            columns,
+            ["align-content: space-between;display: flex;flex-direction: column;flex-wrap: wrap;gap: 8px;"]
+        );
+    }
+
+    #[test]
+    fn grid_auto_layout() {
+        let css = css(serde_json::json!({
+            "id": "1:1",
+            "name": "Cards",
+            "type": "FRAME",
+            "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 300.0, "height": 200.0 },
+            "layoutMode": "GRID",
+            "gridRowCount": 2,
+            "gridColumnCount": 3,
+            "gridRowGap": 4.0,
+            "gridColumnGap": 8.0,
+            "children": [{
+                "id": "1:2",
+                "name": "Card",
+                "type": "FRAME",
+                "absoluteBoundingBox": { "x": 0.0, "y": 100.0, "width": 200.0, "height": 100.0 },
+                "gridRowAnchorIndex": 1,
+                "gridColumnAnchorIndex": 0,
+                "gridColumnSpan": 2,
+                "gridChildHorizontalAlign": "CENTER"
+            }]
+        }));
+        assert_eq!(
+            css,
+            [
+                "display: grid;gap: 4px 8px;grid-template-columns: repeat(3, 1fr);grid-template-rows: repeat(2, 1fr);height: 200px;width: 300px;",
+                "grid-area: 2 / 1 / span 1 / span 2;height: 100px;justify-self: center;width: 200px;"
+            ]
+        );
+    }
+
+    /// A label rotated a quarter turn anticlockwise in a row is sized before
+    /// the rotation, with margins making room for its rotated bounds
+    #[test]
+    fn rotated_nodes() {
+        let css = css(serde_json::json!({
+            "id": "1:1",
+            "name": "Row",
+            "type": "FRAME",
+            "layoutMode": "HORIZONTAL",
+            "children": [{
+                "id": "1:2",
+                "name": "Label",
+                "type": "FRAME",
+                "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 20.0, "height": 100.0 },
+                "size": { "x": 100.0, "y": 20.0 },
+                "relativeTransform": [[0.0, 1.0, 0.0], [-1.0, 0.0, 100.0]]
+            }]
+        }));
+        assert_eq!(
+            css[1],
+            "height: 20px;margin: 40px -40px;transform: rotate(-90deg);width: 100px;"
+        );
+    }
+
+    #[test]
+    fn blend_modes() {
+        let css = css(serde_json::json!({
+            "id": "1:1",
+            "name": "Card",
+            "type": "FRAME",
+            "blendMode": "NORMAL",
+            "children": [{
+                "id": "1:2",
+                "name": "Shade",
+                "type": "RECTANGLE",
+                "blendMode": "MULTIPLY"
+            }]
+        }));
+        assert_eq!(css, ["isolation: isolate;", "mix-blend-mode: multiply;"]);
+    }
+
+    #[test]
+    fn masked_nodes_are_laid_out_by_their_parent() {
+        let node: Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Row",
+            "type": "FRAME",
+            "layoutMode": "HORIZONTAL",
+            "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 100.0, "height": 40.0 },
+            "children": [{
+                "id": "1:2",
+                "name": "Circle",
+                "type": "ELLIPSE",
+                "isMask": true,
+                "absoluteBoundingBox": { "x": 10.0, "y": 0.0, "width": 40.0, "height": 40.0 }
+            }, {
+                "id": "1:3",
+                "name": "Photo",
+                "type": "RECTANGLE",
+                "layoutGrow": 1.0,
+                "layoutAlign": "STRETCH",
+                "constraints": { "vertical": "TOP", "horizontal": "LEFT" },
+                "absoluteBoundingBox": { "x": 20.0, "y": 5.0, "width": 30.0, "height": 30.0 }
+            }]
+        }))
+        .unwrap();
+        let row = figma_node_to_intermediate_node(
+            &node,
+            &mut Default::default(),
+            &ConversionOptions::default(),
+        );
+        let IntermediateNodeType::Frame { children } = &row.node_type else {
+            panic!("The row isn't a frame");
+        };
+        let IntermediateNodeType::Frame { children } = &children[0].node_type else {
+            panic!("The mask isn't a frame");
+        };
+        assert_eq!(
+            children[0].naive_css_string(),
+            "align-self: stretch;flex-grow: 1;\
+             inset: calc(100% * 0 / 1 + 5px) auto auto calc(100% * 0 / 1 + 10px);\
+             position: absolute;"
+        );
+    }
+}
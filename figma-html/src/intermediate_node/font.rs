@@ -0,0 +1,81 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Length, LengthUnit};
+
+/// The line height in the unit the designer chose
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub enum LineHeight {
+    /// The font's intrinsic line height
+    Normal,
+    /// A multiple of the font size, so that it scales with the font
+    Number(f64),
+    Length(Length<'static>),
+}
+
+impl fmt::Display for LineHeight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Normal => write!(f, "normal"),
+            Self::Number(number) => write!(f, "{number}"),
+            Self::Length(length) => write!(f, "{length}"),
+        }
+    }
+}
+
+/**
+A CSS `font` shorthand.
+
+The size and line height are lengths, so that they can be converted to other
+units like other lengths. A font from a text style is written as the style's
+CSS variable, which is set to the shorthand.
+ */
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Font {
+    pub italic: bool,
+    pub small_caps: bool,
+    pub weight: u16,
+    pub size: Length<'static>,
+    pub line_height: LineHeight,
+    /// The font family list, quoted as CSS
+    pub family: String,
+    /// The name of the text style's CSS variable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variable: Option<String>,
+}
+
+impl Font {
+    /// The font size in pixels, if it is in pixels
+    pub fn size_px(&self) -> Option<f64> {
+        match self.size {
+            Length::Value {
+                unit: LengthUnit::Px,
+                value,
+            } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The value of the `font` property, which is the text style's CSS
+    /// variable if there is one
+    pub fn css_value(&self) -> String {
+        match self.variable.as_deref() {
+            Some(name) => format!("var({name})"),
+            None => self.to_string(),
+        }
+    }
+}
+
+/// The shorthand, whatever the variable
+impl fmt::Display for Font {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let style = if self.italic { "italic" } else { "" };
+        let variant = if self.small_caps { "small-caps" } else { "" };
+        write!(
+            f,
+            "{style} {variant} {} {}/{} {}",
+            self.weight, self.size, self.line_height, self.family
+        )
+    }
+}
@@ -5,11 +5,18 @@ use std::{
     fmt::{Display, Write},
 };
 
-use super::{IntermediateNode, IntermediateNodeType};
+use super::{
+    is_void_element, FillRule, IntermediateNode, IntermediateNodeType, Stylesheet, TextRun,
+    VectorPath,
+};
 
 pub struct HtmlFormatter<'a> {
     pub intermediate_node: &'a IntermediateNode<'a>,
     pub nesting_depth: u16,
+    /// Reference the stylesheet's classes instead of using inline styles
+    pub stylesheet: Option<&'a Stylesheet>,
+    /// Additional attributes for this element, such as variant data attributes
+    pub attributes: &'a [(String, String)],
 }
 
 use html_escape::{encode_double_quoted_attribute, encode_text};
@@ -51,22 +58,32 @@ fn end_tag(f: &mut impl Write, level: u16, name: &str) -> std::fmt::Result {
     write!(f, ">")
 }
 
-pub fn format_css(level: u16, naive_css: &str) -> Result<String> {
+/// Minify CSS declarations and write each as `property: value`
+pub fn minified_css_declarations(naive_css: &str) -> Result<Vec<String>> {
     let mut style_attribute = StyleAttribute::parse(naive_css, ParserOptions::default())
         .map_err(|err| anyhow!("Failed to parse CSS\n{err}"))?;
 
     style_attribute.minify(MinifyOptions::default());
 
+    style_attribute
+        .declarations
+        .declarations
+        .iter()
+        .map(|declaration| {
+            declaration
+                .to_css_string(false, PrinterOptions::default())
+                .context("Failed to write CSS property")
+        })
+        .collect()
+}
+
+pub fn format_css(level: u16, naive_css: &str) -> Result<String> {
     let mut output = String::new();
 
-    for declaration in style_attribute.declarations.declarations.iter() {
+    for declaration in minified_css_declarations(naive_css)? {
         output.push('\n');
         indent(&mut output, level + 1)?;
-        output.push_str(
-            &declaration
-                .to_css_string(false, PrinterOptions::default())
-                .context("Failed to write CSS property")?,
-        );
+        output.push_str(&declaration);
         output.push(';');
     }
     output.push('\n');
@@ -75,56 +92,216 @@ pub fn format_css(level: u16, naive_css: &str) -> Result<String> {
     Ok(output)
 }
 
+fn style_attribute(f: &mut impl Write, level: u16, naive_css: &str) -> std::fmt::Result {
+    let css = format_css(level + 1, naive_css).unwrap();
+    if !css.trim().is_empty() {
+        attribute(f, level, "style", &css)?;
+    }
+    Ok(())
+}
+
 fn common_attributes(
     f: &mut impl Write,
     level: u16,
     intermediate_node: &IntermediateNode<'_>,
+    stylesheet: Option<&Stylesheet>,
+    attributes: &[(String, String)],
 ) -> std::fmt::Result {
     if let Some(figma) = intermediate_node.figma.as_ref() {
         attribute(f, level, "data-figma-name", figma.name.borrow())?;
         attribute(f, level, "data-figma-id", figma.id.borrow())?;
     }
+    if let Some(anchor) = intermediate_node.anchor.as_deref() {
+        attribute(f, level, "id", anchor)?;
+    }
+    for (name, value) in attributes.iter() {
+        attribute(f, level, name, value)?;
+    }
     if let Some(href) = intermediate_node.href.as_deref() {
-        attribute(f, level, "href", href.borrow())?;
+        attribute(f, level, "href", href)?;
     }
-    let css = format_css(level + 1, &intermediate_node.naive_css_string()).unwrap();
-    if !css.trim().is_empty() {
-        attribute(f, level, "style", &css)?;
+    match intermediate_node.element_name() {
+        "button" => attribute(f, level, "type", "button")?,
+        "input" => {
+            if let Some(placeholder) = intermediate_node.first_text() {
+                attribute(f, level, "placeholder", placeholder)?;
+            }
+        }
+        _ => {}
+    }
+    let accessibility = &intermediate_node.accessibility;
+    if let Some(role) = accessibility.role.as_deref() {
+        attribute(f, level, "role", role)?;
+    }
+    if let Some(label) = accessibility.label.as_deref() {
+        attribute(f, level, "aria-label", label)?;
+    }
+    if accessibility.hidden {
+        attribute(f, level, "aria-hidden", "true")?;
+    }
+    match stylesheet {
+        Some(stylesheet) => match stylesheet.node_class_name(intermediate_node) {
+            Some(class_name) => attribute(f, level, "class", class_name),
+            None => Ok(()),
+        },
+        None => style_attribute(f, level, &intermediate_node.naive_css_string()),
+    }
+}
+
+fn path(
+    f: &mut impl Write,
+    level: u16,
+    vector_path: &VectorPath<'_>,
+    fill: Option<&str>,
+) -> std::fmt::Result {
+    open_start_tag(f, level, "path")?;
+    attribute(f, level, "d", &vector_path.path)?;
+    if vector_path.fill_rule == FillRule::EvenOdd {
+        attribute(f, level, "fill-rule", "evenodd")?;
+    }
+    if let Some(fill) = fill {
+        attribute(f, level, "fill", fill)?;
+    }
+    close_self_closing_tag(f, level)
+}
+
+fn svg_title(
+    f: &mut impl Write,
+    level: u16,
+    intermediate_node: &IntermediateNode<'_>,
+) -> std::fmt::Result {
+    if let Some(title) = intermediate_node.accessibility.title.as_deref() {
+        open_start_tag(f, level, "title")?;
+        close_start_tag(f, level)?;
+        text(f, level, title)?;
+        end_tag(f, level, "title")?;
     }
     Ok(())
 }
 
+fn text_run(
+    f: &mut impl Write,
+    level: u16,
+    run: &TextRun<'_>,
+    stylesheet: Option<&Stylesheet>,
+) -> std::fmt::Result {
+    let naive_css = run.naive_css_string();
+    if run.href.is_none() && naive_css.is_empty() {
+        return text(f, level, &run.text);
+    }
+    let name = if run.href.is_some() { "a" } else { "span" };
+    open_start_tag(f, level, name)?;
+    if let Some(href) = run.href.as_deref() {
+        attribute(f, level, "href", href)?;
+    }
+    match stylesheet {
+        Some(stylesheet) => {
+            if let Some(class_name) = stylesheet.class_name(&naive_css) {
+                attribute(f, level, "class", class_name)?;
+            }
+        }
+        None => style_attribute(f, level, &naive_css)?,
+    }
+    close_start_tag(f, level)?;
+    text(f, level, &run.text)?;
+    end_tag(f, level, name)
+}
+
 impl<'a> Display for HtmlFormatter<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let container_type = if self.intermediate_node.href.is_some() {
-            "a"
-        } else {
-            "div"
-        };
+        let container_type = self.intermediate_node.element_name();
+        if is_void_element(container_type) {
+            open_start_tag(f, self.nesting_depth, container_type)?;
+            common_attributes(
+                f,
+                self.nesting_depth,
+                self.intermediate_node,
+                self.stylesheet,
+                self.attributes,
+            )?;
+            return close_self_closing_tag(f, self.nesting_depth);
+        }
         match &self.intermediate_node.node_type {
-            IntermediateNodeType::Vector => {
+            IntermediateNodeType::Vector {
+                view_box,
+                fill_geometry,
+                stroke_geometry,
+                stroke,
+            } => {
                 open_start_tag(f, self.nesting_depth, "svg")?;
-                common_attributes(f, self.nesting_depth, self.intermediate_node)?;
-                attribute(f, self.nesting_depth, "viewBox", "0 0 1 1")?;
-                close_start_tag(f, self.nesting_depth)?;
-                open_start_tag(f, self.nesting_depth + 1, "rect")?;
-                attribute(f, self.nesting_depth + 1, "x", "-0.2")?;
-                attribute(f, self.nesting_depth + 1, "y", "-0.2")?;
-                attribute(f, self.nesting_depth + 1, "width", "1.4")?;
-                attribute(f, self.nesting_depth + 1, "height", "1.4")?;
-                close_self_closing_tag(f, self.nesting_depth + 1)?;
+                common_attributes(
+                    f,
+                    self.nesting_depth,
+                    self.intermediate_node,
+                    self.stylesheet,
+                    self.attributes,
+                )?;
+                if fill_geometry.is_empty() && stroke_geometry.is_empty() {
+                    // Without geometry (the file wasn't fetched with
+                    // `geometry=paths`) fall back to a placeholder
+                    attribute(f, self.nesting_depth, "viewBox", "0 0 1 1")?;
+                    close_start_tag(f, self.nesting_depth)?;
+                    svg_title(f, self.nesting_depth + 1, self.intermediate_node)?;
+                    open_start_tag(f, self.nesting_depth + 1, "rect")?;
+                    attribute(f, self.nesting_depth + 1, "x", "-0.2")?;
+                    attribute(f, self.nesting_depth + 1, "y", "-0.2")?;
+                    attribute(f, self.nesting_depth + 1, "width", "1.4")?;
+                    attribute(f, self.nesting_depth + 1, "height", "1.4")?;
+                    close_self_closing_tag(f, self.nesting_depth + 1)?;
+                } else {
+                    if let Some([x, y, width, height]) = view_box {
+                        attribute(
+                            f,
+                            self.nesting_depth,
+                            "viewBox",
+                            &format!("{x} {y} {width} {height}"),
+                        )?;
+                    }
+                    // Strokes aligned outside or centred extend beyond the bounds
+                    attribute(f, self.nesting_depth, "overflow", "visible")?;
+                    close_start_tag(f, self.nesting_depth)?;
+                    svg_title(f, self.nesting_depth + 1, self.intermediate_node)?;
+                    for fill_path in fill_geometry.iter() {
+                        path(f, self.nesting_depth + 1, fill_path, None)?;
+                    }
+                    for stroke_path in stroke_geometry.iter() {
+                        path(f, self.nesting_depth + 1, stroke_path, stroke.as_deref())?;
+                    }
+                }
                 end_tag(f, self.nesting_depth, "svg")?;
             }
-            IntermediateNodeType::Text { text: inner_text } => {
+            IntermediateNodeType::Text {
+                text: inner_text,
+                runs,
+                ..
+            } => {
                 open_start_tag(f, self.nesting_depth, container_type)?;
-                common_attributes(f, self.nesting_depth, self.intermediate_node)?;
+                common_attributes(
+                    f,
+                    self.nesting_depth,
+                    self.intermediate_node,
+                    self.stylesheet,
+                    self.attributes,
+                )?;
                 close_start_tag(f, self.nesting_depth)?;
-                text(f, self.nesting_depth, inner_text)?;
+                if runs.is_empty() {
+                    text(f, self.nesting_depth, inner_text)?;
+                } else {
+                    for run in runs.iter() {
+                        text_run(f, self.nesting_depth + 1, run, self.stylesheet)?;
+                    }
+                }
                 end_tag(f, self.nesting_depth, container_type)?;
             }
             IntermediateNodeType::Frame { children } => {
                 open_start_tag(f, self.nesting_depth, container_type)?;
-                common_attributes(f, self.nesting_depth, self.intermediate_node)?;
+                common_attributes(
+                    f,
+                    self.nesting_depth,
+                    self.intermediate_node,
+                    self.stylesheet,
+                    self.attributes,
+                )?;
                 close_start_tag(f, self.nesting_depth)?;
                 for child in children.iter() {
                     write!(
@@ -132,7 +309,9 @@ impl<'a> Display for HtmlFormatter<'a> {
                         "{}",
                         HtmlFormatter {
                             nesting_depth: self.nesting_depth + 1,
-                            intermediate_node: child
+                            intermediate_node: child,
+                            stylesheet: self.stylesheet,
+                            attributes: &[],
                         }
                     )?;
                 }
@@ -142,3 +321,46 @@ impl<'a> Display for HtmlFormatter<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::HtmlFormatter;
+    use crate::{figma_node_to_intermediate_node, ConversionOptions};
+
+    #[test]
+    fn vector_paths() {
+        let node: figma_schema::Node = serde_json::from_value(serde_json::json!({
+            "id": "1:2",
+            "name": "Tick",
+            "type": "VECTOR",
+            "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 20.0, "height": 10.0 },
+            "fills": [{ "type": "SOLID", "color": { "r": 0.0, "g": 0.0, "b": 1.0, "a": 1.0 } }],
+            "fillGeometry": [{ "path": "M0 0L20 10L0 10Z", "windingRule": "EVENODD" }],
+            "strokes": [{ "type": "SOLID", "color": { "r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0 } }],
+            "strokeGeometry": [{ "path": "M0 0L20 0", "windingRule": "NONZERO" }]
+        }))
+        .unwrap();
+        let intermediate_node = figma_node_to_intermediate_node(
+            &node,
+            &mut Default::default(),
+            &ConversionOptions::default(),
+        );
+        let html = HtmlFormatter {
+            intermediate_node: &intermediate_node,
+            nesting_depth: 0,
+            stylesheet: None,
+            attributes: &[],
+        }
+        .to_string();
+        assert!(html.starts_with("<svg\n"), "{html}");
+        assert!(html.contains(r#"viewBox="0 0 20 10""#), "{html}");
+        assert!(
+            html.contains("<path\n    d=\"M0 0L20 10L0 10Z\"\n    fill-rule=\"evenodd\"\n  />"),
+            "{html}"
+        );
+        assert!(
+            html.contains("<path\n    d=\"M0 0L20 0\"\n    fill=\"rgb(255, 0, 0, 1)\"\n  />"),
+            "{html}"
+        );
+    }
+}
@@ -2,11 +2,11 @@ use std::fmt;
 
 use figma_schema::{
     LayoutConstraintHorizontal, LayoutConstraintVertical, LayoutMode, LayoutPositioning,
-    Node as FigmaNode,
+    Node as FigmaNode, Rectangle,
 };
 use serde::{Deserialize, Serialize};
 
-use crate::css_properties::absolute_bounding_box;
+use crate::css_properties::layout_box;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum Inset {
@@ -27,13 +27,39 @@ impl Inset {
         let parent = parent?;
         if matches!(
             parent.layout_mode,
-            Some(LayoutMode::Horizontal) | Some(LayoutMode::Vertical)
+            Some(LayoutMode::Horizontal | LayoutMode::Vertical | LayoutMode::Grid)
         ) && !matches!(node.layout_positioning, Some(LayoutPositioning::Absolute))
         {
             return None;
         }
-        let parent_rectangle = absolute_bounding_box(parent)?;
-        let node_rectangle = absolute_bounding_box(node)?;
+        Self::from_rectangles(
+            node,
+            layout_box(parent, None)?,
+            layout_box(node, Some(parent))?,
+        )
+    }
+
+    /**
+    The inset of a node within a sibling's box rather than its parent's, such as
+    within the mask it is masked by. Both boxes are in the parent's coordinates.
+     */
+    pub fn within_sibling<'a>(
+        node: &'a FigmaNode,
+        sibling: &'a FigmaNode,
+        parent: &'a FigmaNode,
+    ) -> Option<[Inset; 4]> {
+        Self::from_rectangles(
+            node,
+            layout_box(sibling, Some(parent))?,
+            layout_box(node, Some(parent))?,
+        )
+    }
+
+    fn from_rectangles(
+        node: &FigmaNode,
+        parent_rectangle: Rectangle,
+        node_rectangle: Rectangle,
+    ) -> Option<[Inset; 4]> {
         let top_distance = node_rectangle.y? - parent_rectangle.y?;
         let right_distance = parent_rectangle.x? + parent_rectangle.width?
             - node_rectangle.x?
@@ -0,0 +1,430 @@
+use std::fmt::{Display, Write};
+
+use html_escape::encode_double_quoted_attribute;
+
+use super::{
+    is_void_element, minified_css_declarations, CSSVariablesMap, FillRule, IntermediateNode,
+    IntermediateNodeType, Stylesheet, TextRun, VectorPath,
+};
+
+/**
+Write an intermediate node as JSX.
+
+Elements are the same as `HtmlFormatter` writes, but with React's attribute
+names and `style` objects. Text is written as JavaScript strings so that JSX
+doesn't collapse its whitespace.
+ */
+pub struct JsxFormatter<'a> {
+    pub intermediate_node: &'a IntermediateNode<'a>,
+    pub nesting_depth: u16,
+    /// Reference the stylesheet's classes instead of using inline styles
+    pub stylesheet: Option<&'a Stylesheet>,
+    /// CSS variables to define on this element. Used for the root element.
+    pub css_variables: Option<&'a CSSVariablesMap<'a>>,
+}
+
+fn indent(f: &mut impl Write, level: u16) -> std::fmt::Result {
+    for _ in 0..level {
+        write!(f, "  ")?
+    }
+
+    Ok(())
+}
+
+fn js_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/**
+Convert a CSS property name to the name React uses in style objects.
+
+Custom properties are kept as they are. Vendor prefixes are capitalized, except
+for `-ms-`.
+ */
+fn style_object_key(property: &str) -> String {
+    if property.starts_with("--") {
+        return js_string(property);
+    }
+    let mut parts = property.split('-');
+    let mut key = String::new();
+    if property.starts_with('-') {
+        parts.next();
+        match parts.next() {
+            Some("ms") => key.push_str("ms"),
+            Some(vendor) => key.push_str(&capitalize(vendor)),
+            None => {}
+        }
+    } else if let Some(first) = parts.next() {
+        key.push_str(first);
+    }
+    for part in parts {
+        key.push_str(&capitalize(part));
+    }
+    key
+}
+
+fn open_start_tag(f: &mut impl Write, level: u16, name: &str) -> std::fmt::Result {
+    indent(f, level)?;
+    writeln!(f, "<{name}")
+}
+
+fn close_start_tag(f: &mut impl Write, level: u16) -> std::fmt::Result {
+    indent(f, level)?;
+    writeln!(f, ">")
+}
+
+fn close_self_closing_tag(f: &mut impl Write, level: u16) -> std::fmt::Result {
+    indent(f, level)?;
+    writeln!(f, "/>")
+}
+
+fn end_tag(f: &mut impl Write, level: u16, name: &str) -> std::fmt::Result {
+    indent(f, level)?;
+    writeln!(f, "</{name}>")
+}
+
+fn attribute(f: &mut impl Write, level: u16, name: &str, value: &str) -> std::fmt::Result {
+    indent(f, level + 1)?;
+    writeln!(f, "{name}=\"{}\"", encode_double_quoted_attribute(value))
+}
+
+fn text(f: &mut impl Write, level: u16, value: &str) -> std::fmt::Result {
+    indent(f, level)?;
+    writeln!(f, "{{{}}}", js_string(value))
+}
+
+fn style_attribute(
+    f: &mut impl Write,
+    level: u16,
+    naive_css: &str,
+    stylesheet: Option<&Stylesheet>,
+    class_name: Option<&str>,
+    css_variables: Option<&CSSVariablesMap>,
+) -> std::fmt::Result {
+    let mut style_css = String::new();
+    for v in css_variables.into_iter().flat_map(|vars| vars.values()) {
+        if let Some(value) = v.value.as_deref() {
+            write!(style_css, "{}: {value};", v.name)?;
+        }
+    }
+    if stylesheet.is_some() {
+        if let Some(class_name) = class_name {
+            attribute(f, level, "className", class_name)?;
+        }
+    } else {
+        style_css.push_str(naive_css);
+    }
+
+    let declarations = minified_css_declarations(&style_css).unwrap();
+    if declarations.is_empty() {
+        return Ok(());
+    }
+    indent(f, level + 1)?;
+    writeln!(f, "style={{{{")?;
+    for declaration in declarations {
+        let (property, value) = declaration.split_once(':').unwrap_or((&declaration, ""));
+        indent(f, level + 2)?;
+        writeln!(
+            f,
+            "{}: {},",
+            style_object_key(property.trim()),
+            js_string(value.trim())
+        )?;
+    }
+    indent(f, level + 1)?;
+    writeln!(f, "}}}}")
+}
+
+fn path(
+    f: &mut impl Write,
+    level: u16,
+    vector_path: &VectorPath<'_>,
+    fill: Option<&str>,
+) -> std::fmt::Result {
+    open_start_tag(f, level, "path")?;
+    attribute(f, level, "d", &vector_path.path)?;
+    if vector_path.fill_rule == FillRule::EvenOdd {
+        attribute(f, level, "fillRule", "evenodd")?;
+    }
+    if let Some(fill) = fill {
+        attribute(f, level, "fill", fill)?;
+    }
+    close_self_closing_tag(f, level)
+}
+
+fn text_run(
+    f: &mut impl Write,
+    level: u16,
+    run: &TextRun<'_>,
+    stylesheet: Option<&Stylesheet>,
+) -> std::fmt::Result {
+    let naive_css = run.naive_css_string();
+    if run.href.is_none() && naive_css.is_empty() {
+        return text(f, level, &run.text);
+    }
+    let name = if run.href.is_some() { "a" } else { "span" };
+    open_start_tag(f, level, name)?;
+    if let Some(href) = run.href.as_deref() {
+        attribute(f, level, "href", href)?;
+    }
+    style_attribute(
+        f,
+        level,
+        &naive_css,
+        stylesheet,
+        stylesheet.and_then(|s| s.class_name(&naive_css)),
+        None,
+    )?;
+    close_start_tag(f, level)?;
+    text(f, level + 1, &run.text)?;
+    end_tag(f, level, name)
+}
+
+impl<'a> JsxFormatter<'a> {
+    fn svg_title(&self, f: &mut impl Write) -> std::fmt::Result {
+        let level = self.nesting_depth + 1;
+        if let Some(title) = self.intermediate_node.accessibility.title.as_deref() {
+            open_start_tag(f, level, "title")?;
+            close_start_tag(f, level)?;
+            text(f, level + 1, title)?;
+            end_tag(f, level, "title")?;
+        }
+        Ok(())
+    }
+
+    fn common_attributes(&self, f: &mut impl Write) -> std::fmt::Result {
+        let level = self.nesting_depth;
+        if let Some(figma) = self.intermediate_node.figma.as_ref() {
+            attribute(f, level, "data-figma-name", &figma.name)?;
+            attribute(f, level, "data-figma-id", &figma.id)?;
+        }
+        if let Some(anchor) = self.intermediate_node.anchor.as_deref() {
+            attribute(f, level, "id", anchor)?;
+        }
+        if let Some(href) = self.intermediate_node.href.as_deref() {
+            attribute(f, level, "href", href)?;
+        }
+        match self.intermediate_node.element_name() {
+            "button" => attribute(f, level, "type", "button")?,
+            "input" => {
+                if let Some(placeholder) = self.intermediate_node.first_text() {
+                    attribute(f, level, "placeholder", placeholder)?;
+                }
+            }
+            _ => {}
+        }
+        let accessibility = &self.intermediate_node.accessibility;
+        if let Some(role) = accessibility.role.as_deref() {
+            attribute(f, level, "role", role)?;
+        }
+        if let Some(label) = accessibility.label.as_deref() {
+            attribute(f, level, "aria-label", label)?;
+        }
+        if accessibility.hidden {
+            attribute(f, level, "aria-hidden", "true")?;
+        }
+        style_attribute(
+            f,
+            level,
+            &self.intermediate_node.naive_css_string(),
+            self.stylesheet,
+            self.stylesheet
+                .and_then(|s| s.node_class_name(self.intermediate_node)),
+            self.css_variables,
+        )
+    }
+}
+
+impl<'a> Display for JsxFormatter<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let level = self.nesting_depth;
+        let container_type = self.intermediate_node.element_name();
+        if is_void_element(container_type) {
+            open_start_tag(f, level, container_type)?;
+            self.common_attributes(f)?;
+            return close_self_closing_tag(f, level);
+        }
+        match &self.intermediate_node.node_type {
+            IntermediateNodeType::Vector {
+                view_box,
+                fill_geometry,
+                stroke_geometry,
+                stroke,
+            } => {
+                open_start_tag(f, level, "svg")?;
+                self.common_attributes(f)?;
+                if fill_geometry.is_empty() && stroke_geometry.is_empty() {
+                    attribute(f, level, "viewBox", "0 0 1 1")?;
+                    close_start_tag(f, level)?;
+                    self.svg_title(f)?;
+                    open_start_tag(f, level + 1, "rect")?;
+                    attribute(f, level + 1, "x", "-0.2")?;
+                    attribute(f, level + 1, "y", "-0.2")?;
+                    attribute(f, level + 1, "width", "1.4")?;
+                    attribute(f, level + 1, "height", "1.4")?;
+                    close_self_closing_tag(f, level + 1)?;
+                } else {
+                    if let Some([x, y, width, height]) = view_box {
+                        attribute(f, level, "viewBox", &format!("{x} {y} {width} {height}"))?;
+                    }
+                    attribute(f, level, "overflow", "visible")?;
+                    close_start_tag(f, level)?;
+                    self.svg_title(f)?;
+                    for fill_path in fill_geometry.iter() {
+                        path(f, level + 1, fill_path, None)?;
+                    }
+                    for stroke_path in stroke_geometry.iter() {
+                        path(f, level + 1, stroke_path, stroke.as_deref())?;
+                    }
+                }
+                end_tag(f, level, "svg")?;
+            }
+            IntermediateNodeType::Text {
+                text: inner_text,
+                runs,
+                ..
+            } => {
+                open_start_tag(f, level, container_type)?;
+                self.common_attributes(f)?;
+                close_start_tag(f, level)?;
+                if runs.is_empty() {
+                    text(f, level + 1, inner_text)?;
+                } else {
+                    for run in runs.iter() {
+                        text_run(f, level + 1, run, self.stylesheet)?;
+                    }
+                }
+                end_tag(f, level, container_type)?;
+            }
+            IntermediateNodeType::Frame { children } => {
+                open_start_tag(f, level, container_type)?;
+                self.common_attributes(f)?;
+                close_start_tag(f, level)?;
+                for child in children.iter() {
+                    write!(
+                        f,
+                        "{}",
+                        JsxFormatter {
+                            intermediate_node: child,
+                            nesting_depth: level + 1,
+                            stylesheet: self.stylesheet,
+                            css_variables: None,
+                        }
+                    )?;
+                }
+                end_tag(f, level, container_type)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{style_object_key, JsxFormatter, Stylesheet};
+    use crate::{figma_node_to_intermediate_node, ConversionOptions};
+
+    fn jsx(node: serde_json::Value, shared_stylesheet: bool) -> String {
+        let node: figma_schema::Node = serde_json::from_value(node).unwrap();
+        let intermediate_node = figma_node_to_intermediate_node(
+            &node,
+            &mut Default::default(),
+            &ConversionOptions::default(),
+        );
+        let stylesheet =
+            shared_stylesheet.then(|| Stylesheet::from_intermediate_node(&intermediate_node));
+        JsxFormatter {
+            intermediate_node: &intermediate_node,
+            nesting_depth: 0,
+            stylesheet: stylesheet.as_ref(),
+            css_variables: None,
+        }
+        .to_string()
+    }
+
+    #[test]
+    fn style_object_keys() {
+        assert_eq!(style_object_key("color"), "color");
+        assert_eq!(
+            style_object_key("border-top-left-radius"),
+            "borderTopLeftRadius"
+        );
+        assert_eq!(style_object_key("-webkit-line-clamp"), "WebkitLineClamp");
+        assert_eq!(style_object_key("-ms-flex"), "msFlex");
+        assert_eq!(style_object_key("--Other-Green"), r#""--Other-Green""#);
+    }
+
+    #[test]
+    fn text_is_a_javascript_string() {
+        let jsx = jsx(
+            serde_json::json!({
+                "id": "1:1",
+                "name": "Price",
+                "type": "TEXT",
+                "characters": "{total} < 5 & \"more\"",
+                "style": {
+                    "fontFamily": "Inter",
+                    "fontWeight": 400.0,
+                    "fontSize": 16.0,
+                    "lineHeightPx": 20.0
+                }
+            }),
+            false,
+        );
+        assert!(
+            jsx.contains("\n  {\"{total} < 5 & \\\"more\\\"\"}\n"),
+            "{jsx}"
+        );
+    }
+
+    #[test]
+    fn styles_are_objects_or_class_names() {
+        let card = serde_json::json!({
+            "id": "1:1",
+            "name": "Card",
+            "type": "FRAME",
+            "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 100.0, "height": 50.0 },
+            "fills": [{ "type": "SOLID", "color": { "r": 1.0, "g": 0.0, "b": 0.0, "a": 1.0 } }],
+            "cornerRadius": 4.0
+        });
+
+        let inline = jsx(card.clone(), false);
+        assert!(inline.contains("  style={{\n"), "{inline}");
+        assert!(inline.contains("    background: \"red\",\n"), "{inline}");
+        assert!(inline.contains("    borderRadius: \"4px\",\n"), "{inline}");
+        assert!(!inline.contains("className"), "{inline}");
+
+        let shared = jsx(card, true);
+        assert!(shared.contains("  className=\"card\"\n"), "{shared}");
+        assert!(!shared.contains("style="), "{shared}");
+    }
+
+    #[test]
+    fn svg_attributes_use_react_names() {
+        let jsx = jsx(
+            serde_json::json!({
+                "id": "1:2",
+                "name": "Tick",
+                "type": "VECTOR",
+                "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 20.0, "height": 10.0 },
+                "fills": [{ "type": "SOLID", "color": { "r": 0.0, "g": 0.0, "b": 1.0, "a": 1.0 } }],
+                "fillGeometry": [{ "path": "M0 0L20 10L0 10Z", "windingRule": "EVENODD" }]
+            }),
+            false,
+        );
+        assert!(jsx.contains("  viewBox=\"0 0 20 10\"\n"), "{jsx}");
+        assert!(
+            jsx.contains("<path\n    d=\"M0 0L20 10L0 10Z\"\n    fillRule=\"evenodd\"\n  />"),
+            "{jsx}"
+        );
+        assert!(!jsx.contains("fill-rule"), "{jsx}");
+    }
+}
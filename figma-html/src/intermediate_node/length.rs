@@ -7,6 +7,10 @@ use serde::{Deserialize, Serialize};
 pub enum LengthUnit {
     Px,
     Percentage,
+    /// Relative to the root element's font size
+    Rem,
+    /// Relative to the element's font size
+    Em,
 }
 
 impl fmt::Display for LengthUnit {
@@ -14,6 +18,8 @@ impl fmt::Display for LengthUnit {
         match self {
             LengthUnit::Px => write!(f, "px"),
             LengthUnit::Percentage => write!(f, "%"),
+            LengthUnit::Rem => write!(f, "rem"),
+            LengthUnit::Em => write!(f, "em"),
         }
     }
 }
@@ -39,7 +45,7 @@ impl<'a> Length<'a> {
     pub fn new_from_option_pixels(pixels: Option<f64>) -> Self {
         match pixels {
             None => Self::Zero,
-            Some(p) if p == 0.0 => Self::Zero,
+            Some(0.0) => Self::Zero,
             Some(p) => Self::Value {
                 unit: LengthUnit::Px,
                 value: p,
@@ -59,7 +65,7 @@ impl<'a> Length<'a> {
         match self {
             Self::Zero => Some(false),
             Self::Value {
-                unit: LengthUnit::Px,
+                unit: LengthUnit::Px | LengthUnit::Rem | LengthUnit::Em,
                 ..
             } => Some(false),
             Self::Value {
@@ -84,6 +90,24 @@ impl<'a> Length<'a> {
         }
     }
 
+    /// The length with its pixels converted to another unit, given how many
+    /// pixels are in one of that unit
+    pub fn px_to(&self, unit: LengthUnit, unit_px: f64) -> Self {
+        match self {
+            Self::Value {
+                unit: LengthUnit::Px,
+                value,
+            } => Self::Value {
+                unit,
+                value: value / unit_px,
+            },
+            Self::Addition { terms } => Self::Addition {
+                terms: terms.iter().map(|t| t.px_to(unit, unit_px)).collect(),
+            },
+            _ => self.clone(),
+        }
+    }
+
     fn requires_calc(&self) -> bool {
         match self {
             Self::Zero | Self::Value { .. } => false,
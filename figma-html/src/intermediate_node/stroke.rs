@@ -0,0 +1,245 @@
+use figma_schema::{Node as FigmaNode, StrokeAlign, StrokeWeights};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum StrokeStyle {
+    Solid,
+    Dashed,
+    Dotted,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Stroke {
+    /// Top, right, bottom and left weights in pixels
+    pub weights: [f64; 4],
+    pub style: StrokeStyle,
+    pub offset: StrokeAlign,
+    /// The colours of the stroke paints, the top one first
+    pub colors: Vec<String>,
+}
+
+/**
+The CSS a stroke is drawn with.
+
+Outlines and borders have one colour, so dashed strokes with more than one
+paint only draw the top paint.
+ */
+#[derive(Debug, PartialEq)]
+enum Drawing {
+    /// Uniform strokes of one colour, or dashed uniform strokes
+    Outline,
+    /// Solid strokes. These honour the alignment on every side and don't affect
+    /// layout.
+    BoxShadow,
+    /// Dashed strokes that aren't uniform. Borders are always inside the
+    /// node, whatever the stroke's alignment, and take up space within it.
+    Border,
+}
+
+impl Stroke {
+    pub fn from_figma_node(node: &FigmaNode) -> Option<Self> {
+        let colors = node
+            .strokes()
+            .iter()
+            .rev()
+            .filter(|p| p.visible() && p.opacity() != 0.0)
+            .flat_map(|stroke| stroke.color_with_opacity())
+            .flat_map(|color| color.to_option_rgb_string())
+            .collect::<Vec<_>>();
+        if colors.is_empty() {
+            return None;
+        }
+        let weights = match (&node.individual_stroke_weights, node.stroke_weight) {
+            (
+                Some(StrokeWeights {
+                    top,
+                    right,
+                    bottom,
+                    left,
+                }),
+                _,
+            ) => [*top, *right, *bottom, *left],
+            (None, Some(w)) => [w, w, w, w],
+            (None, None) => return None,
+        };
+        let widest = weights.iter().copied().fold(0.0, f64::max);
+        let style = match node.stroke_dashes.as_deref() {
+            // Dashes no longer than the stroke is wide look like dots
+            Some([dash, ..]) if *dash <= widest => StrokeStyle::Dotted,
+            Some([_, ..]) => StrokeStyle::Dashed,
+            _ => StrokeStyle::Solid,
+        };
+        Some(Self {
+            weights,
+            style,
+            offset: node.stroke_align?,
+            colors,
+        })
+    }
+
+    fn is_uniform(&self) -> bool {
+        self.weights.iter().all(|w| *w == self.weights[0])
+    }
+
+    fn drawing(&self) -> Option<Drawing> {
+        if self.weights.iter().all(|w| *w == 0.0) {
+            None
+        } else if self.is_uniform() && self.colors.len() == 1 {
+            Some(Drawing::Outline)
+        } else if self.style == StrokeStyle::Solid {
+            Some(Drawing::BoxShadow)
+        } else if self.is_uniform() {
+            Some(Drawing::Outline)
+        } else {
+            Some(Drawing::Border)
+        }
+    }
+
+    fn style_value(&self) -> &'static str {
+        match self.style {
+            StrokeStyle::Solid => "solid",
+            StrokeStyle::Dashed => "dashed",
+            StrokeStyle::Dotted => "dotted",
+        }
+    }
+
+    pub fn outline(&self) -> Option<String> {
+        if self.drawing()? != Drawing::Outline {
+            return None;
+        }
+        Some(format!(
+            "{}px {} {}",
+            self.weights[0],
+            self.style_value(),
+            self.colors[0]
+        ))
+    }
+
+    pub fn outline_offset(&self) -> Option<String> {
+        if self.drawing()? != Drawing::Outline {
+            return None;
+        }
+        let width = self.weights[0];
+        match self.offset {
+            StrokeAlign::Inside => Some(format!("-{width}px")),
+            StrokeAlign::Outside => None,
+            StrokeAlign::Center => Some(format!("-{}px", width / 2.0)),
+        }
+    }
+
+    /// The stroke as `box-shadow` layers, the top paint first
+    pub fn box_shadow(&self) -> Option<String> {
+        if self.drawing()? != Drawing::BoxShadow {
+            return None;
+        }
+        let (inside, outside) = match self.offset {
+            StrokeAlign::Inside => (1.0, 0.0),
+            StrokeAlign::Outside => (0.0, 1.0),
+            StrokeAlign::Center => (0.5, 0.5),
+        };
+        let mut layers = Vec::new();
+        for color in self.colors.iter() {
+            for (proportion, inset) in [(inside, "inset "), (outside, "")] {
+                if proportion == 0.0 {
+                    continue;
+                }
+                if self.is_uniform() {
+                    let spread = self.weights[0] * proportion;
+                    layers.push(format!("{inset}0 0 0 {spread}px {color}"));
+                    continue;
+                }
+                // Offsetting the shadow shows it along one side
+                let [top, right, bottom, left] = self.weights.map(|w| w * proportion);
+                let direction = if inset.is_empty() { -1.0 } else { 1.0 };
+                for (width, x, y) in [
+                    (top, 0.0, top),
+                    (right, -right, 0.0),
+                    (bottom, 0.0, -bottom),
+                    (left, left, 0.0),
+                ] {
+                    if width != 0.0 {
+                        let (x, y) = (x * direction + 0.0, y * direction + 0.0);
+                        layers.push(format!("{inset}{x}px {y}px {color}"));
+                    }
+                }
+            }
+        }
+        Some(layers.join(", "))
+    }
+
+    /// `border-width`, `border-style` and `border-color`
+    pub fn border(&self) -> Option<[String; 3]> {
+        if self.drawing()? != Drawing::Border {
+            return None;
+        }
+        let [top, right, bottom, left] = self.weights;
+        Some([
+            format!("{top}px {right}px {bottom}px {left}px"),
+            self.style_value().to_string(),
+            self.colors[0].clone(),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use figma_schema::StrokeAlign;
+
+    use super::{Stroke, StrokeStyle};
+
+    #[test]
+    fn bottom_stroke_is_a_shadow_on_one_side() {
+        let stroke = Stroke {
+            weights: [0.0, 0.0, 2.0, 0.0],
+            style: StrokeStyle::Solid,
+            offset: StrokeAlign::Center,
+            colors: vec!["red".to_string()],
+        };
+        assert_eq!(stroke.outline(), None);
+        assert_eq!(
+            stroke.box_shadow().as_deref(),
+            Some("inset 0px -1px red, 0px 1px red")
+        );
+    }
+
+    #[test]
+    fn dashed_sides_are_borders() {
+        let stroke = Stroke {
+            weights: [1.0, 0.0, 1.0, 0.0],
+            style: StrokeStyle::Dashed,
+            offset: StrokeAlign::Inside,
+            colors: vec!["red".to_string()],
+        };
+        assert_eq!(stroke.box_shadow(), None);
+        assert_eq!(
+            stroke.border(),
+            Some([
+                "1px 0px 1px 0px".to_string(),
+                "dashed".to_string(),
+                "red".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn dashed_strokes_draw_the_top_paint() {
+        let mut stroke = Stroke {
+            weights: [2.0; 4],
+            style: StrokeStyle::Dashed,
+            offset: StrokeAlign::Outside,
+            colors: vec!["red".to_string(), "blue".to_string()],
+        };
+        assert_eq!(stroke.outline().as_deref(), Some("2px dashed red"));
+        assert_eq!(stroke.box_shadow(), None);
+
+        stroke.weights = [2.0, 0.0, 0.0, 0.0];
+        assert_eq!(
+            stroke.border(),
+            Some([
+                "2px 0px 0px 0px".to_string(),
+                "dashed".to_string(),
+                "red".to_string()
+            ])
+        );
+    }
+}
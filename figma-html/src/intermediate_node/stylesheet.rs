@@ -0,0 +1,908 @@
+use std::{collections::HashMap, fmt::Write};
+
+use indexmap::IndexMap;
+use itertools::Itertools;
+
+use super::{minified_css_declarations, IntermediateNode, IntermediateNodeType};
+use crate::{Breakpoint, VariantState};
+
+/**
+CSS rules shared between elements, for output with classes rather than inline
+styles.
+
+Elements with the same CSS share a class, so repeated component instances
+share one rule. Class names come from the Figma name of the first element
+using the rule, with a numeric suffix if that name is already taken.
+ */
+#[derive(Debug, Default)]
+pub struct Stylesheet {
+    /// Class names keyed by their minified CSS declarations
+    classes: IndexMap<Vec<String>, String>,
+    /// Class names of specific elements, keyed by Figma id. These take
+    /// precedence over the classes shared by CSS.
+    element_classes: HashMap<String, String>,
+    /// Declarations keyed by selector
+    rules: IndexMap<String, Vec<String>>,
+    /// Declarations keyed by selector, within `min-width` media queries. The
+    /// narrowest first, so that wider viewports' rules win.
+    media_rules: Vec<(f64, IndexMap<String, Vec<String>>)>,
+}
+
+/**
+A component from a component set, converted to an intermediate node.
+
+The properties are parsed from the component's `Key=Value, ...` name.
+ */
+#[derive(Debug)]
+pub struct Variant<'a> {
+    pub properties: Vec<(&'a str, &'a str)>,
+    pub node: IntermediateNode<'a>,
+}
+
+impl Variant<'_> {
+    /// Whether the variant is an interaction state, such as hover, rather
+    /// than a variant to be rendered
+    pub fn is_state(&self, states: &[VariantState]) -> bool {
+        self.properties
+            .iter()
+            .any(|property| states.iter().any(|state| state.is_active(*property)))
+    }
+
+    /// The width of the narrowest viewport the variant is for, or 0 if it
+    /// isn't for a wider viewport
+    pub fn min_width(&self, breakpoints: &[Breakpoint]) -> f64 {
+        properties_min_width(&self.properties, breakpoints)
+    }
+}
+
+/// The properties of a variant that decide a value, or all of them if none do
+fn deciding_properties<'a>(
+    variant: &Variant<'a>,
+    deciding_keys: Option<&[&str]>,
+) -> Vec<(&'a str, &'a str)> {
+    match deciding_keys {
+        Some(deciding_keys) => variant
+            .properties
+            .iter()
+            .filter(|(k, _)| deciding_keys.contains(k))
+            .copied()
+            .collect(),
+        None => variant.properties.clone(),
+    }
+}
+
+fn without_breakpoints<'a>(
+    properties: &[(&'a str, &'a str)],
+    breakpoints: &[Breakpoint],
+) -> Vec<(&'a str, &'a str)> {
+    properties
+        .iter()
+        .filter(|(k, _)| !breakpoints.iter().any(|b| b.property == *k))
+        .copied()
+        .collect()
+}
+
+/**
+The value at the next narrower viewport than the deciding properties', of the
+variants with the same properties otherwise. This is what applies at the
+viewport without a rule of its own.
+ */
+fn narrower_value<'v>(
+    variants: &[Variant<'_>],
+    values: &[Option<&'v str>],
+    properties: &[(&str, &str)],
+    deciding_keys: Option<&[&str]>,
+    breakpoints: &[Breakpoint],
+) -> Option<&'v str> {
+    let min_width = properties_min_width(properties, breakpoints);
+    let others = without_breakpoints(properties, breakpoints);
+    variants
+        .iter()
+        .zip(values.iter())
+        .filter_map(|(variant, value)| {
+            let properties = deciding_properties(variant, deciding_keys);
+            let width = properties_min_width(&properties, breakpoints);
+            if width < min_width && without_breakpoints(&properties, breakpoints) == others {
+                Some((width, (*value)?))
+            } else {
+                None
+            }
+        })
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, value)| value)
+}
+
+fn properties_min_width(properties: &[(&str, &str)], breakpoints: &[Breakpoint]) -> f64 {
+    properties
+        .iter()
+        .flat_map(|&(key, value)| {
+            breakpoints
+                .iter()
+                .filter(move |b| b.property == key && b.value == value)
+        })
+        .map(|b| b.min_width)
+        .fold(0.0, f64::max)
+}
+
+impl VariantState {
+    fn is_active(&self, (key, value): (&str, &str)) -> bool {
+        self.property == key && self.value == value
+    }
+}
+
+/// Make a Figma name usable as a CSS class name
+fn class_name_base(name: &str) -> String {
+    let mut class_name = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            class_name.push(c.to_ascii_lowercase());
+        } else if !class_name.is_empty() && !class_name.ends_with('-') {
+            class_name.push('-');
+        }
+    }
+    while class_name.ends_with('-') {
+        class_name.pop();
+    }
+    if class_name.is_empty() || class_name.starts_with(|c: char| c.is_ascii_digit()) {
+        class_name.insert_str(0, "node-");
+    }
+    class_name
+}
+
+/// The data attribute a variant property is set with
+pub fn variant_data_attribute(key: &str) -> String {
+    format!("data-{}", class_name_base(key))
+}
+
+fn css_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn figma_name<'a>(node: &'a IntermediateNode<'_>) -> &'a str {
+    node.figma.as_ref().map(|f| f.name.as_ref()).unwrap_or("")
+}
+
+/// Elements keyed by the path of Figma names to them
+type ElementPaths<'a, 'b> = IndexMap<String, &'b IntermediateNode<'a>>;
+
+/// Map each element to a path of names, so that the same element can be found
+/// in each variant
+fn collect_paths<'a, 'b>(
+    node: &'b IntermediateNode<'a>,
+    path: String,
+    paths: &mut ElementPaths<'a, 'b>,
+) {
+    if let IntermediateNodeType::Frame { children } = &node.node_type {
+        let mut name_counts = HashMap::new();
+        for child in children.iter() {
+            let count = name_counts.entry(figma_name(child)).or_insert(0);
+            *count += 1;
+            collect_paths(
+                child,
+                format!("{path}/{}#{count}", figma_name(child)),
+                paths,
+            );
+        }
+    }
+    paths.insert(path, node);
+}
+
+/**
+Each variant's elements by path, and the elements that don't match an element
+of the first variant.
+
+Variants are simplified separately, so an element can be a different kind of
+element in different variants. For example where a frame was combined with its
+only text child in some variants but not others. Such elements are left out
+rather than matched, apart from the variants' roots.
+ */
+fn variant_paths<'a, 'b>(
+    variants: &'b [Variant<'a>],
+) -> (Vec<ElementPaths<'a, 'b>>, Vec<&'b IntermediateNode<'a>>) {
+    let mut variant_paths = variants
+        .iter()
+        .map(|variant| {
+            let mut paths = IndexMap::new();
+            collect_paths(&variant.node, String::new(), &mut paths);
+            paths
+        })
+        .collect::<Vec<_>>();
+    let mut unmatched = vec![];
+    if let Some((base_paths, other_paths)) = variant_paths.split_first_mut() {
+        for paths in other_paths.iter_mut() {
+            paths.retain(|path, node| {
+                // The roots are the variants themselves, whatever their kind
+                let matched = path.is_empty()
+                    || base_paths.get(path).is_some_and(|base_node| {
+                        std::mem::discriminant(&base_node.node_type)
+                            == std::mem::discriminant(&node.node_type)
+                    });
+                if !matched {
+                    unmatched.push(*node);
+                }
+                matched
+            });
+        }
+    }
+    (variant_paths, unmatched)
+}
+
+/// Figma ids of elements in variants that have no matching element in the
+/// first variant. These elements have classes of their own, which don't switch
+/// on the variant properties.
+pub fn unmatched_variant_elements<'a>(variants: &'a [Variant<'a>]) -> Vec<&'a str> {
+    variant_paths(variants)
+        .1
+        .into_iter()
+        .filter_map(|node| node.figma.as_ref().map(|f| f.id.as_ref()))
+        .collect()
+}
+
+fn declaration_map(naive_css: &str) -> IndexMap<String, String> {
+    minified_css_declarations(naive_css)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|declaration| match declaration.split_once(':') {
+            Some((property, value)) => (property.trim().to_string(), value.trim().to_string()),
+            None => (declaration, String::new()),
+        })
+        .collect()
+}
+
+impl Stylesheet {
+    pub fn from_intermediate_node(node: &IntermediateNode<'_>) -> Self {
+        let mut stylesheet = Self::default();
+        stylesheet.add_node(node);
+        stylesheet
+    }
+
+    /**
+    Style the first variant's elements with classes that switch on the data
+    attributes of the variant properties. The root element's class is from the
+    component set's name.
+
+    Elements are matched between variants by the path of Figma names to them,
+    if they are the same kind of element. Declarations shared by all variants
+    go in the element's class, which the matching elements of the other
+    variants also have. Declarations that differ are set by the fewest
+    properties that decide them. Elements missing from a variant are hidden,
+    and those only in other variants have classes of their own.
+
+    State properties, such as hover, select with their pseudo-class instead of
+    a data attribute. The first variant should not be in any of the states.
+
+    Breakpoint properties, such as `Viewport`, select with a `min-width` media
+    query instead. Rules in a media query only have what differs from the next
+    narrower viewport. The first variant should be for the narrowest viewport.
+     */
+    pub fn from_variants(
+        name: &str,
+        variants: &[Variant<'_>],
+        states: &[VariantState],
+        breakpoints: &[Breakpoint],
+    ) -> Self {
+        let mut stylesheet = Self::default();
+        let Some(base) = variants.first() else {
+            return stylesheet;
+        };
+
+        let (variant_paths, unmatched) = variant_paths(variants);
+
+        let root_class = stylesheet.unused_class_name(&class_name_base(name));
+        // Children come before their parents, so the root's class is taken
+        // before theirs are named
+        if let Some(figma) = base.node.figma.as_ref() {
+            stylesheet
+                .element_classes
+                .insert(figma.id.to_string(), root_class.clone());
+        }
+        let root_selector = |properties: &[(&str, &str)]| {
+            let mut selector = format!(".{root_class}");
+            let mut pseudo_classes = String::new();
+            for &(key, value) in properties {
+                if breakpoints.iter().any(|b| b.property == key) {
+                    // Selected by the media query
+                } else if let Some(state) = states.iter().find(|s| s.property == key) {
+                    // Not being in a state needs no selector, as the state's
+                    // rule is more specific
+                    if state.is_active((key, value)) {
+                        pseudo_classes.push_str(&state.selector);
+                    }
+                } else {
+                    write!(
+                        selector,
+                        "[{}={}]",
+                        variant_data_attribute(key),
+                        css_string(value)
+                    )
+                    .unwrap();
+                }
+            }
+            selector + &pseudo_classes
+        };
+
+        for (path, base_node) in variant_paths[0].iter() {
+            let class_name = if path.is_empty() {
+                root_class.clone()
+            } else {
+                stylesheet.unused_class_name(&class_name_base(figma_name(base_node)))
+            };
+            for node in variant_paths.iter().filter_map(|paths| paths.get(path)) {
+                if let Some(figma) = node.figma.as_ref() {
+                    stylesheet
+                        .element_classes
+                        .insert(figma.id.to_string(), class_name.clone());
+                }
+            }
+            let selector = |properties: &[(&str, &str)]| {
+                if path.is_empty() {
+                    root_selector(properties)
+                } else {
+                    format!("{} .{class_name}", root_selector(properties))
+                }
+            };
+
+            // Declarations of each variant, or None if the element is missing
+            let declarations = variant_paths
+                .iter()
+                .map(|paths| {
+                    paths
+                        .get(path)
+                        .map(|n| declaration_map(&n.naive_css_string()))
+                })
+                .collect::<Vec<_>>();
+            let base_declarations = declarations[0].clone().unwrap_or_default();
+            stylesheet.rules.insert(
+                format!(".{class_name}"),
+                base_declarations
+                    .iter()
+                    .map(|(p, v)| format!("{p}: {v}"))
+                    .collect(),
+            );
+
+            let mut properties = declarations
+                .iter()
+                .flatten()
+                .flat_map(|d| d.keys().map(String::as_str))
+                .collect::<Vec<_>>();
+            if declarations.iter().any(Option::is_none) {
+                properties.push("display");
+            }
+            properties.sort();
+            properties.dedup();
+
+            for property in properties {
+                // The value in each variant, or None if it doesn't matter
+                let values = declarations
+                    .iter()
+                    .map(|d| match d {
+                        Some(d) => Some(d.get(property).map_or("unset", String::as_str)),
+                        None if property == "display" => Some("none"),
+                        None => None,
+                    })
+                    .collect::<Vec<_>>();
+                let base_value = base_declarations
+                    .get(property)
+                    .map_or("unset", String::as_str);
+                if values.iter().flatten().all(|v| *v == base_value) {
+                    continue;
+                }
+
+                // The fewest properties that decide the value
+                let keys = base
+                    .properties
+                    .iter()
+                    .map(|(key, _)| *key)
+                    .collect::<Vec<_>>();
+                let deciding_keys = (1..=keys.len())
+                    .flat_map(|size| keys.iter().copied().combinations(size))
+                    .find(|deciding_keys| {
+                        let mut by_properties = HashMap::new();
+                        variants.iter().zip(values.iter()).all(|(variant, value)| {
+                            let Some(value) = value else {
+                                return true;
+                            };
+                            let properties = variant
+                                .properties
+                                .iter()
+                                .filter(|(k, _)| deciding_keys.contains(k))
+                                .collect::<Vec<_>>();
+                            *by_properties.entry(properties).or_insert(*value) == *value
+                        })
+                    });
+
+                for (variant, value) in variants.iter().zip(values.iter()) {
+                    let Some(value) = *value else {
+                        continue;
+                    };
+                    let properties = deciding_properties(variant, deciding_keys.as_deref());
+                    let min_width = properties_min_width(&properties, breakpoints);
+                    let previous_value = if min_width > 0.0 {
+                        narrower_value(
+                            variants,
+                            &values,
+                            &properties,
+                            deciding_keys.as_deref(),
+                            breakpoints,
+                        )
+                        .unwrap_or(base_value)
+                    } else {
+                        base_value
+                    };
+                    if value == previous_value {
+                        continue;
+                    }
+                    let declaration = format!("{property}: {value}");
+                    let rule = stylesheet.rule_mut(selector(&properties), min_width);
+                    if !rule.contains(&declaration) {
+                        rule.push(declaration);
+                    }
+                }
+            }
+
+            for node in variant_paths.iter().filter_map(|paths| paths.get(path)) {
+                if let IntermediateNodeType::Text { runs, .. } = &node.node_type {
+                    let base = format!("{class_name}-span");
+                    for run in runs.iter() {
+                        stylesheet.add_rule(&run.naive_css_string(), &base);
+                    }
+                }
+            }
+        }
+
+        for node in unmatched {
+            stylesheet.add_element(node);
+        }
+
+        // Group the rules by how many properties they depend on. Specificity
+        // means the order doesn't matter, but it is easier to read.
+        let by_specificity = |rules: &mut IndexMap<String, Vec<String>>| {
+            rules.sort_by_cached_key(|selector, _| selector.matches('[').count())
+        };
+        by_specificity(&mut stylesheet.rules);
+        for (_, rules) in stylesheet.media_rules.iter_mut() {
+            by_specificity(rules);
+        }
+
+        stylesheet
+    }
+
+    /// The declarations of a rule, within a media query if the minimum
+    /// viewport width isn't 0
+    fn rule_mut(&mut self, selector: String, min_width: f64) -> &mut Vec<String> {
+        let rules = if min_width > 0.0 {
+            let index = match self
+                .media_rules
+                .iter()
+                .position(|(width, _)| *width >= min_width)
+            {
+                Some(index) if self.media_rules[index].0 == min_width => index,
+                Some(index) => {
+                    self.media_rules.insert(index, (min_width, IndexMap::new()));
+                    index
+                }
+                None => {
+                    self.media_rules.push((min_width, IndexMap::new()));
+                    self.media_rules.len() - 1
+                }
+            };
+            &mut self.media_rules[index].1
+        } else {
+            &mut self.rules
+        };
+        rules.entry(selector).or_default()
+    }
+
+    fn add_node(&mut self, node: &IntermediateNode<'_>) {
+        self.add_element(node);
+        if let IntermediateNodeType::Frame { children } = &node.node_type {
+            for child in children.iter() {
+                self.add_node(child);
+            }
+        }
+    }
+
+    /// Add the rules of an element and its text runs, but not its children
+    fn add_element(&mut self, node: &IntermediateNode<'_>) {
+        let base = class_name_base(figma_name(node));
+        self.add_rule(&node.naive_css_string(), &base);
+        if let IntermediateNodeType::Text { runs, .. } = &node.node_type {
+            let base = format!("{base}-span");
+            for run in runs.iter() {
+                self.add_rule(&run.naive_css_string(), &base);
+            }
+        }
+    }
+
+    fn unused_class_name(&self, base: &str) -> String {
+        let mut class_name = base.to_string();
+        let mut suffix = 1;
+        while self
+            .classes
+            .values()
+            .chain(self.element_classes.values())
+            .any(|c| c == &class_name)
+        {
+            suffix += 1;
+            class_name = format!("{base}-{suffix}");
+        }
+        class_name
+    }
+
+    fn add_rule(&mut self, naive_css: &str, base: &str) {
+        let Some(key) = Self::key(naive_css) else {
+            return;
+        };
+        if self.classes.contains_key(&key) {
+            return;
+        }
+        let class_name = self.unused_class_name(base);
+        self.rules.insert(format!(".{class_name}"), key.clone());
+        self.classes.insert(key, class_name);
+    }
+
+    fn key(naive_css: &str) -> Option<Vec<String>> {
+        let declarations = minified_css_declarations(naive_css).ok()?;
+        if declarations.is_empty() {
+            None
+        } else {
+            Some(declarations)
+        }
+    }
+
+    /// The class for an element's CSS, if it has any
+    pub fn class_name(&self, naive_css: &str) -> Option<&str> {
+        self.classes.get(&Self::key(naive_css)?).map(String::as_str)
+    }
+
+    /// The class for an element
+    pub fn node_class_name(&self, node: &IntermediateNode<'_>) -> Option<&str> {
+        node.figma
+            .as_ref()
+            .and_then(|figma| self.element_classes.get(figma.id.as_ref()))
+            .map(String::as_str)
+            .or_else(|| self.class_name(&node.naive_css_string()))
+    }
+
+    /// The rules, formatted to be nested `level` deep
+    pub fn css_rules(&self, level: u16) -> String {
+        let mut output = rules_css(&self.rules, level);
+        let indent = "  ".repeat(level.into());
+        for (min_width, rules) in self.media_rules.iter() {
+            writeln!(output, "{indent}@media (min-width: {min_width}px) {{").unwrap();
+            output.push_str(&rules_css(rules, level + 1));
+            writeln!(output, "{indent}}}").unwrap();
+        }
+        output
+    }
+}
+
+fn rules_css(rules: &IndexMap<String, Vec<String>>, level: u16) -> String {
+    let mut output = String::new();
+    let indent = "  ".repeat(level.into());
+    for (selector, declarations) in rules.iter() {
+        if declarations.is_empty() {
+            continue;
+        }
+        writeln!(output, "{indent}{selector} {{").unwrap();
+        for declaration in declarations {
+            writeln!(output, "{indent}  {declaration};").unwrap();
+        }
+        writeln!(output, "{indent}}}").unwrap();
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{unmatched_variant_elements, IntermediateNodeType, Stylesheet, Variant};
+    use crate::{figma_node_to_intermediate_node, Breakpoint, ConversionOptions, VariantState};
+    use figma_schema::Node;
+
+    fn frame(id: &str, name: &str, width: f64, children: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "name": name,
+            "type": "FRAME",
+            "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": width, "height": 10.0 },
+            "children": children
+        })
+    }
+
+    fn text(id: &str, name: &str, font_size: f64) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "name": name,
+            "type": "TEXT",
+            "characters": name,
+            "style": {
+                "fontFamily": "Inter",
+                "fontWeight": 400.0,
+                "fontSize": font_size,
+                "lineHeightPx": 20.0
+            }
+        })
+    }
+
+    fn node(value: serde_json::Value) -> Node {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn variant<'a>(properties: &[(&'a str, &'a str)], node: &'a Node) -> Variant<'a> {
+        Variant {
+            properties: properties.to_vec(),
+            node: figma_node_to_intermediate_node(
+                node,
+                &mut Default::default(),
+                &ConversionOptions::default(),
+            ),
+        }
+    }
+
+    #[test]
+    fn class_names_are_unique() {
+        let card = node(frame(
+            "1:1",
+            "Card",
+            100.0,
+            serde_json::json!([
+                text("1:2", "Title", 16.0),
+                text("1:3", "Title", 24.0),
+                text("1:4", "Title", 16.0),
+                text("1:5", "2 Up", 12.0),
+            ]),
+        ));
+        let css = Stylesheet::from_intermediate_node(&figma_node_to_intermediate_node(
+            &card,
+            &mut Default::default(),
+            &ConversionOptions::default(),
+        ))
+        .css_rules(0);
+        let selectors = css
+            .lines()
+            .filter(|line| line.ends_with('{'))
+            .collect::<Vec<_>>();
+        // The third title has the same CSS as the first, so shares its class
+        assert_eq!(
+            selectors,
+            [".card {", ".title {", ".title-2 {", ".node-2-up {"]
+        );
+
+        // A variant element's class doesn't reuse the component set's
+        let label = node(frame(
+            "2:1",
+            "Label",
+            100.0,
+            serde_json::json!([text("2:2", "Label", 16.0)]),
+        ));
+        let variants = [variant(&[("Size", "Small")], &label)];
+        let stylesheet = Stylesheet::from_variants("Label", &variants, &[], &[]);
+        let IntermediateNodeType::Frame { children } = &variants[0].node.node_type else {
+            panic!();
+        };
+        assert_eq!(stylesheet.node_class_name(&variants[0].node), Some("label"));
+        assert_eq!(stylesheet.node_class_name(&children[0]), Some("label-2"));
+    }
+
+    #[test]
+    fn variant_values_are_decided_by_the_fewest_properties() {
+        let nodes = [
+            ("Small", "Dark", 16.0),
+            ("Large", "Dark", 24.0),
+            ("Small", "Light", 16.0),
+            ("Large", "Light", 24.0),
+        ]
+        .map(|(_, tone, font_size)| {
+            let children = if tone == "Dark" {
+                serde_json::json!([text("1:2", "Label", font_size), text("1:3", "Icon", 8.0)])
+            } else {
+                serde_json::json!([text("1:2", "Label", font_size)])
+            };
+            node(frame("1:1", "Button", 100.0, children))
+        });
+        let variants = [
+            variant(&[("Size", "Small"), ("Tone", "Dark")], &nodes[0]),
+            variant(&[("Size", "Large"), ("Tone", "Dark")], &nodes[1]),
+            variant(&[("Size", "Small"), ("Tone", "Light")], &nodes[2]),
+            variant(&[("Size", "Large"), ("Tone", "Light")], &nodes[3]),
+        ];
+        let css = Stylesheet::from_variants("Button", &variants, &[], &[]).css_rules(0);
+        assert!(
+            css.contains(
+                ".button[data-size=\"Large\"] .label {\n  font: 400 24px / 20px Inter;\n}"
+            ),
+            "{css}"
+        );
+        // Elements missing from a variant are hidden
+        assert!(
+            css.contains(".button[data-tone=\"Light\"] .icon {\n  display: none;\n}"),
+            "{css}"
+        );
+        assert_eq!(css.matches("[data-").count(), 2, "{css}");
+    }
+
+    #[test]
+    fn elements_of_different_kinds_are_not_matched() {
+        // The second variant's wrapper was combined with its only child
+        let small = node(frame(
+            "1:1",
+            "Button",
+            100.0,
+            serde_json::json!([frame(
+                "1:2",
+                "Wrapper",
+                80.0,
+                serde_json::json!([text("1:3", "Label", 16.0), text("1:4", "Icon", 8.0)])
+            )]),
+        ));
+        let large = node(frame(
+            "2:1",
+            "Button",
+            100.0,
+            serde_json::json!([text("2:2", "Wrapper", 24.0)]),
+        ));
+        let variants = [
+            variant(&[("Size", "Small")], &small),
+            variant(&[("Size", "Large")], &large),
+        ];
+        assert_eq!(unmatched_variant_elements(&variants), ["2:2"]);
+        let stylesheet = Stylesheet::from_variants("Button", &variants, &[], &[]);
+        let css = stylesheet.css_rules(0);
+        assert!(
+            css.contains(".button[data-size=\"Large\"] .wrapper {\n  display: none;\n}"),
+            "{css}"
+        );
+        // The unmatched element has a class of its own, for rendering its variant
+        let IntermediateNodeType::Frame { children } = &variants[1].node.node_type else {
+            panic!();
+        };
+        assert_eq!(
+            stylesheet.node_class_name(&variants[1].node),
+            Some("button")
+        );
+        assert_eq!(stylesheet.node_class_name(&children[0]), Some("wrapper-2"));
+        assert!(
+            css.contains(".wrapper-2 {\n  font: 400 24px / 20px Inter;\n}"),
+            "{css}"
+        );
+
+        // Roots always match, even where one was combined with its only child
+        let with_icon = node(frame(
+            "3:1",
+            "Button",
+            100.0,
+            serde_json::json!([text("3:2", "Label", 16.0), text("3:3", "Icon", 8.0)]),
+        ));
+        let label = node(text("4:1", "Button", 16.0));
+        let variants = [
+            variant(&[("Icon", "False")], &label),
+            variant(&[("Icon", "True")], &with_icon),
+        ];
+        assert_eq!(unmatched_variant_elements(&variants), ["3:2", "3:3"]);
+        let css = Stylesheet::from_variants("Button", &variants, &[], &[]).css_rules(0);
+        assert!(!css.contains("display: none"), "{css}");
+        assert!(css.contains(".button[data-icon=\"True\"] {"), "{css}");
+    }
+
+    /// A card with a label for each font size, one per variant
+    fn labels(font_sizes: &[f64]) -> Vec<Node> {
+        font_sizes
+            .iter()
+            .map(|font_size| {
+                node(frame(
+                    "1:1",
+                    "Card",
+                    100.0,
+                    serde_json::json!([text("1:2", "Label", *font_size)]),
+                ))
+            })
+            .collect()
+    }
+
+    fn font(font_size: u8) -> String {
+        format!("font: 400 {font_size}px / 20px Inter;")
+    }
+
+    #[test]
+    fn viewports_only_set_what_differs_from_the_next_narrower() {
+        let breakpoints = [
+            Breakpoint::new("Viewport", "Tablet", 641.0),
+            Breakpoint::new("Viewport", "Desktop", 1024.0),
+        ];
+        let css = |font_sizes: &[f64]| {
+            let nodes = labels(font_sizes);
+            let variants = ["Mobile", "Tablet", "Desktop"]
+                .iter()
+                .zip(nodes.iter())
+                .map(|(viewport, node)| variant(&[("Viewport", viewport)], node))
+                .collect::<Vec<_>>();
+            Stylesheet::from_variants("Card", &variants, &[], &breakpoints).css_rules(0)
+        };
+
+        // Tablet is the same as mobile
+        let tablet_as_mobile = css(&[16.0, 16.0, 24.0]);
+        assert!(!tablet_as_mobile.contains("641px"), "{tablet_as_mobile}");
+        assert!(
+            tablet_as_mobile.contains(&format!(
+                "@media (min-width: 1024px) {{\n  .card .label {{\n    {}\n  }}\n}}",
+                font(24)
+            )),
+            "{tablet_as_mobile}"
+        );
+
+        // Desktop is the same as tablet
+        let desktop_as_tablet = css(&[16.0, 24.0, 24.0]);
+        assert!(desktop_as_tablet.contains("641px"), "{desktop_as_tablet}");
+        assert!(!desktop_as_tablet.contains("1024px"), "{desktop_as_tablet}");
+    }
+
+    #[test]
+    fn viewports_combine_with_states() {
+        let nodes = labels(&[16.0, 18.0, 24.0, 28.0]);
+        let variants = [
+            variant(&[("Viewport", "Mobile"), ("Hover", "False")], &nodes[0]),
+            variant(&[("Viewport", "Mobile"), ("Hover", "True")], &nodes[1]),
+            variant(&[("Viewport", "Desktop"), ("Hover", "False")], &nodes[2]),
+            variant(&[("Viewport", "Desktop"), ("Hover", "True")], &nodes[3]),
+        ];
+        let css = Stylesheet::from_variants(
+            "Card",
+            &variants,
+            &[VariantState::new("Hover", "True", ":hover")],
+            &[Breakpoint::new("Viewport", "Desktop", 1024.0)],
+        )
+        .css_rules(0);
+        let (base, media) = css.split_once("@media (min-width: 1024px)").unwrap();
+        assert!(
+            base.contains(&format!(".label {{\n  {}\n", font(16))),
+            "{css}"
+        );
+        assert!(
+            base.contains(&format!(".card:hover .label {{\n  {}\n}}", font(18))),
+            "{css}"
+        );
+        assert!(
+            media.contains(&format!("  .card .label {{\n    {}\n  }}", font(24))),
+            "{css}"
+        );
+        assert!(
+            media.contains(&format!("  .card:hover .label {{\n    {}\n  }}", font(28))),
+            "{css}"
+        );
+    }
+
+    #[test]
+    fn viewports_combine_with_other_properties() {
+        let nodes = labels(&[16.0, 20.0, 16.0, 28.0]);
+        let variants = [
+            variant(&[("Size", "Small"), ("Viewport", "Mobile")], &nodes[0]),
+            variant(&[("Size", "Large"), ("Viewport", "Mobile")], &nodes[1]),
+            variant(&[("Size", "Small"), ("Viewport", "Desktop")], &nodes[2]),
+            variant(&[("Size", "Large"), ("Viewport", "Desktop")], &nodes[3]),
+        ];
+        let css = Stylesheet::from_variants(
+            "Card",
+            &variants,
+            &[],
+            &[Breakpoint::new("Viewport", "Desktop", 1024.0)],
+        )
+        .css_rules(0);
+        let (base, media) = css.split_once("@media (min-width: 1024px)").unwrap();
+        assert!(
+            base.contains(&format!(
+                ".card[data-size=\"Large\"] .label {{\n  {}\n}}",
+                font(20)
+            )),
+            "{css}"
+        );
+        // Small text is the same at every viewport
+        assert_eq!(
+            media,
+            format!(
+                " {{\n  .card[data-size=\"Large\"] .label {{\n    {}\n  }}\n}}\n",
+                font(28)
+            ),
+            "{css}"
+        );
+    }
+}
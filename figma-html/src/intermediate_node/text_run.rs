@@ -0,0 +1,350 @@
+use std::{borrow::Cow, ops::Range};
+
+use figma_schema::{Node as FigmaNode, TextDecoration, TypeStyle, TypeStyleOverride};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    text_decoration_line_value, text_transform_value, Appearance, CSSVariablesMap, Length,
+};
+use crate::{
+    css_properties::{
+        hyperlink_href, paints_color, style_font, style_value, type_style_font,
+        type_style_font_feature_settings,
+    },
+    ConversionOptions,
+};
+
+/**
+A section of a text node's characters that share a style override.
+
+The appearance only has the properties that differ from the text node's own
+appearance, except for decoration. When the runs differ in decoration, each has
+its own and the text node has none. A run without any is plain text.
+ */
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TextRun<'a> {
+    pub text: Cow<'a, str>,
+    pub appearance: Appearance,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub href: Option<Cow<'a, str>>,
+}
+
+/// A run's byte range in the characters, and its styling
+type RunRange<'a> = (Range<usize>, Appearance, Option<Cow<'a, str>>);
+
+impl<'a> TextRun<'a> {
+    /**
+    Split a text node's characters into runs by their `characterStyleOverrides`.
+
+    Returns nothing if the text is uniformly styled.
+     */
+    pub fn from_figma_node(
+        node: &'a FigmaNode,
+        css_variables: &mut CSSVariablesMap,
+        options: &ConversionOptions,
+    ) -> Vec<Self> {
+        let (Some(text), Some(base_style)) = (node.characters.as_deref(), node.style.as_ref())
+        else {
+            return vec![];
+        };
+        let overrides = node.character_style_overrides();
+        if overrides.iter().all(|&id| id == 0) {
+            return vec![];
+        }
+
+        let mut runs: Vec<RunRange<'a>> = vec![];
+        let mut start = 0;
+        // The overrides are indexed by UTF-16 code unit, and trailing unstyled
+        // characters may be left off
+        let mut utf16_index = 0;
+        let mut current_id = None;
+        for (byte_index, c) in text.char_indices() {
+            let id = overrides.get(utf16_index).copied().unwrap_or(0);
+            utf16_index += c.len_utf16();
+            if current_id.is_some_and(|current| current != id) {
+                push_run(
+                    &mut runs,
+                    start..byte_index,
+                    node,
+                    base_style,
+                    current_id.and_then(|id| node.style_override(id)),
+                    css_variables,
+                    options,
+                );
+                start = byte_index;
+            }
+            current_id = Some(id);
+        }
+        push_run(
+            &mut runs,
+            start..text.len(),
+            node,
+            base_style,
+            current_id.and_then(|id| node.style_override(id)),
+            css_variables,
+            options,
+        );
+
+        // Runs can't remove the decoration of the text they're in, so if any
+        // differs each run has its own and the text node has none
+        let base_decoration = base_style.text_decoration();
+        if base_decoration != TextDecoration::None
+            && runs
+                .iter()
+                .any(|(_, appearance, _)| appearance.text_decoration_line.is_some())
+        {
+            for (_, appearance, _) in runs.iter_mut() {
+                appearance.text_decoration_line =
+                    Some(appearance.text_decoration_line.unwrap_or(base_decoration))
+                        .filter(|d| *d != TextDecoration::None);
+            }
+        }
+
+        if runs
+            .iter()
+            .all(|(_, appearance, href)| href.is_none() && appearance == &Appearance::default())
+        {
+            return vec![];
+        }
+
+        runs.into_iter()
+            .map(|(range, appearance, href)| TextRun {
+                text: Cow::Borrowed(&text[range]),
+                appearance,
+                href,
+            })
+            .collect()
+    }
+
+    pub fn naive_css_string(&self) -> String {
+        let properties = &[
+            ("color", self.appearance.color.as_deref().map(Cow::Borrowed)),
+            (
+                "font",
+                self.appearance
+                    .font
+                    .as_ref()
+                    .map(|f| Cow::Owned(f.css_value())),
+            ),
+            (
+                "font-feature-settings",
+                self.appearance
+                    .font_feature_settings
+                    .as_deref()
+                    .map(Cow::Borrowed),
+            ),
+            (
+                "letter-spacing",
+                self.appearance
+                    .letter_spacing
+                    .as_ref()
+                    .map(|l| Cow::Owned(format!("{l}"))),
+            ),
+            (
+                "text-decoration-line",
+                self.appearance
+                    .text_decoration_line
+                    .map(text_decoration_line_value),
+            ),
+            (
+                "text-transform",
+                self.appearance.text_tranform.and_then(text_transform_value),
+            ),
+        ];
+        let mut output = String::new();
+        for (name, value) in properties.iter() {
+            if let Some(v) = value {
+                output.push_str(name);
+                output.push_str(": ");
+                output.push_str(v);
+                output.push(';');
+            }
+        }
+        output
+    }
+}
+
+/// Add a run, extending the previous one if they look the same
+fn push_run<'a>(
+    runs: &mut Vec<RunRange<'a>>,
+    range: Range<usize>,
+    node: &'a FigmaNode,
+    base_style: &TypeStyle,
+    style_override: Option<&'a TypeStyleOverride>,
+    css_variables: &mut CSSVariablesMap,
+    options: &ConversionOptions,
+) {
+    let (appearance, href) = match style_override {
+        Some(style_override) => {
+            override_appearance(node, base_style, style_override, css_variables, options)
+        }
+        None => (Appearance::default(), None),
+    };
+    match runs.last_mut() {
+        Some((last_range, last_appearance, last_href))
+            if last_appearance == &appearance && last_href == &href =>
+        {
+            last_range.end = range.end;
+        }
+        _ => runs.push((range, appearance, href)),
+    }
+}
+
+fn override_appearance<'a>(
+    node: &'a FigmaNode,
+    base_style: &TypeStyle,
+    style_override: &'a TypeStyleOverride,
+    css_variables: &mut CSSVariablesMap,
+    options: &ConversionOptions,
+) -> (Appearance, Option<Cow<'a, str>>) {
+    let style = base_style.with_override(style_override);
+
+    let font = type_style_font(&style, &options.fonts);
+    let font = if font == type_style_font(base_style, &options.fonts) {
+        None
+    } else {
+        Some(style_font(
+            css_variables,
+            style_override.inherit_text_style_id.as_deref(),
+            font,
+        ))
+    };
+
+    let color = style_override
+        .fills
+        .as_deref()
+        .and_then(paints_color)
+        .filter(|color| Some(color) != paints_color(node.fills()).as_ref())
+        .and_then(|color| {
+            style_value(
+                css_variables,
+                style_override.inherit_fill_style_id.as_deref(),
+                color,
+            )
+        });
+
+    // Links can't be nested, so a run can only be a link if the node isn't
+    let href = if base_style.hyperlink.is_none() {
+        style_override
+            .hyperlink
+            .as_ref()
+            .and_then(|hyperlink| hyperlink_href(hyperlink, options))
+    } else {
+        None
+    };
+
+    (
+        Appearance {
+            color,
+            font,
+            font_feature_settings: type_style_font_feature_settings(&style)
+                .filter(|f| Some(f) != type_style_font_feature_settings(base_style).as_ref()),
+            letter_spacing: style
+                .letter_spacing
+                .filter(|_| style.letter_spacing != base_style.letter_spacing)
+                .map(|l| Length::new_from_option_pixels(Some(l))),
+            // An override can remove the base style's decoration or case
+            text_decoration_line: Some(style.text_decoration())
+                .filter(|d| *d != base_style.text_decoration()),
+            text_tranform: Some(style.text_case()).filter(|c| *c != base_style.text_case()),
+            ..Appearance::default()
+        },
+        href,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextRun;
+    use crate::{figma_node_to_intermediate_node, ConversionOptions};
+
+    fn text_node(
+        characters: &str,
+        overrides: &[u32],
+        style: serde_json::Value,
+    ) -> figma_schema::Node {
+        serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Text",
+            "type": "TEXT",
+            "characters": characters,
+            "style": {
+                "fontFamily": "Inter",
+                "fontWeight": 400.0,
+                "fontSize": 16.0,
+                "lineHeightPx": 20.0,
+                "textDecoration": "UNDERLINE"
+            },
+            "characterStyleOverrides": overrides,
+            "styleOverrideTable": { "1": style }
+        }))
+        .unwrap()
+    }
+
+    fn runs(node: &figma_schema::Node) -> Vec<(String, String)> {
+        TextRun::from_figma_node(node, &mut Default::default(), &ConversionOptions::default())
+            .into_iter()
+            .map(|run| (run.text.to_string(), run.naive_css_string()))
+            .collect()
+    }
+
+    #[test]
+    fn overrides_are_indexed_by_utf16_code_unit() {
+        // The emoji is two UTF-16 code units and four bytes
+        let node = text_node(
+            "👍 ok and more",
+            &[0, 0, 0, 1, 1],
+            serde_json::json!({ "fontWeight": 700.0 }),
+        );
+        assert_eq!(
+            runs(&node),
+            vec![
+                ("👍 ".to_string(), String::new()),
+                (
+                    "ok".to_string(),
+                    r#"font:   700 16px/20px "Inter";"#.to_string()
+                ),
+                (" and more".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn overrides_can_remove_decoration() {
+        let node = text_node(
+            "plain link",
+            &[1, 1, 1, 1, 1],
+            serde_json::json!({ "textDecoration": "NONE" }),
+        );
+        assert_eq!(
+            runs(&node),
+            vec![
+                ("plain".to_string(), String::new()),
+                (
+                    " link".to_string(),
+                    "text-decoration-line: underline;".to_string()
+                ),
+            ]
+        );
+        let text = figma_node_to_intermediate_node(
+            &node,
+            &mut Default::default(),
+            &ConversionOptions::default(),
+        );
+        assert!(
+            !text.naive_css_string().contains("text-decoration"),
+            "{}",
+            text.naive_css_string()
+        );
+    }
+
+    #[test]
+    fn uniform_text_has_no_runs() {
+        let node = text_node(
+            "same",
+            &[1, 1],
+            serde_json::json!({ "textDecoration": "UNDERLINE" }),
+        );
+        assert!(runs(&node).is_empty());
+    }
+}
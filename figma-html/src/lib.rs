@@ -3,20 +3,34 @@ use std::{
     io::Write,
 };
 
-use figma_schema::Node;
-use html_escape::{encode_style, encode_text};
+use figma_schema::{Node, PaintType};
+use html_escape::{encode_double_quoted_attribute, encode_style, encode_text};
+use indexmap::IndexMap;
+mod accessibility;
+pub use accessibility::{annotate_accessibility, contrast_warnings, ContrastWarning};
+mod conversion_options;
+pub use conversion_options::{ConversionOptions, NodeLink};
+mod element_rules;
+pub use element_rules::{glob_match, ElementRule, ElementRules};
+mod font_mapping;
+pub use font_mapping::{figma_node_collect_fonts, FigmaFont, FontFace, FontMapping, FontRule};
 mod inherited_properties;
 pub use inherited_properties::InheritedProperties;
 pub mod mutator;
+mod output_options;
+pub use output_options::{Breakpoint, OutputOptions, VariantState};
+mod relative_units;
+pub use relative_units::{use_relative_units, RelativeUnits};
 
 use self::intermediate_node::{
-    format_css, CSSVariable, CSSVariablesMap, HtmlFormatter, IntermediateNode,
+    format_css, variant_data_attribute, CSSVariable, CSSVariablesMap, HtmlFormatter,
+    IntermediateNode, JsxFormatter, Stylesheet, Variant,
 };
 
 mod css_properties;
 pub mod intermediate_node;
 
-pub fn file_collect_css_variables(file: &figma_schema::File) -> CSSVariablesMap {
+pub fn file_collect_css_variables(file: &figma_schema::File) -> CSSVariablesMap<'_> {
     file.styles
         .iter()
         .map(|(key, style)| {
@@ -38,8 +52,7 @@ pub fn find_figma_node_by_id<'a>(
     file: &'a figma_schema::File,
     node_id: &str,
 ) -> Option<(&'a figma_schema::Node, Vec<&'a Node>)> {
-    file
-        .document
+    file.document
         .depth_first_stack_iter()
         .find(|(n, _)| n.id == node_id)
 }
@@ -47,15 +60,49 @@ pub fn find_figma_node_by_id<'a>(
 pub fn figma_node_to_intermediate_node<'a>(
     node: &'a Node,
     css_variables: &mut CSSVariablesMap,
+    options: &ConversionOptions,
 ) -> IntermediateNode<'a> {
-    IntermediateNode::from_figma_node(node, None, css_variables)
+    IntermediateNode::from_figma_node(node, None, css_variables, options)
 }
 
-pub fn intermediate_node_to_html_writer(
+/**
+Find the image fills used by a node and its visible descendants, where they
+are drawn as backgrounds.
+
+Keys are the image refs and values are the ids of the nodes using them. The
+images can be fetched using Figma's `GET file images` endpoint.
+ */
+pub fn figma_node_collect_image_refs(node: &Node) -> IndexMap<&str, Vec<&str>> {
+    fn collect<'a>(node: &'a Node, image_refs: &mut IndexMap<&'a str, Vec<&'a str>>) {
+        let fills = if css_properties::fills_as_background(node) {
+            node.fills()
+        } else {
+            &[]
+        };
+        for paint in fills {
+            if let (PaintType::Image, true, Some(image_ref)) =
+                (paint.r#type, paint.visible(), paint.image_ref.as_deref())
+            {
+                image_refs.entry(image_ref).or_default().push(&node.id);
+            }
+        }
+        for child in node.enabled_children() {
+            collect(child, image_refs);
+        }
+    }
+    let mut image_refs = IndexMap::new();
+    collect(node, &mut image_refs);
+    image_refs
+}
+
+fn html_page_writer(
     writer: &mut impl Write,
-    node: &IntermediateNode,
+    title: &str,
+    options: &OutputOptions,
     css_variables: &CSSVariablesMap,
     additional_css_rules: &str,
+    class_rules: &str,
+    body: &str,
 ) -> Result<(), std::io::Error> {
     let mut naive_css = "margin: 0;".to_string();
     for v in css_variables.values() {
@@ -68,26 +115,324 @@ pub fn intermediate_node_to_html_writer(
     }
 
     let body_css = format_css(3, &naive_css).unwrap_or_default();
+    let lang = match options.lang.as_deref() {
+        Some(lang) => format!(r#" lang="{}""#, encode_double_quoted_attribute(lang)),
+        None => String::new(),
+    };
 
     writeln!(
         writer,
         r#"<!DOCTYPE html>
-<html
+<html{lang}
   ><head
     ><meta charset="utf-8" /><title>{}</title
     ><style type="text/css">
 {additional_css_rules}
       body {{{}}}
-    </style></head
+{}    </style></head
   ><body
-    >{}</body
+    >{body}</body
   ></html
 >"#,
-        encode_text(&node.figma.as_ref().map(|f| f.name.borrow()).unwrap_or("")),
+        encode_text(title),
         encode_style(&body_css),
-        HtmlFormatter {
+        encode_style(class_rules),
+    )
+}
+
+pub fn intermediate_node_to_html_writer(
+    writer: &mut impl Write,
+    node: &IntermediateNode,
+    css_variables: &CSSVariablesMap,
+    additional_css_rules: &str,
+    options: &OutputOptions,
+) -> Result<(), std::io::Error> {
+    let stylesheet = options
+        .shared_stylesheet
+        .then(|| Stylesheet::from_intermediate_node(node));
+    let class_rules = stylesheet
+        .as_ref()
+        .map(|s| s.css_rules(3))
+        .unwrap_or_default();
+
+    html_page_writer(
+        writer,
+        node.figma.as_ref().map(|f| f.name.borrow()).unwrap_or(""),
+        options,
+        css_variables,
+        additional_css_rules,
+        &class_rules,
+        &HtmlFormatter {
             intermediate_node: node,
-            nesting_depth: 2
+            nesting_depth: 2,
+            stylesheet: stylesheet.as_ref(),
+            attributes: &[],
         }
+        .to_string(),
+    )
+}
+
+/**
+Write the variants of a component set as one component whose styles switch on
+data attributes.
+
+Each variant's own elements are written, with that variant's data attributes.
+Variants in an interaction state, such as hover, or for a wider viewport aren't
+written, as their styles apply with pseudo-classes and media queries. The styles
+always go in a stylesheet.
+ */
+pub fn variants_to_html_writer(
+    writer: &mut impl Write,
+    title: &str,
+    variants: &[Variant],
+    css_variables: &CSSVariablesMap,
+    additional_css_rules: &str,
+    options: &OutputOptions,
+) -> Result<(), std::io::Error> {
+    let states = &options.variant_states;
+    let breakpoints = &options.breakpoints;
+    let stylesheet = Stylesheet::from_variants(title, variants, states, breakpoints);
+
+    let mut body = String::new();
+    for variant in variants
+        .iter()
+        .filter(|v| !v.is_state(states) && v.min_width(breakpoints) == 0.0)
+    {
+        let attributes = variant
+            .properties
+            .iter()
+            .filter(|(key, _)| {
+                !states.iter().any(|s| s.property == *key)
+                    && !breakpoints.iter().any(|b| b.property == *key)
+            })
+            .map(|(key, value)| (variant_data_attribute(key), value.to_string()))
+            .collect::<Vec<_>>();
+        body.push_str(
+            &HtmlFormatter {
+                intermediate_node: &variant.node,
+                nesting_depth: 2,
+                stylesheet: Some(&stylesheet),
+                attributes: &attributes,
+            }
+            .to_string(),
+        );
+    }
+
+    html_page_writer(
+        writer,
+        title,
+        options,
+        css_variables,
+        additional_css_rules,
+        &stylesheet.css_rules(3),
+        &body,
     )
 }
+
+/// Make a Figma name usable as a React component name
+pub fn jsx_component_name(name: &str) -> String {
+    let mut component_name = String::new();
+    for word in name.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            component_name.push(first.to_ascii_uppercase());
+            component_name.push_str(chars.as_str());
+        }
+    }
+    if component_name.is_empty() || component_name.starts_with(|c: char| c.is_ascii_digit()) {
+        component_name.insert_str(0, "Component");
+    }
+    component_name
+}
+
+/**
+Write a `.tsx` file with a React function component rendering the node.
+
+The CSS variables are defined on the root element. With a shared stylesheet the
+rules are exported as `css` and rendered in a `<style>` element, as are any
+additional rules such as `@font-face` rules.
+ */
+pub fn intermediate_node_to_jsx_writer(
+    writer: &mut impl Write,
+    node: &IntermediateNode,
+    css_variables: &CSSVariablesMap,
+    component_name: &str,
+    additional_css_rules: &str,
+    options: &OutputOptions,
+) -> Result<(), std::io::Error> {
+    let stylesheet = options
+        .shared_stylesheet
+        .then(|| Stylesheet::from_intermediate_node(node));
+
+    let mut css = String::new();
+    if !additional_css_rules.is_empty() {
+        css.push_str(additional_css_rules);
+        css.push('\n');
+    }
+    if let Some(stylesheet) = stylesheet.as_ref() {
+        css.push_str(&stylesheet.css_rules(0));
+    }
+    let has_style = !css.is_empty();
+    if has_style {
+        let css = css
+            .replace('\\', "\\\\")
+            .replace('`', "\\`")
+            .replace("${", "\\${");
+        writeln!(writer, "export const css = `\n{css}`;\n")?;
+    }
+
+    let root = JsxFormatter {
+        intermediate_node: node,
+        nesting_depth: if has_style { 3 } else { 2 },
+        stylesheet: stylesheet.as_ref(),
+        css_variables: Some(css_variables),
+    };
+
+    writeln!(writer, "export function {component_name}() {{")?;
+    writeln!(writer, "  return (")?;
+    if has_style {
+        writeln!(writer, "    <>")?;
+        writeln!(writer, "      <style>{{css}}</style>")?;
+        write!(writer, "{root}")?;
+        writeln!(writer, "    </>")?;
+    } else {
+        write!(writer, "{root}")?;
+    }
+    writeln!(writer, "  );")?;
+    writeln!(writer, "}}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        figma_node_collect_image_refs, figma_node_to_intermediate_node,
+        intermediate_node_to_jsx_writer, variants_to_html_writer, ConversionOptions, OutputOptions,
+        Variant,
+    };
+
+    #[test]
+    fn image_refs_of_backgrounds() {
+        let node: figma_schema::Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Card",
+            "type": "FRAME",
+            "fills": [{ "type": "IMAGE", "imageRef": "photo" }],
+            "children": [
+                {
+                    "id": "1:2",
+                    "name": "Icon",
+                    "type": "VECTOR",
+                    "fills": [{ "type": "IMAGE", "imageRef": "icon" }]
+                },
+                {
+                    "id": "1:3",
+                    "name": "Hidden",
+                    "type": "RECTANGLE",
+                    "fills": [{ "type": "IMAGE", "imageRef": "hidden", "visible": false }]
+                },
+                {
+                    "id": "1:4",
+                    "name": "Photo",
+                    "type": "RECTANGLE",
+                    "fills": [{ "type": "IMAGE", "imageRef": "photo" }]
+                }
+            ]
+        }))
+        .unwrap();
+        let image_refs = figma_node_collect_image_refs(&node);
+        assert_eq!(
+            image_refs.into_iter().collect::<Vec<_>>(),
+            vec![("photo", vec!["1:1", "1:4"])]
+        );
+    }
+
+    #[test]
+    fn jsx_css_is_a_template_literal() {
+        let node: figma_schema::Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Card",
+            "type": "FRAME"
+        }))
+        .unwrap();
+        let intermediate_node = figma_node_to_intermediate_node(
+            &node,
+            &mut Default::default(),
+            &ConversionOptions::default(),
+        );
+        let mut jsx = vec![];
+        intermediate_node_to_jsx_writer(
+            &mut jsx,
+            &intermediate_node,
+            &Default::default(),
+            "Card",
+            r#"@font-face { src: url("fonts/`${name}`\\.woff2"); }"#,
+            &OutputOptions::default(),
+        )
+        .unwrap();
+        let jsx = String::from_utf8(jsx).unwrap();
+        assert!(
+            jsx.starts_with(concat!(
+                "export const css = `\n",
+                r#"@font-face { src: url("fonts/\`\${name}\`\\\\.woff2"); }"#,
+                "\n`;\n"
+            )),
+            "{jsx}"
+        );
+        assert!(jsx.contains("      <style>{css}</style>\n"), "{jsx}");
+    }
+
+    #[test]
+    fn variants_are_rendered_with_their_own_elements() {
+        let nodes = [("1", false), ("2", true)].map(|(page, icon)| {
+            let mut children = vec![serde_json::json!({
+                "id": format!("{page}:2"),
+                "name": "Label",
+                "type": "FRAME",
+                "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 80.0, "height": 10.0 }
+            })];
+            if icon {
+                children.push(serde_json::json!({
+                    "id": format!("{page}:3"),
+                    "name": "Icon",
+                    "type": "FRAME",
+                    "absoluteBoundingBox": { "x": 80.0, "y": 0.0, "width": 10.0, "height": 10.0 }
+                }));
+            }
+            serde_json::from_value::<figma_schema::Node>(serde_json::json!({
+                "id": format!("{page}:1"),
+                "name": "Button",
+                "type": "FRAME",
+                "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 100.0, "height": 10.0 },
+                "children": children
+            }))
+            .unwrap()
+        });
+        let variants = [("False", &nodes[0]), ("True", &nodes[1])].map(|(icon, node)| Variant {
+            properties: vec![("Icon", icon)],
+            node: figma_node_to_intermediate_node(
+                node,
+                &mut Default::default(),
+                &ConversionOptions::default(),
+            ),
+        });
+        let mut html = vec![];
+        variants_to_html_writer(
+            &mut html,
+            "Button",
+            &variants,
+            &Default::default(),
+            "",
+            &OutputOptions::default(),
+        )
+        .unwrap();
+        let html = String::from_utf8(html).unwrap();
+        for id in ["1:1", "1:2", "2:1", "2:2", "2:3"] {
+            assert_eq!(
+                html.matches(&format!("data-figma-id=\"{id}\"")).count(),
+                1,
+                "{html}"
+            );
+        }
+        assert_eq!(html.matches("class=\"label\"").count(), 2, "{html}");
+    }
+}
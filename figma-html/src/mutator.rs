@@ -10,12 +10,14 @@ mod collapse_to_padding;
 mod combine_parent_child;
 mod drop_empty_absolute_frames;
 mod elevate_frame_appearance_properties;
+mod pipeline;
 
 pub use collapse_to_gap::collapse_to_gap;
 pub use collapse_to_padding::collapse_to_padding;
 pub use combine_parent_child::combine_parent_child;
 pub use drop_empty_absolute_frames::drop_empty_absolute_frames;
 pub use elevate_frame_appearance_properties::elevate_frame_appearance_properties;
+pub use pipeline::{FnMutator, Mutator, Pipeline};
 
 /**
 Recursive node visitor with callback that can mutate the node
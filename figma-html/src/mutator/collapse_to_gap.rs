@@ -26,6 +26,7 @@ pub fn collapse_to_gap(node: &mut IntermediateNode, _css_variables: &mut CSSVari
                     Some(FlexContainer {
                         direction,
                         gap: parent_gap,
+                        wrap: None,
                         ..
                     }),
                 node_type: IntermediateNodeType::Frame { children },
@@ -62,12 +63,14 @@ pub fn collapse_to_gap(node: &mut IntermediateNode, _css_variables: &mut CSSVari
                             flex_grow,
                             height,
                             width,
+                            transform: None,
                             ..
                         },
                     frame_appearance:
                         FrameAppearance {
                             background: None,
                             box_shadow: None,
+                            backdrop_filter: None,
                             stroke: None,
                             ..
                         },
@@ -76,6 +79,7 @@ pub fn collapse_to_gap(node: &mut IntermediateNode, _css_variables: &mut CSSVari
                             children: grand_children,
                         },
                     href: None,
+                    anchor: None,
                     ..
                 }) = potential_gaps.next()
                 {
@@ -93,6 +97,8 @@ pub fn collapse_to_gap(node: &mut IntermediateNode, _css_variables: &mut CSSVari
                                     && pg.location.flex_grow.unwrap_or(0.0) == 0.0
                                     && &pg.location.height == height
                                     && &pg.location.width == width
+                                    && pg.anchor.is_none()
+                                    && pg.location.transform.is_none()
                                     && other_grand_children.is_empty()
                             }
                             _ => false,
@@ -36,6 +36,7 @@ pub fn collapse_to_padding(
                                 | JustifyContent::Center
                                 | JustifyContent::FlexEnd,
                             ),
+                        wrap: None,
                         ..
                     }),
                 location:
@@ -69,12 +70,14 @@ pub fn collapse_to_padding(
                                     flex_grow,
                                     height,
                                     width,
+                                    transform: None,
                                     ..
                                 },
                             frame_appearance:
                                 FrameAppearance {
                                     background: None,
                                     box_shadow: None,
+                                    backdrop_filter: None,
                                     stroke: None,
                                     ..
                                 },
@@ -83,6 +86,7 @@ pub fn collapse_to_padding(
                                     children: grand_children,
                                 },
                             href: None,
+                            anchor: None,
                             ..
                         },
                     )) = match direction_forwards {
@@ -122,6 +122,7 @@ pub const BLOCK_FLEX: FlexContainer = FlexContainer {
     direction: FlexDirection::Column,
     gap: Length::Zero,
     justify_content: None,
+    wrap: None,
 };
 
 /**
@@ -129,15 +130,24 @@ Combine child nodes into their parent
 
 In order to do this certain conditions have to be met:
 * No other children of the parent node
+* Neither node may be a grid, as the child's cell would be lost
 * No background on the child node
 * The child must not be absolutely positioned
-* The child must not have an opacity
+* The child must not have an opacity or blend mode
 * The child must not have a border radius as the clipping complicates
   interactions with padding.
 * The child must not have box shadow or stroke as it complicates interactions
   with padding and the parent box shadow and stroke.
+* The child must not have a filter or backdrop filter as they would be lost.
+* The child must not clip its content, as the parent's padding would then be
+  clipped too. Nor may it be masking its children.
 * The child must not have an href, as the target area might grow if combined
   with the parent's padding.
+* The child must not have an anchor, as links to it would be lost.
+* The child must not be transformed, as the parent's padding would be
+  transformed with it.
+* The child must not be text with styled runs, as the runs would become flex
+  items of the parent.
 * The child must be the same size as the parent's context box
   - The child is allowed to be smaller or bigger if its content is sized as
     such, but mustn't have width or height properties sizing it differently.
@@ -176,25 +186,26 @@ From the parent take:
 * location align-self
 * location flex-grow
 * location inset
-* opacity
+* opacity and blend mode
 * background
 * border radius
 * box shadow
 * stroke
+* clipping and masking
 * href
+* anchor
 
 From the child take:
 * flex-container (direction and gap)
 * The appearance properties, except for opacity. Take the parent values if not
-set for the child.
+  set for the child.
 * The node type and any associated properties (eg grandchildren, text or vectors).
 * width and height. Add in the parent padding if present.
 
 From a combination take:
 * padding - add it together
 */
-
-/**
+/*
  * Look at parent, child combinations
  *
  * For each combination work out:
@@ -221,6 +232,9 @@ pub fn combine_parent_child(
                   height_from_descent_inclusive,
                   ..
               }| {
+            if parent.grid_container.is_some() {
+                return false;
+            }
             let children = match parent {
                 IntermediateNode {
                     node_type: IntermediateNodeType::Frame { children },
@@ -240,10 +254,19 @@ pub fn combine_parent_child(
 
             if child.frame_appearance.background.is_some()
                 || child.appearance.opacity.unwrap_or(1.0) != 1.0
+                || child.appearance.mix_blend_mode.is_some()
                 || child.frame_appearance.border_radius.is_some()
                 || child.frame_appearance.box_shadow.is_some()
+                || child.frame_appearance.filter.is_some()
+                || child.frame_appearance.backdrop_filter.is_some()
                 || child.frame_appearance.stroke.is_some()
+                || child.frame_appearance.clips_content
+                || child.frame_appearance.clip_path.is_some()
+                || child.frame_appearance.mask.is_some()
                 || child.href.is_some()
+                || child.anchor.is_some()
+                || child.grid_container.is_some()
+                || child.location.transform.is_some()
                 || child.location.inset.is_some()
             {
                 return false;
@@ -259,6 +282,13 @@ pub fn combine_parent_child(
 
                     Some(grandchildren)
                 }
+                // Styled runs would become flex items of the combined node
+                IntermediateNodeType::Text { runs, .. } if !runs.is_empty() => return false,
+                // Truncated text is clamped by its own box, which a flex
+                // container can't be
+                IntermediateNodeType::Text {
+                    max_lines: Some(_), ..
+                } => return false,
                 _ => None,
             };
 
@@ -484,7 +514,19 @@ pub fn combine_parent_child(
                         .font
                         .take()
                         .or(parent.appearance.font.take()),
+                    font_feature_settings: child
+                        .appearance
+                        .font_feature_settings
+                        .take()
+                        .or(parent.appearance.font_feature_settings.take()),
+                    letter_spacing: child
+                        .appearance
+                        .letter_spacing
+                        .take()
+                        .or(parent.appearance.letter_spacing.take()),
                     opacity: parent.appearance.opacity.take(),
+                    mix_blend_mode: parent.appearance.mix_blend_mode.take(),
+                    isolate: child.appearance.isolate || parent.appearance.isolate,
                     preserve_whitespace: child.appearance.preserve_whitespace
                         || parent.appearance.preserve_whitespace,
                     text_tranform: child
@@ -497,6 +539,21 @@ pub fn combine_parent_child(
                         .text_decoration_line
                         .take()
                         .or(parent.appearance.text_decoration_line.take()),
+                    text_align: child
+                        .appearance
+                        .text_align
+                        .take()
+                        .or(parent.appearance.text_align.take()),
+                    text_align_vertical: child
+                        .appearance
+                        .text_align_vertical
+                        .take()
+                        .or(parent.appearance.text_align_vertical.take()),
+                    text_indent: child
+                        .appearance
+                        .text_indent
+                        .take()
+                        .or(parent.appearance.text_indent.take()),
                 };
 
                 for i in 0..4 {
@@ -516,3 +573,66 @@ pub fn combine_parent_child(
         },
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::combine_parent_child;
+    use crate::{
+        figma_node_to_intermediate_node, intermediate_node::IntermediateNodeType, ConversionOptions,
+    };
+
+    /// Whether a padded auto-layout frame is combined with its only child, a text
+    fn combines_with_text(style: serde_json::Value) -> bool {
+        let node: figma_schema::Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Tag",
+            "type": "FRAME",
+            "layoutMode": "HORIZONTAL",
+            "primaryAxisSizingMode": "AUTO",
+            "counterAxisSizingMode": "AUTO",
+            "paddingLeft": 8.0,
+            "paddingRight": 8.0,
+            "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 116.0, "height": 40.0 },
+            "children": [{
+                "id": "1:2",
+                "name": "Label",
+                "type": "TEXT",
+                "characters": "A long label",
+                "absoluteBoundingBox": { "x": 8.0, "y": 0.0, "width": 100.0, "height": 40.0 },
+                "style": style
+            }]
+        }))
+        .unwrap();
+        let mut css_variables = Default::default();
+        let mut intermediate_node = figma_node_to_intermediate_node(
+            &node,
+            &mut css_variables,
+            &ConversionOptions::default(),
+        );
+        let combined = combine_parent_child(&mut intermediate_node, &mut css_variables);
+        assert_eq!(
+            combined,
+            matches!(
+                intermediate_node.node_type,
+                IntermediateNodeType::Text { .. }
+            )
+        );
+        combined
+    }
+
+    #[test]
+    fn truncated_text_is_not_combined() {
+        let style = serde_json::json!({
+            "fontFamily": "Inter",
+            "fontWeight": 400.0,
+            "fontSize": 16.0,
+            "lineHeightPx": 20.0
+        });
+        assert!(combines_with_text(style.clone()));
+
+        let mut truncated = style;
+        truncated["textTruncation"] = serde_json::json!("ENDING");
+        truncated["maxLines"] = serde_json::json!(2);
+        assert!(!combines_with_text(truncated));
+    }
+}
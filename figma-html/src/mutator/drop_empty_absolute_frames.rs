@@ -12,7 +12,7 @@ Drop empty absolutely positioned frames
 
 Drop frames with:
 - no children
-- no background background or stroke
+- no background, stroke or backdrop filter
 - absolutely positioned so no affect on parent size
  */
 pub fn drop_empty_absolute_frames(
@@ -30,10 +30,12 @@ pub fn drop_empty_absolute_frames(
                                  FrameAppearance {
                                      background: None,
                                      box_shadow: None,
+                                     backdrop_filter: None,
                                      stroke: None,
                                      ..
                                  },
                             href: None,
+                            anchor: None,
                              ..
                         } if children.is_empty())
         },
@@ -26,6 +26,15 @@ In order for this to happen certain conditions need to be met:
     auto.
   - The parent cannot be align-self stretch.
   - The grandparent cannot have align-items stretch.
+* The parent may not be a grid, as its cells may be bigger than the child.
+* The child may not have a filter or backdrop filter. Elevated properties
+  would escape the filter.
+* The child may not have a blend mode, as elevated properties would no longer
+  blend with what is behind it.
+* The child may not clip its content, as its clipping would no longer match
+  its border radius. Nor may it be masking its children.
+* The child may not be transformed, as elevated properties would no longer be
+  transformed with it.
 * If the child has border radius set then the parent cannot have any of the
   other properties set. This is because elevating border-radius would modify
   the existing property (eg clipping the corners of the existing background).
@@ -38,6 +47,8 @@ The way properties individually work are:
   this isn't possible with variable colours.
 * Only elevate box-shadow, stroke and border radius for similar reasons of both
   values being required to accurately show the result.
+* Don't elevate border radius to a parent that clips its content, as it would
+  round the clipping of the parent's other children.
 * href of the child will be elevated if href is set on the child. The parent's
   existing value doesn't matter if the child has an href value.
 
@@ -60,6 +71,9 @@ pub fn elevate_frame_appearance_properties(
                   height_from_descent_inclusive,
                   ..
               }| {
+            if parent.grid_container.is_some() {
+                return false;
+            }
             let children = match parent {
                 IntermediateNode {
                     node_type: IntermediateNodeType::Frame { children },
@@ -82,6 +96,17 @@ pub fn elevate_frame_appearance_properties(
                 None => return false,
             };
 
+            if static_child.frame_appearance.filter.is_some()
+                || static_child.frame_appearance.backdrop_filter.is_some()
+                || static_child.frame_appearance.clips_content
+                || static_child.frame_appearance.clip_path.is_some()
+                || static_child.frame_appearance.mask.is_some()
+                || static_child.appearance.mix_blend_mode.is_some()
+                || static_child.location.transform.is_some()
+            {
+                return false;
+            }
+
             {
                 let parent_flex = parent.flex_container.as_ref().unwrap_or(&BLOCK_FLEX);
                 let (
@@ -131,10 +156,13 @@ pub fn elevate_frame_appearance_properties(
             {
                 parent.frame_appearance.background =
                     static_child.frame_appearance.background.take();
+                parent.frame_appearance.background_blend_mode =
+                    static_child.frame_appearance.background_blend_mode.take();
                 mutated = true;
             }
 
             if (parent.frame_appearance.border_radius.is_none()
+                && !parent.frame_appearance.clips_content
                 || parent.frame_appearance.border_radius
                     == static_child.frame_appearance.border_radius)
                 && static_child.frame_appearance.border_radius.is_some()
@@ -0,0 +1,402 @@
+use std::io::Write;
+
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+
+use crate::intermediate_node::{CSSVariablesMap, IntermediateNode, IntermediateNodeType};
+
+use super::{
+    collapse_to_gap, collapse_to_padding, combine_parent_child, drop_empty_absolute_frames,
+    elevate_frame_appearance_properties,
+};
+
+/// A pass that simplifies intermediate nodes
+pub trait Mutator {
+    /// Name for enabling, disabling and tracing the pass
+    fn name(&self) -> &str;
+
+    /// Mutate the node and its descendants, returning whether anything changed
+    fn mutate(&self, node: &mut IntermediateNode, css_variables: &mut CSSVariablesMap) -> bool;
+}
+
+/// A mutator from a function, such as those in this module
+pub struct FnMutator {
+    pub name: &'static str,
+    pub function: fn(&mut IntermediateNode, &mut CSSVariablesMap) -> bool,
+}
+
+impl Mutator for FnMutator {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn mutate(&self, node: &mut IntermediateNode, css_variables: &mut CSSVariablesMap) -> bool {
+        (self.function)(node, css_variables)
+    }
+}
+
+/**
+Mutators run in order until none of them change the node.
+
+After a mutator changes the node, the pipeline starts again from the first
+mutator. `Pipeline::default()` has all of this module's mutators, and
+`Pipeline::empty()` has none, for building one from only your own.
+ */
+pub struct Pipeline {
+    mutators: Vec<Box<dyn Mutator>>,
+    /// How many changes to allow before giving up on the node settling
+    pub max_iterations: usize,
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        let mut pipeline = Self::empty();
+        pipeline.push(FnMutator {
+            name: "combine-parent-child",
+            function: combine_parent_child,
+        });
+        pipeline.push(FnMutator {
+            name: "collapse-to-padding",
+            function: collapse_to_padding,
+        });
+        pipeline.push(FnMutator {
+            name: "collapse-to-gap",
+            function: collapse_to_gap,
+        });
+        pipeline.push(FnMutator {
+            name: "drop-empty-absolute-frames",
+            function: drop_empty_absolute_frames,
+        });
+        pipeline.push(FnMutator {
+            name: "elevate-frame-appearance-properties",
+            function: elevate_frame_appearance_properties,
+        });
+        pipeline
+    }
+}
+
+/// Serialized nodes by Figma id, with children replaced by their ids
+fn node_fingerprints(node: &IntermediateNode, fingerprints: &mut IndexMap<String, String>) {
+    let children = match &node.node_type {
+        IntermediateNodeType::Frame { children } => children.as_slice(),
+        _ => &[],
+    };
+    for child in children.iter() {
+        node_fingerprints(child, fingerprints);
+    }
+    let Some(id) = node.figma.as_ref().map(|f| f.id.to_string()) else {
+        return;
+    };
+    let mut value = serde_json::to_value(node).unwrap_or_default();
+    if let Some(frame) = value.pointer_mut("/node_type/Frame") {
+        frame["children"] = children
+            .iter()
+            .map(|child| child.figma.as_ref().map(|f| f.id.to_string()))
+            .collect();
+    }
+    fingerprints.insert(id, value.to_string());
+}
+
+impl Pipeline {
+    /// A pipeline without any mutators, unlike the default pipeline
+    pub fn empty() -> Self {
+        Self {
+            mutators: vec![],
+            max_iterations: 1000,
+        }
+    }
+
+    pub fn push(&mut self, mutator: impl Mutator + 'static) {
+        self.mutators.push(Box::new(mutator));
+    }
+
+    /// Remove the mutator with this name, returning whether there was one
+    pub fn remove(&mut self, name: &str) -> bool {
+        let len = self.mutators.len();
+        self.mutators.retain(|m| m.name() != name);
+        self.mutators.len() != len
+    }
+
+    /// Keep only the named mutators, in the order given. Fails if a name isn't
+    /// one of the pipeline's mutators.
+    pub fn select(&mut self, names: &[impl AsRef<str>]) -> Result<()> {
+        let mut mutators = std::mem::take(&mut self.mutators);
+        for name in names {
+            let name = name.as_ref();
+            match mutators.iter().position(|m| m.name() == name) {
+                Some(index) => self.mutators.push(mutators.remove(index)),
+                None => bail!(
+                    "Unknown mutator {name}. The mutators are {}",
+                    self.names()
+                        .chain(mutators.iter().map(|m| m.name()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.mutators.iter().map(|m| m.name())
+    }
+
+    /**
+    Run the mutators until the node stops changing.
+
+    With a trace, each change is written as the mutator's name and the ids of
+    the nodes it changed. Fails if the node is still changing after the maximum
+    iterations, naming the mutators that were.
+     */
+    pub fn run(
+        &self,
+        node: &mut IntermediateNode,
+        css_variables: &mut CSSVariablesMap,
+        mut trace: Option<&mut dyn Write>,
+    ) -> Result<()> {
+        let mut recent_changes = vec![];
+        for _ in 0..self.max_iterations {
+            let before = trace.as_ref().map(|_| {
+                let mut fingerprints = IndexMap::new();
+                node_fingerprints(node, &mut fingerprints);
+                fingerprints
+            });
+            let Some(mutator) = self
+                .mutators
+                .iter()
+                .find(|mutator| mutator.mutate(node, css_variables))
+            else {
+                return Ok(());
+            };
+            recent_changes.push(mutator.name());
+            if recent_changes.len() > self.mutators.len() {
+                recent_changes.remove(0);
+            }
+
+            if let (Some(trace), Some(before)) = (trace.as_mut(), before) {
+                let mut after = IndexMap::new();
+                node_fingerprints(node, &mut after);
+                let changes = before
+                    .iter()
+                    .filter_map(|(id, fingerprint)| match after.get(id) {
+                        None => Some(format!("removed {id}")),
+                        Some(f) if f != fingerprint => Some(format!("changed {id}")),
+                        _ => None,
+                    })
+                    .chain(
+                        after
+                            .keys()
+                            .filter(|id| !before.contains_key(*id))
+                            .map(|id| format!("added {id}")),
+                    )
+                    .collect::<Vec<_>>();
+                writeln!(trace, "{}: {}", mutator.name(), changes.join(", "))
+                    .context("Failed to write mutator trace")?;
+            }
+        }
+        recent_changes.sort();
+        recent_changes.dedup();
+        bail!(
+            "The node was still changing after {} iterations, by {}",
+            self.max_iterations,
+            recent_changes.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::{FnMutator, Pipeline};
+    use crate::{
+        figma_node_to_intermediate_node,
+        intermediate_node::{CSSVariablesMap, IntermediateNode, IntermediateNodeType},
+        ConversionOptions,
+    };
+
+    fn name<'a>(node: &'a IntermediateNode) -> &'a str {
+        &node.figma.as_ref().unwrap().name
+    }
+
+    fn rename(node: &mut IntermediateNode, name: String) {
+        node.figma.as_mut().unwrap().name = Cow::Owned(name);
+    }
+
+    /// Removes a trailing `!` from the node's name
+    fn trim(node: &mut IntermediateNode, _: &mut CSSVariablesMap) -> bool {
+        let Some(trimmed) = name(node).strip_suffix('!') else {
+            return false;
+        };
+        rename(node, trimmed.to_string());
+        true
+    }
+
+    /// Renames `a` to `b!!`
+    fn a_to_b(node: &mut IntermediateNode, _: &mut CSSVariablesMap) -> bool {
+        if name(node) != "a" {
+            return false;
+        }
+        rename(node, "b!!".to_string());
+        true
+    }
+
+    /// Swaps the names `a` and `b`, so never settles
+    fn flip(node: &mut IntermediateNode, _: &mut CSSVariablesMap) -> bool {
+        let flipped = if name(node) == "a" { "b" } else { "a" };
+        rename(node, flipped.to_string());
+        true
+    }
+
+    /// Trims the names of the node's children
+    fn trim_children(node: &mut IntermediateNode, css_variables: &mut CSSVariablesMap) -> bool {
+        let IntermediateNodeType::Frame { children } = &mut node.node_type else {
+            return false;
+        };
+        let mut changed = false;
+        for child in children.iter_mut() {
+            changed |= trim(child, css_variables);
+        }
+        changed
+    }
+
+    fn run(pipeline: &Pipeline, node_name: &str) -> (String, anyhow::Result<()>, String) {
+        let figma_node: figma_schema::Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": node_name,
+            "type": "FRAME",
+            "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 10.0, "height": 10.0 }
+        }))
+        .unwrap();
+        let mut node = figma_node_to_intermediate_node(
+            &figma_node,
+            &mut Default::default(),
+            &ConversionOptions::default(),
+        );
+        let mut trace = vec![];
+        let result = pipeline.run(&mut node, &mut Default::default(), Some(&mut trace));
+        (
+            name(&node).to_string(),
+            result,
+            String::from_utf8(trace).unwrap(),
+        )
+    }
+
+    #[test]
+    fn runs_from_the_first_mutator_until_nothing_changes() {
+        let mut pipeline = Pipeline::empty();
+        pipeline.push(FnMutator {
+            name: "trim",
+            function: trim,
+        });
+        pipeline.push(FnMutator {
+            name: "a-to-b",
+            function: a_to_b,
+        });
+        let (name, result, trace) = run(&pipeline, "a!!");
+        result.unwrap();
+        assert_eq!(name, "b");
+        assert_eq!(
+            trace,
+            "trim: changed 1:1\n\
+             trim: changed 1:1\n\
+             a-to-b: changed 1:1\n\
+             trim: changed 1:1\n\
+             trim: changed 1:1\n"
+        );
+    }
+
+    #[test]
+    fn fails_naming_the_mutator_that_does_not_settle() {
+        let mut pipeline = Pipeline::empty();
+        pipeline.push(FnMutator {
+            name: "trim",
+            function: trim,
+        });
+        pipeline.push(FnMutator {
+            name: "flip",
+            function: flip,
+        });
+        let (_, result, trace) = run(&pipeline, "a!");
+        let error = result.unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "The node was still changing after 1000 iterations, by flip"
+        );
+        assert_eq!(trace.lines().count(), 1000);
+        assert_eq!(
+            trace
+                .lines()
+                .filter(|line| line.starts_with("trim"))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn traces_changes_within_nodes_without_figma_ids() {
+        let figma_node: figma_schema::Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Wrapper",
+            "type": "FRAME",
+            "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 10.0, "height": 10.0 },
+            "children": [{
+                "id": "1:2",
+                "name": "Label!",
+                "type": "FRAME",
+                "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 10.0, "height": 10.0 }
+            }]
+        }))
+        .unwrap();
+        let mut node = figma_node_to_intermediate_node(
+            &figma_node,
+            &mut Default::default(),
+            &ConversionOptions::default(),
+        );
+        node.figma = None;
+        let mut pipeline = Pipeline::empty();
+        pipeline.push(FnMutator {
+            name: "trim-children",
+            function: trim_children,
+        });
+        let mut trace = vec![];
+        pipeline
+            .run(&mut node, &mut Default::default(), Some(&mut trace))
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(trace).unwrap(),
+            "trim-children: changed 1:2\n"
+        );
+    }
+
+    #[test]
+    fn select_and_remove_mutators() {
+        let mut pipeline = Pipeline::default();
+        assert!(pipeline.remove("collapse-to-gap"));
+        assert!(!pipeline.remove("collapse-to-gap"));
+        pipeline
+            .select(&[
+                "elevate-frame-appearance-properties",
+                "combine-parent-child",
+            ])
+            .unwrap();
+        assert_eq!(
+            pipeline.names().collect::<Vec<_>>(),
+            [
+                "elevate-frame-appearance-properties",
+                "combine-parent-child"
+            ]
+        );
+
+        let error = Pipeline::default()
+            .select(&["collapse-to-gaps"])
+            .unwrap_err();
+        assert!(
+            error.to_string().starts_with(
+                "Unknown mutator collapse-to-gaps. The mutators are combine-parent-child, "
+            ),
+            "{error}"
+        );
+        assert!(Pipeline::empty().names().next().is_none());
+    }
+}
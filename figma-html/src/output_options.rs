@@ -0,0 +1,74 @@
+/// Options for writing intermediate nodes as HTML
+#[derive(Debug)]
+pub struct OutputOptions {
+    /// Put the CSS in class rules in the `<style>` element instead of inline
+    /// `style` attributes. Elements with the same CSS share a class.
+    pub shared_stylesheet: bool,
+    /// Variant properties of component sets that are interaction states rather
+    /// than data attributes
+    pub variant_states: Vec<VariantState>,
+    /// Variant property values for wider viewports, such as
+    /// `Viewport=Desktop`, which are styled with media queries rather than
+    /// data attributes
+    pub breakpoints: Vec<Breakpoint>,
+    /// The language of the page's text, such as `en-GB`
+    pub lang: Option<String>,
+}
+
+impl Default for OutputOptions {
+    fn default() -> Self {
+        Self {
+            shared_stylesheet: false,
+            variant_states: vec![
+                VariantState::new("Hover", "True", ":hover"),
+                VariantState::new("Focus", "True", ":focus-visible"),
+                VariantState::new(
+                    "Disabled",
+                    "True",
+                    r#":is(:disabled, [aria-disabled="true"])"#,
+                ),
+            ],
+            breakpoints: vec![],
+            lang: None,
+        }
+    }
+}
+
+/// A variant property that represents an interaction state, such as hover
+#[derive(Debug, Clone)]
+pub struct VariantState {
+    pub property: String,
+    /// The property's value when in the state
+    pub value: String,
+    /// Selector matching the state, such as `:hover`
+    pub selector: String,
+}
+
+impl VariantState {
+    pub fn new(property: &str, value: &str, selector: &str) -> Self {
+        Self {
+            property: property.to_string(),
+            value: value.to_string(),
+            selector: selector.to_string(),
+        }
+    }
+}
+
+/// A variant property value for viewports at least as wide as a breakpoint
+#[derive(Debug, Clone)]
+pub struct Breakpoint {
+    pub property: String,
+    pub value: String,
+    /// The width of the narrowest viewport in pixels
+    pub min_width: f64,
+}
+
+impl Breakpoint {
+    pub fn new(property: &str, value: &str, min_width: f64) -> Self {
+        Self {
+            property: property.to_string(),
+            value: value.to_string(),
+            min_width,
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use crate::intermediate_node::{
+    CSSVariablesMap, Font, IntermediateNode, IntermediateNodeType, Length, LengthUnit, LineHeight,
+};
+
+/// Options for sizing with `rem` and `em` rather than pixels, so that the page
+/// scales with the reader's font size
+#[derive(Debug, Clone)]
+pub struct RelativeUnits {
+    /// The root font size in pixels that the design is drawn at, which is
+    /// `1rem`
+    pub root_font_size: f64,
+    /// Convert widths and heights too
+    pub sizes: bool,
+}
+
+impl Default for RelativeUnits {
+    fn default() -> Self {
+        Self {
+            root_font_size: 16.0,
+            sizes: false,
+        }
+    }
+}
+
+/// Convert a font's size, and line height if it is in pixels, to `rem`. Its
+/// text style's CSS variable is set to the converted font.
+fn rem_font(font: &mut Option<Font>, css_variables: &mut CSSVariablesMap, root_font_size: f64) {
+    let Some(font) = font.as_mut() else {
+        return;
+    };
+    font.size = font.size.px_to(LengthUnit::Rem, root_font_size);
+    if let LineHeight::Length(line_height) = &mut font.line_height {
+        *line_height = line_height.px_to(LengthUnit::Rem, root_font_size);
+    }
+    if let Some(variable) = font
+        .variable
+        .as_deref()
+        .and_then(|name| css_variables.values_mut().find(|v| v.name == name))
+    {
+        variable.value = Some(font.to_string());
+    }
+}
+
+fn em_letter_spacing(letter_spacing: &mut Option<Length<'static>>, font_size: Option<f64>) {
+    if let (Some(l), Some(font_size)) = (letter_spacing.as_mut(), font_size) {
+        if font_size > 0.0 {
+            *l = l.px_to(LengthUnit::Em, font_size);
+        }
+    }
+}
+
+/**
+Convert the lengths of a node and its descendants from pixels to relative
+units.
+
+Padding, gaps, font sizes and line heights become `rem`, as do widths and
+heights with `sizes`. Letter spacing becomes `em` of the text's font size.
+Borders, shadows, corner radii and positions stay in pixels.
+
+The fonts of text styles' CSS variables are converted too.
+ */
+pub fn use_relative_units(
+    node: &mut IntermediateNode<'_>,
+    css_variables: &mut CSSVariablesMap,
+    units: &RelativeUnits,
+) {
+    convert(node, css_variables, units, None);
+}
+
+fn convert(
+    node: &mut IntermediateNode<'_>,
+    css_variables: &mut CSSVariablesMap,
+    units: &RelativeUnits,
+    inherited_font_size: Option<f64>,
+) {
+    let rem = |length: &mut Length| *length = length.px_to(LengthUnit::Rem, units.root_font_size);
+
+    // The font size is needed in pixels, so is found before the font is
+    // converted
+    let font_size = node
+        .appearance
+        .font
+        .as_ref()
+        .and_then(Font::size_px)
+        .or_else(|| node.figma.as_ref().and_then(|f| f.font_size))
+        .or(inherited_font_size);
+    em_letter_spacing(&mut node.appearance.letter_spacing, font_size);
+    rem_font(
+        &mut node.appearance.font,
+        css_variables,
+        units.root_font_size,
+    );
+
+    node.location.padding.iter_mut().for_each(rem);
+    if units.sizes {
+        node.location.width.iter_mut().for_each(rem);
+        node.location.height.iter_mut().for_each(rem);
+    }
+    if let Some(flex_container) = node.flex_container.as_mut() {
+        rem(&mut flex_container.gap);
+        if let Some(wrap) = flex_container.wrap.as_mut() {
+            rem(&mut wrap.gap);
+        }
+    }
+    if let Some(grid_container) = node.grid_container.as_mut() {
+        rem(&mut grid_container.row_gap);
+        rem(&mut grid_container.column_gap);
+    }
+
+    match &mut node.node_type {
+        IntermediateNodeType::Text { runs, .. } => {
+            for run in runs.iter_mut() {
+                let run_font_size = run
+                    .appearance
+                    .font
+                    .as_ref()
+                    .and_then(Font::size_px)
+                    .or(font_size);
+                em_letter_spacing(&mut run.appearance.letter_spacing, run_font_size);
+                rem_font(
+                    &mut run.appearance.font,
+                    css_variables,
+                    units.root_font_size,
+                );
+            }
+        }
+        IntermediateNodeType::Frame { children } => {
+            for child in children.iter_mut() {
+                convert(child, css_variables, units, font_size);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{use_relative_units, RelativeUnits};
+    use crate::{
+        figma_node_to_intermediate_node,
+        intermediate_node::{CSSVariable, CSSVariablesMap},
+        ConversionOptions,
+    };
+    use figma_schema::Node;
+
+    #[test]
+    fn fonts_and_spacing_in_relative_units() {
+        let node: Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Heading",
+            "type": "TEXT",
+            "characters": "Heading",
+            "styles": { "text": "S:1" },
+            "style": {
+                "fontFamily": "GDS Transport Website",
+                "fontWeight": 700.0,
+                "fontSize": 24.0,
+                "lineHeightPx": 30.0,
+                "letterSpacing": 3.0
+            }
+        }))
+        .unwrap();
+        let mut css_variables = CSSVariablesMap::new();
+        css_variables.insert(
+            "S:1".into(),
+            CSSVariable {
+                name: "--heading".to_string(),
+                value: None,
+            },
+        );
+        let mut node = figma_node_to_intermediate_node(
+            &node,
+            &mut css_variables,
+            &ConversionOptions::default(),
+        );
+        use_relative_units(&mut node, &mut css_variables, &RelativeUnits::default());
+        assert_eq!(
+            node.naive_css_string(),
+            "font: var(--heading);letter-spacing: 0.125em;"
+        );
+        assert_eq!(
+            css_variables["S:1"].value.as_deref(),
+            Some(r#"  700 1.5rem/1.875rem "GDS Transport Website""#)
+        );
+    }
+
+    #[test]
+    fn borders_and_shadows_stay_in_pixels() {
+        let node: Node = serde_json::from_value(serde_json::json!({
+            "id": "1:1",
+            "name": "Card",
+            "type": "FRAME",
+            "layoutMode": "VERTICAL",
+            "paddingTop": 16.0,
+            "itemSpacing": 8.0,
+            "strokes": [{ "type": "SOLID", "color": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 1.0 } }],
+            "strokeWeight": 2.0,
+            "strokeAlign": "INSIDE",
+            "cornerRadius": 4.0,
+            "effects": [{
+                "type": "DROP_SHADOW",
+                "visible": true,
+                "radius": 4.0,
+                "color": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 0.5 },
+                "offset": { "x": 0.0, "y": 2.0 }
+            }],
+            "absoluteBoundingBox": { "x": 0.0, "y": 0.0, "width": 320.0, "height": 100.0 }
+        }))
+        .unwrap();
+        let mut css_variables = CSSVariablesMap::new();
+        let mut node = figma_node_to_intermediate_node(
+            &node,
+            &mut css_variables,
+            &ConversionOptions::default(),
+        );
+        use_relative_units(&mut node, &mut css_variables, &RelativeUnits::default());
+        assert_eq!(
+            node.naive_css_string(),
+            "border-radius: 4px 4px 4px 4px;\
+             box-shadow: 0px 2px 4px 0px rgb(0, 0, 0, 0.5);\
+             display: flex;flex-direction: column;gap: 0.5rem;\
+             outline: 2px solid rgb(0, 0, 0, 1);outline-offset: -2px;\
+             padding: 1rem 0 0 0;"
+        );
+    }
+}
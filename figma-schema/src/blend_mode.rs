@@ -3,10 +3,11 @@ use serde::{Deserialize, Serialize};
 /// how layer blends with layers below
 ///
 /// [Figma documentation](https://www.figma.com/developers/api#blendmode-type)
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[typeshare::typeshare]
 pub enum BlendMode {
+    /// Children of a group blend with the layers below the group, as if it wasn't there
     PassThrough,
     Normal,
     Darken,
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use super::Color;
+
+/// A position color pair representing a gradient stop
+///
+/// [Figma documentation](https://www.figma.com/developers/api#colorstop-type)
+#[derive(Debug, Deserialize, Serialize)]
+#[typeshare::typeshare]
+pub struct ColorStop {
+    /// Value between 0 and 1 representing position along gradient axis
+    pub position: f64,
+    /// Color attached to corresponding position
+    pub color: Color,
+}
@@ -2,9 +2,23 @@ use serde::{Deserialize, Serialize};
 
 /// [Figma documentation](https://www.figma.com/developers/api#component-type)
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 #[typeshare::typeshare]
 pub struct Component {
     pub key: String,
     pub name: String,
     pub description: String,
+    /// The id of the component set the component is a variant of
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component_set_id: Option<String>,
+}
+
+/// [Figma documentation](https://www.figma.com/developers/api#component-set-type)
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[typeshare::typeshare]
+pub struct ComponentSet {
+    pub key: String,
+    pub name: String,
+    pub description: String,
 }
@@ -29,13 +29,27 @@ pub struct Effect {
     /// How far the shadow is projected in the x and y directions
     #[serde(skip_serializing_if = "Option::is_none")]
     pub offset: Option<Vector>,
+    /// Radius of the blur effect (applies to shadows as well)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub radius: Option<f64>,
     /// How far the shadow spreads
     #[serde(skip_serializing_if = "Option::is_none")]
     pub spread: Option<f64>,
+    /// Whether to show the shadow behind translucent or transparent pixels (applies only to drop shadows)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_shadow_behind_node: Option<bool>,
 }
 
 impl Effect {
+    pub fn radius(&self) -> f64 {
+        self.radius.unwrap_or(0.0)
+    }
+
     pub fn spread(&self) -> f64 {
         self.spread.unwrap_or(0.0)
     }
+
+    pub fn show_shadow_behind_node(&self) -> bool {
+        self.show_shadow_behind_node.unwrap_or(false)
+    }
 }
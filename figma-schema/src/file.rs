@@ -1,4 +1,4 @@
-use super::{Component, Node, Style};
+use super::{Component, ComponentSet, Node, Style};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +9,10 @@ pub struct File {
     pub document: Node,
     #[typeshare(serialized_as = "std::collections::HashMap<String, Component>")]
     pub components: IndexMap<String, Component>,
+    /// Files exported before component sets existed don't have any
+    #[serde(default)]
+    #[typeshare(serialized_as = "std::collections::HashMap<String, ComponentSet>")]
+    pub component_sets: IndexMap<String, ComponentSet>,
     #[typeshare(serialized_as = "std::collections::HashMap<String, Style>")]
     pub styles: IndexMap<String, Style>,
     pub name: String,
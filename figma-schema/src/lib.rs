@@ -1,5 +1,6 @@
 mod blend_mode;
 mod color;
+mod color_stop;
 mod component;
 mod easing_type;
 mod effect;
@@ -7,27 +8,38 @@ mod file;
 mod layout_constraint;
 mod node;
 mod paint;
+mod path;
+mod reaction;
 mod rectangle;
 mod style;
 mod styles;
+mod transform;
 mod type_style;
 mod vector;
 pub use self::{
     blend_mode::BlendMode,
     color::Color,
-    component::Component,
+    color_stop::ColorStop,
+    component::{Component, ComponentSet},
     easing_type::EasingType,
     effect::{Effect, EffectType},
     file::File,
     layout_constraint::{LayoutConstraint, LayoutConstraintHorizontal, LayoutConstraintVertical},
     node::{
-        AxisSizingMode, CounterAxisAlignItems, LayoutAlign, LayoutMode, LayoutPositioning, Node,
-        NodeType, PrimaryAxisAlignItems, StrokeAlign, StrokeWeights,
+        AxisSizingMode, CounterAxisAlignContent, CounterAxisAlignItems, GridChildAlign,
+        LayoutAlign, LayoutMode, LayoutPositioning, LayoutWrap, MaskType, Node, NodeType,
+        PrimaryAxisAlignItems, StrokeAlign, StrokeWeights,
     },
-    paint::Paint,
+    paint::{Paint, PaintType, ScaleMode},
+    path::{Path, WindingRule},
+    reaction::{Action, ActionType, Navigation, Reaction, Trigger, TriggerType},
     rectangle::Rectangle,
     style::{Style, StyleType},
     styles::Styles,
-    type_style::{Hyperlink, HyperlinkType, TextAutoResize, TextCase, TextDecoration, TypeStyle},
+    transform::Transform,
+    type_style::{
+        Hyperlink, HyperlinkType, LineHeightUnit, TextAlignHorizontal, TextAlignVertical,
+        TextAutoResize, TextCase, TextDecoration, TextTruncation, TypeStyle, TypeStyleOverride,
+    },
     vector::Vector,
 };
@@ -1,7 +1,8 @@
 use super::{
-    Color, Component, EasingType, Effect, File, LayoutConstraint, Paint, Rectangle, Styles,
-    TypeStyle,
+    BlendMode, Color, Component, EasingType, Effect, File, LayoutConstraint, Paint, Path, Reaction,
+    Rectangle, Styles, Transform, TypeStyle, TypeStyleOverride, Vector,
 };
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize, Copy, Clone)]
@@ -16,6 +17,19 @@ pub enum StrokeAlign {
     Center,
 }
 
+/// How a mask hides the layers it masks
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[typeshare::typeshare]
+pub enum MaskType {
+    /// The mask's opacity, including that of its fills, shows the layers
+    Alpha,
+    /// The mask's shape shows the layers, ignoring its fills
+    Vector,
+    /// The brightness of the mask's fills shows the layers
+    Luminance,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[typeshare::typeshare]
@@ -23,6 +37,33 @@ pub enum LayoutMode {
     None,
     Horizontal,
     Vertical,
+    Grid,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[typeshare::typeshare]
+pub enum LayoutWrap {
+    NoWrap,
+    Wrap,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[typeshare::typeshare]
+pub enum CounterAxisAlignContent {
+    Auto,
+    SpaceBetween,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[typeshare::typeshare]
+pub enum GridChildAlign {
+    Auto,
+    Min,
+    Center,
+    Max,
 }
 
 #[derive(Debug, Deserialize, Serialize, PartialEq)]
@@ -120,6 +161,12 @@ pub struct Node {
     /// Position of stroke relative to vector outline
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stroke_align: Option<StrokeAlign>,
+    /// An array of paths representing the object fill. Only returned if `geometry=paths` is requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fill_geometry: Option<Vec<Path>>,
+    /// An array of paths representing the object stroke. Only returned if `geometry=paths` is requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stroke_geometry: Option<Vec<Path>>,
     /// An array of floating point numbers describing the pattern of dash length and gap lengths that the vector path follows. For example a value of [1, 2] indicates that the path has a dash of length 1 followed by a gap of length 2, repeated.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stroke_dashes: Option<Vec<f64>>,
@@ -135,9 +182,36 @@ pub struct Node {
     /// The easing curve used in the prototyping transition on this node
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transition_easing: Option<EasingType>,
+    /// Node ID of node to transition to in prototyping. Deprecated in favour of `reactions`.
+    #[serde(rename = "transitionNodeID", skip_serializing_if = "Option::is_none")]
+    pub transition_node_id: Option<String>,
+    /// The prototype interactions of this node
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reactions: Option<Vec<Reaction>>,
     /// Opacity of the node
     #[serde(skip_serializing_if = "Option::is_none")]
     pub opacity: Option<f64>,
+    /// How this node blends with nodes behind it in the scene
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blend_mode: Option<BlendMode>,
+    /// Whether this node is a mask for the sibling nodes above it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_mask: Option<bool>,
+    /// How this node masks, if it is a mask
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mask_type: Option<MaskType>,
+    /// Whether or not this node clips content outside of its bounds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clips_content: Option<bool>,
+    /// The transform of the node relative to its parent. Only present if `geometry=paths` is requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relative_transform: Option<Transform>,
+    /// The rotation of the node in radians, anticlockwise, if not 0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<f64>,
+    /// Width and height of the node before it is transformed. Only present if `geometry=paths` is requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<Vector>,
     /// Bounding box of the node in absolute space coordinates
     #[serde(skip_serializing_if = "Option::is_none")]
     pub absolute_bounding_box: Option<Rectangle>,
@@ -159,6 +233,51 @@ pub struct Node {
     /// The distance between children of the frame. Can be negative. This property is only applicable for auto-layout frames.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub item_spacing: Option<f64>,
+    /// Whether the children of a horizontal auto-layout frame wrap onto new lines when there isn't room. This property is only applicable for auto-layout frames.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layout_wrap: Option<LayoutWrap>,
+    /// The distance between wrapped lines of children. Null means it is the same as `itemSpacing`. This property is only applicable for auto-layout frames with wrapping.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counter_axis_spacing: Option<f64>,
+    /// Whether wrapped lines of children are packed together or spread apart. This property is only applicable for auto-layout frames with wrapping.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counter_axis_align_content: Option<CounterAxisAlignContent>,
+    /// The number of rows of a grid auto-layout frame
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid_row_count: Option<u32>,
+    /// The number of columns of a grid auto-layout frame
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid_column_count: Option<u32>,
+    /// The distance between rows of a grid auto-layout frame
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid_row_gap: Option<f64>,
+    /// The distance between columns of a grid auto-layout frame
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid_column_gap: Option<f64>,
+    /// The sizes of the rows of a grid auto-layout frame, as a value for CSS grid-template-rows
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid_rows_sizing: Option<String>,
+    /// The sizes of the columns of a grid auto-layout frame, as a value for CSS grid-template-columns
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid_columns_sizing: Option<String>,
+    /// The index of the row the node starts in, counting from 0. This property is only applicable for direct children of grid auto-layout frames.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid_row_anchor_index: Option<u32>,
+    /// The index of the column the node starts in, counting from 0. This property is only applicable for direct children of grid auto-layout frames.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid_column_anchor_index: Option<u32>,
+    /// The number of rows the node spans. This property is only applicable for direct children of grid auto-layout frames.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid_row_span: Option<u32>,
+    /// The number of columns the node spans. This property is only applicable for direct children of grid auto-layout frames.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid_column_span: Option<u32>,
+    /// The horizontal alignment of the node within its grid cells. This property is only applicable for direct children of grid auto-layout frames.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid_child_horizontal_align: Option<GridChildAlign>,
+    /// The vertical alignment of the node within its grid cells. This property is only applicable for direct children of grid auto-layout frames.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grid_child_vertical_align: Option<GridChildAlign>,
     /// Determines whether a layer's size and position should be determined by auto-layout settings or manually adjustable.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub layout_positioning: Option<LayoutPositioning>,
@@ -189,6 +308,13 @@ pub struct Node {
     /// Style of text including font family and weight
     #[serde(skip_serializing_if = "Option::is_none")]
     pub style: Option<TypeStyle>,
+    /// Array with same number of elements as characters in text box, each element is a reference to the styleOverrideTable defined below and maps to the corresponding character in the characters field. Elements with value 0 have the default type style
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub character_style_overrides: Option<Vec<u32>>,
+    /// Map from ID to TypeStyle for looking up style overrides
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[typeshare(serialized_as = "Option<std::collections::HashMap<String, TypeStyleOverride>>")]
+    pub style_override_table: Option<IndexMap<String, TypeStyleOverride>>,
     /// Horizontal and vertical layout contraints for node
     #[serde(skip_serializing_if = "Option::is_none")]
     pub constraints: Option<LayoutConstraint>,
@@ -198,6 +324,9 @@ pub struct Node {
     /// This property is applicable only for direct children of auto-layout frames, ignored otherwise. Determines whether a layer should stretch along the parent’s primary axis. A 0 corresponds to a fixed size and 1 corresponds to stretch
     #[serde(skip_serializing_if = "Option::is_none")]
     pub layout_grow: Option<f64>,
+    /// ID of component that this instance came from, refers to components table
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component_id: Option<String>,
 }
 
 impl Node {
@@ -258,15 +387,59 @@ impl Node {
         self.children().iter().filter(|c| c.visible())
     }
 
+    pub fn is_mask(&self) -> bool {
+        self.is_mask.unwrap_or(false)
+    }
+
+    pub fn mask_type(&self) -> MaskType {
+        self.mask_type.unwrap_or(MaskType::Alpha)
+    }
+
     pub fn fills(&self) -> &[Paint] {
         self.fills.as_deref().unwrap_or_default()
     }
 
+    pub fn reactions(&self) -> &[Reaction] {
+        self.reactions.as_deref().unwrap_or_default()
+    }
+
     pub fn strokes(&self) -> &[Paint] {
         self.strokes.as_deref().unwrap_or_default()
     }
 
-    pub fn depth_first_stack_iter(&self) -> NodeDepthFirstStackIterator {
+    pub fn character_style_overrides(&self) -> &[u32] {
+        self.character_style_overrides
+            .as_deref()
+            .unwrap_or_default()
+    }
+
+    /// Look up a style override by its ID, from `character_style_overrides`
+    pub fn style_override(&self, id: u32) -> Option<&TypeStyleOverride> {
+        self.style_override_table
+            .as_ref()?
+            .get(id.to_string().as_str())
+    }
+
+    pub fn effects(&self) -> &[Effect] {
+        self.effects.as_deref().unwrap_or_default()
+    }
+
+    pub fn fill_geometry(&self) -> &[Path] {
+        self.fill_geometry.as_deref().unwrap_or_default()
+    }
+
+    pub fn stroke_geometry(&self) -> &[Path] {
+        self.stroke_geometry.as_deref().unwrap_or_default()
+    }
+
+    /// The variant properties of a component in a component set, parsed from its `Key=Value, ...` name
+    pub fn variant_properties(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.name
+            .split(", ")
+            .filter_map(|key_value| key_value.split_once('='))
+    }
+
+    pub fn depth_first_stack_iter(&self) -> NodeDepthFirstStackIterator<'_> {
         NodeDepthFirstStackIterator {
             stack: vec![self],
             iter_stack: vec![self.children().iter()],
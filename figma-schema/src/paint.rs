@@ -1,7 +1,7 @@
-use super::{BlendMode, Color, Vector};
+use super::{BlendMode, Color, ColorStop, Transform, Vector};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, PartialEq, Copy, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[typeshare::typeshare]
 pub enum PaintType {
@@ -13,10 +13,28 @@ pub enum PaintType {
     Image,
 }
 
+/// Image scaling mode
+#[derive(Debug, Deserialize, Serialize, PartialEq, Copy, Clone)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[typeshare::typeshare]
+pub enum ScaleMode {
+    /// Scale the image to cover the node, cropping the overflow
+    Fill,
+    /// Scale the image to fit within the node
+    Fit,
+    /// Repeat the image at its natural size multiplied by the scaling factor
+    Tile,
+    /// Position and crop the image with the image transform
+    Stretch,
+    /// Plugin API name for `STRETCH`
+    Crop,
+}
+
 /// A solid color, gradient, or image texture that can be applied as fills or strokes
 ///
 /// [Figma documentation](https://www.figma.com/developers/api#paint-type)
 #[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
 #[typeshare::typeshare]
 pub struct Paint {
     pub r#type: PaintType,
@@ -29,12 +47,27 @@ pub struct Paint {
     /// Solid color of the paint
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<Color>,
-    /// How this node blends with nodes behind it in the scene
+    /// How this paint blends with the paints below it
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blend_mode: Option<BlendMode>,
     /// This field contains three vectors, each of which are a position in normalized object space (normalized object space is if the top left corner of the bounding box of the object is (0, 0) and the bottom right is (1,1)). The first position corresponds to the start of the gradient (value 0 for the purposes of calculating gradient stops), the second position is the end of the gradient (value 1), and the third handle position determines the width of the gradient. See image examples below:
     #[serde(skip_serializing_if = "Option::is_none")]
     pub gradient_handle_positions: Option<[Vector; 3]>,
+    /// Positions of key points along the gradient axis with the colors anchored there. Colors along the gradient are interpolated smoothly between neighboring gradient stops.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gradient_stops: Option<Vec<ColorStop>>,
+    /// Image scaling mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale_mode: Option<ScaleMode>,
+    /// Affine transform applied to the image, only present if scaleMode is `STRETCH`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_transform: Option<Transform>,
+    /// Amount image is scaled by in tiling, only present if scaleMode is `TILE`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scaling_factor: Option<f64>,
+    /// A reference to an image embedded in this node. To download the image using this reference, use the `GET file images` endpoint to retrieve the mapping from image references to image URLs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_ref: Option<String>,
 }
 
 impl Paint {
@@ -49,4 +82,24 @@ impl Paint {
     pub fn color(&self) -> Option<&Color> {
         self.color.as_ref()
     }
+
+    /// The solid color with the paint's opacity multiplied into its alpha
+    pub fn color_with_opacity(&self) -> Option<Color> {
+        self.color().map(|color| Color {
+            alpha: color.alpha * self.opacity(),
+            ..color.clone()
+        })
+    }
+
+    pub fn scale_mode(&self) -> ScaleMode {
+        self.scale_mode.unwrap_or(ScaleMode::Fill)
+    }
+
+    pub fn scaling_factor(&self) -> f64 {
+        self.scaling_factor.unwrap_or(1.0)
+    }
+
+    pub fn gradient_stops(&self) -> &[ColorStop] {
+        self.gradient_stops.as_deref().unwrap_or_default()
+    }
 }
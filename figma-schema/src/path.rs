@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// Winding rule used to determine the inside of a path
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[typeshare::typeshare]
+pub enum WindingRule {
+    Nonzero,
+    Evenodd,
+}
+
+/// A vector svg path
+///
+/// [Figma documentation](https://www.figma.com/developers/api#path-type)
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[typeshare::typeshare]
+pub struct Path {
+    /// A series of path commands that encodes how to draw the path
+    pub path: String,
+    /// The winding rule for the path, either "EVENODD" or "NONZERO"
+    pub winding_rule: WindingRule,
+}
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+/// The event that triggers a prototype interaction
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[typeshare::typeshare]
+pub enum TriggerType {
+    OnClick,
+    OnHover,
+    OnPress,
+    OnDrag,
+    AfterTimeout,
+    MouseEnter,
+    MouseLeave,
+    MouseUp,
+    MouseDown,
+    OnKeyDown,
+    OnKeyUp,
+    OnMediaHit,
+    OnMediaEnd,
+}
+
+/// [Figma documentation](https://www.figma.com/developers/api#trigger-type)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+#[typeshare::typeshare]
+pub struct Trigger {
+    pub r#type: TriggerType,
+}
+
+/// What a prototype interaction does
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[typeshare::typeshare]
+pub enum ActionType {
+    /// Go back to the previous frame
+    Back,
+    /// Close the overlay
+    Close,
+    /// Open a URL
+    Url,
+    /// Go to another node
+    Node,
+    UpdateMediaRuntime,
+    SetVariable,
+    SetVariableMode,
+    Conditional,
+}
+
+/// How a node action moves to its destination
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[typeshare::typeshare]
+pub enum Navigation {
+    /// Replace the current frame with the destination
+    Navigate,
+    /// Replace the current overlay with the destination
+    Swap,
+    /// Open the destination as an overlay
+    Overlay,
+    /// Scroll to the destination within the current frame
+    ScrollTo,
+    /// Change to another variant of the component
+    ChangeTo,
+}
+
+/// [Figma documentation](https://www.figma.com/developers/api#action-type)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+#[typeshare::typeshare]
+pub struct Action {
+    pub r#type: ActionType,
+    /// URL to open, if URL type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// ID of the node to go to, if NODE type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination_id: Option<String>,
+    /// How to go to the destination, if NODE type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub navigation: Option<Navigation>,
+}
+
+/// A prototype interaction
+///
+/// [Figma documentation](https://www.figma.com/developers/api#reaction-type)
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+#[typeshare::typeshare]
+pub struct Reaction {
+    /// Deprecated in favour of `actions`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<Action>,
+    /// The actions performed, in order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actions: Option<Vec<Action>>,
+    pub trigger: Trigger,
+}
+
+impl Reaction {
+    /// The actions, falling back to the deprecated single action
+    pub fn actions(&self) -> impl Iterator<Item = &Action> {
+        match self.actions.as_deref() {
+            Some(actions) => actions.iter().chain(None),
+            None => [].iter().chain(self.action.as_ref()),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+
+use super::Vector;
+
+/// A 2D affine transform, as the top two rows of a 3x3 matrix
+///
+/// A point `(x, y)` is transformed to
+/// `(m[0][0] * x + m[0][1] * y + m[0][2], m[1][0] * x + m[1][1] * y + m[1][2])`.
+///
+/// [Figma documentation](https://www.figma.com/developers/api#transform-type)
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(transparent)]
+#[typeshare::typeshare]
+pub struct Transform(pub [[f64; 3]; 2]);
+
+impl Transform {
+    pub const IDENTITY: Self = Self([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+
+    /// A rotation by an angle in radians, anticlockwise as shown in Figma
+    pub fn rotation(radians: f64) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        Self([[cos, sin, 0.0], [-sin, cos, 0.0]])
+    }
+
+    /// The transform without its translation
+    pub fn linear(&self) -> Self {
+        let [[a, c, _], [b, d, _]] = self.0;
+        Self([[a, c, 0.0], [b, d, 0.0]])
+    }
+
+    /// Whether the transform is the identity, allowing for rounding errors
+    pub fn is_identity(&self) -> bool {
+        self.0
+            .iter()
+            .flatten()
+            .zip(Self::IDENTITY.0.iter().flatten())
+            .all(|(a, b)| (a - b).abs() < 1e-6)
+    }
+
+    /// The transform applying `other` and then this
+    pub fn multiply(&self, other: &Self) -> Self {
+        let [[a1, c1, e1], [b1, d1, f1]] = self.0;
+        let [[a2, c2, e2], [b2, d2, f2]] = other.0;
+        Self([
+            [a1 * a2 + c1 * b2, a1 * c2 + c1 * d2, a1 * e2 + c1 * f2 + e1],
+            [b1 * a2 + d1 * b2, b1 * c2 + d1 * d2, b1 * e2 + d1 * f2 + f1],
+        ])
+    }
+
+    /// The transform undoing this, unless it flattens points onto a line
+    pub fn invert(&self) -> Option<Self> {
+        let [[a, c, e], [b, d, f]] = self.0;
+        let determinant = a * d - b * c;
+        if determinant.abs() < 1e-12 {
+            return None;
+        }
+        Some(Self([
+            [
+                d / determinant,
+                -c / determinant,
+                (c * f - d * e) / determinant,
+            ],
+            [
+                -b / determinant,
+                a / determinant,
+                (b * e - a * f) / determinant,
+            ],
+        ]))
+    }
+
+    pub fn apply(&self, point: &Vector) -> Vector {
+        let [[a, c, e], [b, d, f]] = self.0;
+        Vector {
+            x: a * point.x + c * point.y + e,
+            y: b * point.x + d * point.y + f,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Transform, Vector};
+
+    #[test]
+    fn invert_undoes_multiply() {
+        let transform =
+            Transform::rotation(0.5).multiply(&Transform([[2.0, 0.0, 10.0], [0.0, -1.0, 5.0]]));
+        let inverse = transform.invert().unwrap();
+        assert!(transform.multiply(&inverse).is_identity());
+        let point = transform.apply(&Vector { x: 3.0, y: 4.0 });
+        let point = inverse.apply(&point);
+        assert!((point.x - 3.0).abs() < 1e-9 && (point.y - 4.0).abs() < 1e-9);
+        assert!(Transform([[1.0, 2.0, 0.0], [2.0, 4.0, 0.0]])
+            .invert()
+            .is_none());
+    }
+}
@@ -1,9 +1,13 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Serialize, Copy, Clone)]
+use super::Paint;
+
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[typeshare::typeshare]
 pub enum TextCase {
+    Original,
     Upper,
     Lower,
     Title,
@@ -11,15 +15,16 @@ pub enum TextCase {
     SmallCapsForced,
 }
 
-#[derive(Debug, Deserialize, Serialize, Copy, Clone)]
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[typeshare::typeshare]
 pub enum TextDecoration {
+    None,
     Strikethrough,
     Underline,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Copy, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[typeshare::typeshare]
 pub enum TextAutoResize {
@@ -29,8 +34,51 @@ pub enum TextAutoResize {
     Truncate,
 }
 
+/// Whether text that doesn't fit is truncated
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[typeshare::typeshare]
+pub enum TextTruncation {
+    Disabled,
+    /// Trailing text is replaced with "…"
+    Ending,
+}
+
+/// Horizontal text alignment
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[typeshare::typeshare]
+pub enum TextAlignHorizontal {
+    Left,
+    Right,
+    Center,
+    Justified,
+}
+
+/// Vertical text alignment
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[typeshare::typeshare]
+pub enum TextAlignVertical {
+    Top,
+    Center,
+    Bottom,
+}
+
+/// The unit of the line height value specified by the user
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
+#[typeshare::typeshare]
+pub enum LineHeightUnit {
+    #[serde(rename = "PIXELS")]
+    Pixels,
+    #[serde(rename = "FONT_SIZE_%")]
+    FontSizePercent,
+    #[serde(rename = "INTRINSIC_%")]
+    IntrinsicPercent,
+}
+
 /// Type of hyperlink
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Copy, Clone)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 #[typeshare::typeshare]
 pub enum HyperlinkType {
@@ -38,7 +86,7 @@ pub enum HyperlinkType {
     Node,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 #[typeshare::typeshare]
 pub struct Hyperlink {
@@ -52,14 +100,20 @@ pub struct Hyperlink {
 /// Metadata for character formatting
 ///
 /// [Figma documentation](https://www.figma.com/developers/api#typestyle-type)
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 #[typeshare::typeshare]
 pub struct TypeStyle {
     /// Font family of text (standard name)
     pub font_family: String,
+    /// PostScript font name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_post_script_name: Option<String>,
     /// Space between paragraphs in px, 0 if not present
     pub paragraph_spacing: Option<f64>,
+    /// Paragraph indentation in px, 0 if not present
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paragraph_indent: Option<f64>,
     /// Whether or not text is italicized
     #[serde(skip_serializing_if = "Option::is_none")]
     pub italic: Option<bool>,
@@ -75,8 +129,131 @@ pub struct TypeStyle {
     pub text_decoration: Option<TextDecoration>,
     /// Dimensions along which text will auto resize, default is that the text does not auto-resize
     pub text_auto_resize: Option<TextAutoResize>,
+    /// Whether text that doesn't fit its bounds is truncated, default is disabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_truncation: Option<TextTruncation>,
+    /// The number of lines shown before truncating the text, if truncation is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_lines: Option<u32>,
+    /// Horizontal text alignment as string enum
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_align_horizontal: Option<TextAlignHorizontal>,
+    /// Vertical text alignment as string enum
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_align_vertical: Option<TextAlignVertical>,
+    /// Space between characters in px
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub letter_spacing: Option<f64>,
+    /// A map of OpenType feature flags to 1 or 0, 1 if it is enabled and 0 if it is disabled. Note that some flags aren't reflected here. For example, SMCP (small caps) is still represented by the textCase field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[typeshare(serialized_as = "Option<std::collections::HashMap<String, f64>>")]
+    pub opentype_flags: Option<IndexMap<String, f64>>,
     /// Link to a URL or frame
     pub hyperlink: Option<Hyperlink>,
     /// Line height in px
     pub line_height_px: f64,
+    /// Line height as a percentage of normal line height. This is deprecated; in a future version of the API only lineHeightPx and lineHeightPercentFontSize will be returned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_height_percent: Option<f64>,
+    /// Line height as a percentage of the font size. Only returned when lineHeightPercent is not 100
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_height_percent_font_size: Option<f64>,
+    /// The unit of the line height value specified by the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_height_unit: Option<LineHeightUnit>,
+}
+
+/// Character formatting that overrides part of a text node's `TypeStyle`. Only the overridden fields are present.
+///
+/// [Figma documentation](https://www.figma.com/developers/api#text-props)
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[typeshare::typeshare]
+pub struct TypeStyleOverride {
+    /// Font family of text (standard name)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_family: Option<String>,
+    /// Whether or not text is italicized
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub italic: Option<bool>,
+    /// Numeric font weight
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_weight: Option<f64>,
+    /// Font size in px
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<f64>,
+    /// Text casing applied to the characters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_case: Option<TextCase>,
+    /// Text decoration applied to the characters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_decoration: Option<TextDecoration>,
+    /// Link to a URL or frame
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hyperlink: Option<Hyperlink>,
+    /// Space between characters in px
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub letter_spacing: Option<f64>,
+    /// A map of OpenType feature flags to 1 or 0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[typeshare(serialized_as = "Option<std::collections::HashMap<String, f64>>")]
+    pub opentype_flags: Option<IndexMap<String, f64>>,
+    /// Line height in px
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_height_px: Option<f64>,
+    /// Line height as a percentage of the font size
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_height_percent_font_size: Option<f64>,
+    /// The unit of the line height value specified by the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_height_unit: Option<LineHeightUnit>,
+    /// Paints applied to characters
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fills: Option<Vec<Paint>>,
+    /// ID of the fill style the paints come from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inherit_fill_style_id: Option<String>,
+    /// ID of the text style the font properties come from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inherit_text_style_id: Option<String>,
+}
+
+impl TypeStyle {
+    pub fn text_case(&self) -> TextCase {
+        self.text_case.unwrap_or(TextCase::Original)
+    }
+
+    pub fn text_decoration(&self) -> TextDecoration {
+        self.text_decoration.unwrap_or(TextDecoration::None)
+    }
+
+    /// Apply the fields present in an override to a copy of this style
+    pub fn with_override(&self, style_override: &TypeStyleOverride) -> TypeStyle {
+        TypeStyle {
+            font_family: style_override
+                .font_family
+                .clone()
+                .unwrap_or_else(|| self.font_family.clone()),
+            italic: style_override.italic.or(self.italic),
+            font_weight: style_override.font_weight.unwrap_or(self.font_weight),
+            font_size: style_override.font_size.unwrap_or(self.font_size),
+            text_case: style_override.text_case.or(self.text_case),
+            text_decoration: style_override.text_decoration.or(self.text_decoration),
+            hyperlink: style_override
+                .hyperlink
+                .clone()
+                .or_else(|| self.hyperlink.clone()),
+            letter_spacing: style_override.letter_spacing.or(self.letter_spacing),
+            opentype_flags: style_override
+                .opentype_flags
+                .clone()
+                .or_else(|| self.opentype_flags.clone()),
+            line_height_px: style_override.line_height_px.unwrap_or(self.line_height_px),
+            line_height_percent_font_size: style_override
+                .line_height_percent_font_size
+                .or(self.line_height_percent_font_size),
+            line_height_unit: style_override.line_height_unit.or(self.line_height_unit),
+            ..self.clone()
+        }
+    }
 }
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 /// [Figma documentation](https://www.figma.com/developers/api#vector-type)
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
 #[typeshare::typeshare]
 pub struct Vector {
     pub x: f64,
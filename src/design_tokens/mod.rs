@@ -118,10 +118,7 @@ fn token_style_transformer(
         }
 
         if style.style_type == style_type
-            && !style
-                .name
-                .trim_start()
-                .starts_with(|c| c == '.' || c == '_' || c == '*')
+            && !style.name.trim_start().starts_with(['.', '_', '*'])
             && !insert_by_name(
                 output,
                 &once(name).chain(style.name.split('/')).collect::<Vec<_>>(),
@@ -141,6 +138,46 @@ fn token_style_transformer(
     }
 }
 
+/// The widths in pixels of the breakpoint tokens, by their lower case names
+/// below `breakpoints`
+pub fn breakpoint_widths(file: &figma_schema::File) -> IndexMap<String, f64> {
+    fn collect(output: &MapOrJson, name: &str, widths: &mut IndexMap<String, f64>) {
+        match output {
+            MapOrJson::Map(map) => {
+                for (key, value) in map.iter() {
+                    let name = if name.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{name}/{key}")
+                    };
+                    collect(value, &name, widths);
+                }
+            }
+            MapOrJson::Json(json) => {
+                if let Some(width) = json.get("value").and_then(serde_json::Value::as_f64) {
+                    widths.insert(name.to_string(), width);
+                }
+            }
+        }
+    }
+
+    let mut output = MapOrJson::Map(IndexMap::new());
+    token_document_transformer(
+        file,
+        &mut output,
+        &["breakpoints"],
+        &mut std::io::sink(),
+        |node, _| breakpoint_tokens::as_breakpoint_token(node, file),
+    );
+    let mut widths = IndexMap::new();
+    if let MapOrJson::Map(map) = &output {
+        if let Some(breakpoints) = map.get("breakpoints") {
+            collect(breakpoints, "", &mut widths);
+        }
+    }
+    widths
+}
+
 pub fn main(
     file: &figma_schema::File,
     stdout: &mut impl Write,
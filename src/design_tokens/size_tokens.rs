@@ -17,9 +17,9 @@ struct SizeToken<'a> {
 pub fn as_size_token(node: &Node, file: &File) -> Option<serde_json::Value> {
     if !matches!(
         node.r#type,
-        figma_schema::NodeType::Component { .. }
-            | figma_schema::NodeType::Rectangle { .. }
-            | figma_schema::NodeType::Frame { .. }
+        figma_schema::NodeType::Component
+            | figma_schema::NodeType::Rectangle
+            | figma_schema::NodeType::Frame
     ) {
         return None;
     }
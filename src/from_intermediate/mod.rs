@@ -0,0 +1,44 @@
+use figma_html::{
+    intermediate_node::IntermediateTree, intermediate_node_to_html_writer, mutator::Pipeline,
+    OutputOptions,
+};
+
+use anyhow::{Context, Result};
+use std::io::{Read, Write};
+
+#[derive(Debug, clap::Args)]
+pub struct Options {
+    /// Simplify the tree with the mutators before writing it
+    #[arg(long)]
+    simplify: bool,
+    /// Use classes in a shared stylesheet instead of inline styles
+    #[arg(long)]
+    stylesheet: bool,
+    /// The language of the page's text, such as `en-GB`
+    #[arg(long)]
+    lang: Option<String>,
+}
+
+pub fn main(stdin: impl Read, stdout: &mut impl Write, options: &Options) -> Result<()> {
+    let mut tree: IntermediateTree<'static> = serde_json::from_reader(stdin)
+        .context("Failed to parse intermediate tree JSON from stdin")?;
+
+    if options.simplify {
+        Pipeline::default()
+            .run(&mut tree.node, &mut tree.css_variables, None)
+            .context("Failed to simplify the node")?;
+    }
+
+    intermediate_node_to_html_writer(
+        stdout,
+        &tree.node,
+        &tree.css_variables,
+        "",
+        &OutputOptions {
+            shared_stylesheet: options.stylesheet,
+            lang: options.lang.clone(),
+            ..OutputOptions::default()
+        },
+    )
+    .context("Failed to write HTML to stdout")
+}
@@ -1,12 +1,16 @@
 mod design_tokens;
+mod from_intermediate;
 mod to_html;
+mod to_html_batch;
+mod to_intermediate;
+mod to_jsx;
 mod typescript_props;
 
 use anyhow::{bail, Context, Result};
 use clap::{Parser, Subcommand};
 
 #[derive(Debug, Parser)]
-#[command(about = "Figma API tooling", long_about = Some("Figma API tooling. Requires a Figma file on stdin."))]
+#[command(about = "Figma API tooling", long_about = Some("Figma API tooling. Requires a Figma file on stdin, except for from-intermediate."))]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
@@ -22,6 +26,32 @@ enum Commands {
     ToHtml {
         /// node-id within the Figma file to build HTML from
         node_id: String,
+        #[command(flatten)]
+        options: to_html::Options,
+    },
+    #[command(name = "to-html-batch", about = "Generate HTML and CSS of many nodes into a directory", long_about = None)]
+    ToHtmlBatch {
+        #[command(flatten)]
+        options: to_html_batch::Options,
+    },
+    #[command(name = "to-jsx", about = "Generate a React component of a node", long_about = None)]
+    ToJsx {
+        /// node-id within the Figma file to build the component from
+        node_id: String,
+        #[command(flatten)]
+        options: to_jsx::Options,
+    },
+    #[command(name = "to-intermediate", about = "Write the intermediate tree of a node as JSON", long_about = None)]
+    ToIntermediate {
+        /// node-id within the Figma file to convert
+        node_id: String,
+        #[command(flatten)]
+        options: to_intermediate::Options,
+    },
+    #[command(name = "from-intermediate", about = "Generate HTML and CSS from an intermediate tree on stdin", long_about = None)]
+    FromIntermediate {
+        #[command(flatten)]
+        options: from_intermediate::Options,
     },
     #[command(about = "Echo the JSON back", long_about = None)]
     Echo,
@@ -30,6 +60,15 @@ enum Commands {
 fn main() -> Result<()> {
     let args = Cli::parse();
 
+    if let Commands::FromIntermediate { options } = &args.command {
+        return from_intermediate::main(
+            std::io::stdin().lock(),
+            &mut std::io::stdout().lock(),
+            options,
+        )
+        .context("Failed to generate HTML");
+    }
+
     let file_or_error: serde_json::Value = serde_json::from_reader(std::io::stdin())
         .context("Failed to parse Figma API JSON from stdin")?;
     if let (Some(err), Some(status)) = (
@@ -72,15 +111,46 @@ fn main() -> Result<()> {
             )
             .context("Failed to generate TypeScript props")?;
         }
-        Commands::ToHtml { node_id } => {
+        Commands::ToHtml { node_id, options } => {
             to_html::main(
                 &file,
                 &mut std::io::stdout().lock(),
                 &mut std::io::stderr().lock(),
                 &node_id.replace("%3A", ":").replace('-', ":"),
+                options,
+            )
+            .context("Failed to generate HTML")?;
+        }
+        Commands::ToHtmlBatch { options } => {
+            to_html_batch::main(
+                &file,
+                &mut std::io::stdout().lock(),
+                &mut std::io::stderr().lock(),
+                options,
             )
             .context("Failed to generate HTML")?;
         }
+        Commands::ToJsx { node_id, options } => {
+            to_jsx::main(
+                &file,
+                &mut std::io::stdout().lock(),
+                &mut std::io::stderr().lock(),
+                &node_id.replace("%3A", ":").replace('-', ":"),
+                options,
+            )
+            .context("Failed to generate JSX")?;
+        }
+        Commands::ToIntermediate { node_id, options } => {
+            to_intermediate::main(
+                &file,
+                &mut std::io::stdout().lock(),
+                &mut std::io::stderr().lock(),
+                &node_id.replace("%3A", ":").replace('-', ":"),
+                options,
+            )
+            .context("Failed to generate the intermediate tree")?;
+        }
+        Commands::FromIntermediate { .. } => unreachable!("Handled before reading the file"),
         Commands::Echo => {
             serde_json::to_writer_pretty(std::io::stdout().lock(), &file)
                 .context("Failed to echo JSON")?;
@@ -1,30 +1,627 @@
 use figma_html::{
-    figma_node_to_intermediate_node, file_collect_css_variables, find_figma_node_by_id,
-    intermediate_node_to_html_writer, mutator,
+    annotate_accessibility, contrast_warnings, figma_node_collect_fonts,
+    figma_node_collect_image_refs, figma_node_to_intermediate_node, file_collect_css_variables,
+    find_figma_node_by_id,
+    intermediate_node::{unmatched_variant_elements, CSSVariablesMap, IntermediateNode, Variant},
+    intermediate_node_to_html_writer,
+    mutator::Pipeline,
+    use_relative_units, variants_to_html_writer, Breakpoint, ConversionOptions, ElementRules,
+    FigmaFont, FontMapping, NodeLink, OutputOptions, RelativeUnits, VariantState,
 };
+use figma_schema::NodeType;
 
-use anyhow::{Context, Result};
-use std::io::Write;
+use crate::design_tokens::breakpoint_widths;
+use anyhow::{bail, Context, Result};
+use indexmap::{IndexMap, IndexSet};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
-pub fn main(
+/// Options for converting a node into an intermediate tree and simplifying it,
+/// shared by the commands that convert nodes
+#[derive(Debug, Clone, clap::Args)]
+pub struct ConvertOptions {
+    /// Prepended to image refs to make the URLs of image fills
+    #[arg(long, default_value = "images/")]
+    image_url_prefix: String,
+    /// Write a JSON manifest of the image fills used by the node to this file
+    #[arg(long)]
+    image_manifest: Option<PathBuf>,
+    /// Name fonts in CSS, and load them with `@font-face` rules, using the
+    /// mapping in this JSON file
+    #[arg(long, value_name = "FILE", value_parser = parse_font_mapping)]
+    fonts: Option<FontMapping>,
+    /// Write a JSON report of the fonts used by the node to this file
+    #[arg(long, value_name = "FILE")]
+    font_report: Option<PathBuf>,
+    /// Choose semantic elements, such as `button` and `h1`, instead of `div`s
+    /// using the built in rules
+    #[arg(long)]
+    semantic_elements: bool,
+    /// Choose semantic elements using the rules in this JSON file
+    #[arg(long, value_name = "FILE")]
+    element_rules: Option<PathBuf>,
+    /// Hide decorative vectors from screen readers, label icon links and
+    /// buttons, and warn about text without enough contrast
+    #[arg(long)]
+    accessibility: bool,
+    /// Size padding, gaps and text in `rem`, and letter spacing in `em`, for
+    /// this root font size in pixels
+    #[arg(long, value_name = "ROOT_FONT_SIZE", num_args = 0..=1, default_missing_value = "16")]
+    rem: Option<f64>,
+    /// Size widths and heights in `rem` too
+    #[arg(long, requires = "rem")]
+    rem_sizes: bool,
+    /// Simplify with only these mutators, in this order
+    #[arg(long, value_delimiter = ',')]
+    mutators: Option<Vec<String>>,
+    /// Don't simplify with this mutator
+    #[arg(long = "disable-mutator", value_name = "MUTATOR")]
+    disable_mutators: Vec<String>,
+    /// Write which nodes each mutator changes to stderr
+    #[arg(long)]
+    trace_mutators: bool,
+    /// Where other nodes are exported, so that links to them can be kept
+    #[arg(skip)]
+    pub node_links: HashMap<String, NodeLink>,
+}
+
+#[derive(Debug, Clone, clap::Args)]
+pub struct Options {
+    /// Use classes in a shared stylesheet instead of inline styles
+    #[arg(long)]
+    stylesheet: bool,
+    /// Render a component set as one component whose styles switch on data
+    /// attributes of its variant properties
+    #[arg(long)]
+    pub variants: bool,
+    /// A variant property that is an interaction state, styled with a selector
+    /// rather than a data attribute, as `Property=Value=selector`. For example
+    /// `Hover=True=:hover`. Replaces the default hover, focus and disabled
+    /// states.
+    #[arg(long = "state", value_name = "STATE", value_parser = parse_variant_state)]
+    states: Vec<VariantState>,
+    /// Don't treat any variant properties as interaction states
+    #[arg(long, conflicts_with = "states")]
+    no_states: bool,
+    /// A variant property value for wider viewports, styled with a
+    /// `min-width` media query rather than a data attribute, as
+    /// `Property=Value=width`. The width is in pixels or the name of a
+    /// breakpoint token. For example `Viewport=Desktop=641` or
+    /// `Viewport=Desktop=md`.
+    #[arg(
+        long = "breakpoint",
+        value_name = "BREAKPOINT",
+        value_parser = parse_breakpoint,
+        requires = "variants"
+    )]
+    breakpoints: Vec<BreakpointOption>,
+    /// Style the values of this variant property, such as `Viewport`, with
+    /// media queries using the widths of the breakpoint tokens of the same
+    /// names
+    #[arg(long, value_name = "PROPERTY", requires = "variants")]
+    viewport_property: Option<String>,
+    /// The language of the page's text, such as `en-GB`
+    #[arg(long)]
+    lang: Option<String>,
+    #[command(flatten)]
+    pub convert_options: ConvertOptions,
+}
+
+/// Read the font mapping once, rather than for every node converted
+fn parse_font_mapping(path: &str) -> Result<FontMapping, String> {
+    let fonts_file = std::fs::File::open(path)
+        .map_err(|err| format!("Failed to open font mapping {path}: {err}"))?;
+    serde_json::from_reader(std::io::BufReader::new(fonts_file))
+        .map_err(|err| format!("Failed to parse font mapping {path}: {err}"))
+}
+
+fn parse_variant_state(value: &str) -> Result<VariantState, String> {
+    match value.splitn(3, '=').collect::<Vec<_>>()[..] {
+        [property, value, selector] => Ok(VariantState::new(property, value, selector)),
+        _ => Err("expected Property=Value=selector".to_string()),
+    }
+}
+
+/// A breakpoint as given on the command line, with its width in pixels or the
+/// name of a breakpoint token
+#[derive(Debug, Clone)]
+struct BreakpointOption {
+    property: String,
+    value: String,
+    width: String,
+}
+
+fn parse_breakpoint(value: &str) -> Result<BreakpointOption, String> {
+    match value.splitn(3, '=').collect::<Vec<_>>()[..] {
+        [property, value, width] => Ok(BreakpointOption {
+            property: property.to_string(),
+            value: value.to_string(),
+            width: width.to_string(),
+        }),
+        _ => Err("expected Property=Value=width".to_string()),
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ImageManifest<'a> {
+    images: IndexMap<&'a str, ImageManifestEntry<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ImageManifestEntry<'a> {
+    url: String,
+    node_ids: Vec<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+struct FontReport<'a> {
+    fonts: Vec<FontReportEntry<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FontReportEntry<'a> {
+    #[serde(flatten)]
+    font: &'a FigmaFont,
+    font_family: String,
+    font_weight: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    src: Option<&'a [String]>,
+    node_ids: &'a [&'a str],
+}
+
+impl Options {
+    pub fn output_options(&self) -> OutputOptions {
+        let default = OutputOptions::default();
+        OutputOptions {
+            shared_stylesheet: self.stylesheet,
+            variant_states: if self.no_states {
+                vec![]
+            } else if self.states.is_empty() {
+                default.variant_states
+            } else {
+                self.states.clone()
+            },
+            // These depend on the component set, so are found by
+            // `convert_variants`
+            breakpoints: vec![],
+            lang: self.lang.clone(),
+        }
+    }
+}
+
+/// The mutators to simplify nodes with
+fn pipeline(options: &ConvertOptions) -> Result<Pipeline> {
+    let mut pipeline = Pipeline::default();
+    if let Some(names) = options.mutators.as_ref() {
+        pipeline.select(names)?;
+    }
+    for name in options.disable_mutators.iter() {
+        if !pipeline.remove(name) {
+            bail!(
+                "Unknown mutator {name}. The mutators are {}",
+                Pipeline::default().names().collect::<Vec<_>>().join(", ")
+            );
+        }
+    }
+    Ok(pipeline)
+}
+
+fn simplify(
+    node: &mut IntermediateNode,
+    css_variables: &mut CSSVariablesMap,
+    pipeline: &Pipeline,
+    options: &ConvertOptions,
+    stderr: &mut impl Write,
+) -> Result<()> {
+    let trace: Option<&mut dyn Write> = if options.trace_mutators {
+        Some(stderr)
+    } else {
+        None
+    };
+    pipeline
+        .run(node, css_variables, trace)
+        .context("Failed to simplify the node")
+}
+
+fn write_image_manifest(
+    body: &figma_schema::Node,
+    conversion_options: &ConversionOptions,
+    path: &Path,
+) -> Result<()> {
+    let manifest = ImageManifest {
+        images: figma_node_collect_image_refs(body)
+            .into_iter()
+            .map(|(image_ref, node_ids)| {
+                (
+                    image_ref,
+                    ImageManifestEntry {
+                        url: conversion_options.image_url(image_ref),
+                        node_ids,
+                    },
+                )
+            })
+            .collect(),
+    };
+    let manifest_file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create image manifest {}", path.display()))?;
+    serde_json::to_writer_pretty(manifest_file, &manifest).context("Failed to write image manifest")
+}
+
+fn write_font_report(
+    body: &figma_schema::Node,
+    conversion_options: &ConversionOptions,
+    path: &Path,
+) -> Result<()> {
+    let fonts = figma_node_collect_fonts(body);
+    let mapping = &conversion_options.fonts;
+    let report = FontReport {
+        fonts: fonts
+            .iter()
+            .map(|(font, node_ids)| FontReportEntry {
+                font,
+                font_family: mapping.font_family(font),
+                font_weight: mapping.font_weight(font),
+                src: mapping.font_src(font),
+                node_ids,
+            })
+            .collect(),
+    };
+    let report_file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create font report {}", path.display()))?;
+    serde_json::to_writer_pretty(report_file, &report).context("Failed to write font report")
+}
+
+fn element_rules(options: &ConvertOptions) -> Result<Option<ElementRules>> {
+    match options.element_rules.as_ref() {
+        Some(path) => {
+            let rules_file = std::fs::File::open(path)
+                .with_context(|| format!("Failed to open element rules {}", path.display()))?;
+            serde_json::from_reader(std::io::BufReader::new(rules_file))
+                .with_context(|| format!("Failed to parse element rules {}", path.display()))
+                .map(Some)
+        }
+        None => Ok(options.semantic_elements.then(ElementRules::default)),
+    }
+}
+
+/// Find the node and write the image manifest and font report if requested
+fn prepare<'a>(
+    file: &'a figma_schema::File,
+    node_id: &str,
+    options: &ConvertOptions,
+) -> Result<(&'a figma_schema::Node, ConversionOptions)> {
+    let (body, _) = find_figma_node_by_id(file, node_id)
+        .with_context(|| format!("Failed to find node with id {}", node_id))?;
+
+    let conversion_options = ConversionOptions {
+        image_url_prefix: options.image_url_prefix.clone(),
+        node_links: options.node_links.clone(),
+        fonts: options.fonts.clone().unwrap_or_default(),
+    };
+
+    if let Some(path) = options.image_manifest.as_ref() {
+        write_image_manifest(body, &conversion_options, path)?;
+    }
+    if let Some(path) = options.font_report.as_ref() {
+        write_font_report(body, &conversion_options, path)?;
+    }
+
+    Ok((body, conversion_options))
+}
+
+/// Choose the elements and accessibility annotations of a simplified node, and
+/// convert its units
+fn annotate(
+    node: &mut IntermediateNode,
     file: &figma_schema::File,
-    stdout: &mut impl Write,
-    _stderr: &mut impl Write,
+    element_rules: Option<&ElementRules>,
+    css_variables: &mut CSSVariablesMap,
+    options: &ConvertOptions,
+    stderr: &mut impl Write,
+) -> Result<()> {
+    if let Some(element_rules) = element_rules {
+        element_rules.apply(node, file);
+    }
+    if options.accessibility {
+        annotate_accessibility(node);
+        for warning in contrast_warnings(node, css_variables) {
+            writeln!(stderr, "{warning}").context("Failed to write to stderr")?;
+        }
+    }
+    if let Some(root_font_size) = options.rem {
+        let units = RelativeUnits {
+            root_font_size,
+            sizes: options.rem_sizes,
+        };
+        use_relative_units(node, css_variables, &units);
+    }
+    Ok(())
+}
+
+/// Convert the node without simplifying it, writing the image manifest if
+/// requested
+pub fn convert_unsimplified<'a>(
+    file: &'a figma_schema::File,
     node_id: &str,
+    options: &ConvertOptions,
+) -> Result<(IntermediateNode<'a>, CSSVariablesMap<'a>)> {
+    let (body, conversion_options) = prepare(file, node_id, options)?;
+
+    let mut css_variables = file_collect_css_variables(file);
+
+    let node = figma_node_to_intermediate_node(body, &mut css_variables, &conversion_options);
+
+    Ok((node, css_variables))
+}
+
+/// Simplify a converted node, then choose its elements and accessibility
+/// annotations
+pub fn simplify_and_annotate(
+    node: &mut IntermediateNode,
+    file: &figma_schema::File,
+    css_variables: &mut CSSVariablesMap,
+    options: &ConvertOptions,
+    stderr: &mut impl Write,
 ) -> Result<()> {
+    let element_rules = element_rules(options)?;
+    let pipeline = pipeline(options)?;
+
+    simplify(node, css_variables, &pipeline, options, stderr)?;
+    annotate(
+        node,
+        file,
+        element_rules.as_ref(),
+        css_variables,
+        options,
+        stderr,
+    )
+}
+
+/// Convert and simplify the node, writing the image manifest if requested
+pub fn convert<'a>(
+    file: &'a figma_schema::File,
+    node_id: &str,
+    options: &ConvertOptions,
+    stderr: &mut impl Write,
+) -> Result<(IntermediateNode<'a>, CSSVariablesMap<'a>)> {
+    let (mut node, mut css_variables) = convert_unsimplified(file, node_id, options)?;
+    simplify_and_annotate(&mut node, file, &mut css_variables, options, stderr)?;
+
+    Ok((node, css_variables))
+}
+
+/**
+The breakpoints of a component set's variant properties.
+
+The narrowest viewport of a property is the base, without a media query, so it
+doesn't get a breakpoint.
+ */
+fn breakpoints(
+    file: &figma_schema::File,
+    body: &figma_schema::Node,
+    options: &Options,
+) -> Result<Vec<Breakpoint>> {
+    if options.breakpoints.is_empty() && options.viewport_property.is_none() {
+        return Ok(vec![]);
+    }
+    let tokens = breakpoint_widths(file);
+    let token_names = || tokens.keys().cloned().collect::<Vec<_>>().join(", ");
+    let mut breakpoints = options
+        .breakpoints
+        .iter()
+        .map(|b| {
+            let min_width = match b.width.parse::<f64>() {
+                Ok(width) => width,
+                Err(_) => *tokens.get(&b.width.to_lowercase()).with_context(|| {
+                    format!(
+                        "Unknown breakpoint token {}. The breakpoint tokens are {}",
+                        b.width,
+                        token_names()
+                    )
+                })?,
+            };
+            Ok(Breakpoint::new(&b.property, &b.value, min_width))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let values = body
+        .enabled_children()
+        .flat_map(|child| child.variant_properties())
+        .collect::<IndexSet<_>>();
+    if let Some(property) = options.viewport_property.as_deref() {
+        let viewports = values.iter().filter(|(key, _)| *key == property);
+        for &(key, value) in viewports {
+            if let Some(width) = tokens.get(&value.to_lowercase()) {
+                if !breakpoints
+                    .iter()
+                    .any(|b| b.property == key && b.value == value)
+                {
+                    breakpoints.push(Breakpoint::new(key, value, *width));
+                }
+            }
+        }
+        if !breakpoints.iter().any(|b| b.property == property) {
+            bail!(
+                "No breakpoint tokens are named after the values of {property}. The breakpoint tokens are {}",
+                token_names()
+            );
+        }
+    }
+    let properties = breakpoints
+        .iter()
+        .map(|b| b.property.clone())
+        .collect::<IndexSet<_>>();
+    for property in properties {
+        let without_breakpoints = values
+            .iter()
+            .filter(|&&(key, value)| {
+                key == property
+                    && !breakpoints
+                        .iter()
+                        .any(|b| b.property == key && b.value == value)
+            })
+            .map(|(_, value)| *value)
+            .collect::<Vec<_>>();
+        if without_breakpoints.len() > 1 {
+            bail!(
+                "Only the narrowest viewport can be without a breakpoint, but {property} has {}",
+                without_breakpoints.join(", ")
+            );
+        }
+        // Without a value for narrower viewports, the narrowest breakpoint is
+        // the base
+        if without_breakpoints.is_empty() {
+            if let Some(narrowest) = breakpoints
+                .iter()
+                .enumerate()
+                .filter(|(_, b)| b.property == property)
+                .min_by(|(_, a), (_, b)| a.min_width.total_cmp(&b.min_width))
+                .map(|(i, _)| i)
+            {
+                breakpoints.remove(narrowest);
+            }
+        }
+    }
+    Ok(breakpoints)
+}
+
+/// Convert and simplify each variant of a component set, with the breakpoints
+/// of its viewport properties
+pub fn convert_variants<'a>(
+    file: &'a figma_schema::File,
+    node_id: &str,
+    options: &Options,
+    stderr: &mut impl Write,
+) -> Result<(
+    &'a figma_schema::Node,
+    Vec<Variant<'a>>,
+    CSSVariablesMap<'a>,
+    Vec<Breakpoint>,
+)> {
+    let (body, conversion_options) = prepare(file, node_id, &options.convert_options)?;
+    if body.r#type != NodeType::ComponentSet {
+        bail!("Node {node_id} is a {:?}, not a component set", body.r#type);
+    }
+    let element_rules = element_rules(&options.convert_options)?;
+    let pipeline = pipeline(&options.convert_options)?;
+    let breakpoints = breakpoints(file, body, options)?;
+
+    let mut css_variables = file_collect_css_variables(file);
+
+    let mut variants = body
+        .enabled_children()
+        .map(|child| {
+            let mut node =
+                figma_node_to_intermediate_node(child, &mut css_variables, &conversion_options);
+            simplify(
+                &mut node,
+                &mut css_variables,
+                &pipeline,
+                &options.convert_options,
+                stderr,
+            )?;
+            annotate(
+                &mut node,
+                file,
+                element_rules.as_ref(),
+                &mut css_variables,
+                &options.convert_options,
+                stderr,
+            )?;
+            Ok(Variant {
+                properties: child.variant_properties().collect(),
+                node,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    // The first variant is the base, so it mustn't be an interaction state
+    // and must be for the narrowest viewport
+    let states = options.output_options().variant_states;
+    variants.sort_by(|a, b| {
+        a.is_state(&states).cmp(&b.is_state(&states)).then(
+            a.min_width(&breakpoints)
+                .total_cmp(&b.min_width(&breakpoints)),
+        )
+    });
+
+    Ok((body, variants, css_variables, breakpoints))
+}
+
+/// `@font-face` rules for the fonts the node uses that the font mapping loads,
+/// formatted to be nested `level` deep
+pub fn font_face_rules(
+    file: &figma_schema::File,
+    node_id: &str,
+    options: &ConvertOptions,
+    level: u16,
+) -> Result<String> {
     let (body, _) = find_figma_node_by_id(file, node_id)
         .with_context(|| format!("Failed to find node with id {}", node_id))?;
+    let Some(font_mapping) = options.fonts.as_ref() else {
+        return Ok(String::new());
+    };
+    Ok(font_mapping.font_face_rules(figma_node_collect_fonts(body).keys(), level))
+}
 
-    let mut css_variables = file_collect_css_variables(file);
+pub fn main(
+    file: &figma_schema::File,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+    node_id: &str,
+    options: &Options,
+) -> Result<()> {
+    write_html(file, stdout, stderr, node_id, options, options.variants)
+}
+
+/// Write the node's HTML, as one component switching between its variants if
+/// `variants` is set
+pub fn write_html(
+    file: &figma_schema::File,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+    node_id: &str,
+    options: &Options,
+    variants: bool,
+) -> Result<()> {
+    if variants {
+        let (body, variants, css_variables, breakpoints) =
+            convert_variants(file, node_id, options, stderr)?;
+        let font_face_rules = font_face_rules(file, node_id, &options.convert_options, 3)?;
+        for id in unmatched_variant_elements(&variants) {
+            writeln!(
+                stderr,
+                "Node {id} doesn't match an element of the first variant, so its styles won't switch with the variant"
+            )
+            .context("Failed to write to stderr")?;
+        }
+        return variants_to_html_writer(
+            stdout,
+            &body.name,
+            &variants,
+            &css_variables,
+            &font_face_rules,
+            &OutputOptions {
+                breakpoints,
+                ..options.output_options()
+            },
+        )
+        .context("Failed to write HTML to stdout");
+    }
 
-    let mut node = figma_node_to_intermediate_node(body, &mut css_variables);
-    while mutator::combine_parent_child(&mut node, &mut css_variables)
-        || mutator::collapse_to_padding(&mut node, &mut css_variables)
-        || mutator::collapse_to_gap(&mut node, &mut css_variables)
-        || mutator::drop_empty_absolute_frames(&mut node, &mut css_variables)
-        || mutator::elevate_frame_appearance_properties(&mut node, &mut css_variables)
-    {}
+    let (node, css_variables) = convert(file, node_id, &options.convert_options, stderr)?;
+    let font_face_rules = font_face_rules(file, node_id, &options.convert_options, 3)?;
 
-    intermediate_node_to_html_writer(stdout, &node, &css_variables, "")
-        .context("Failed to write HTML to stdout")
+    intermediate_node_to_html_writer(
+        stdout,
+        &node,
+        &css_variables,
+        &font_face_rules,
+        &options.output_options(),
+    )
+    .context("Failed to write HTML to stdout")
 }
@@ -0,0 +1,428 @@
+use figma_html::{find_figma_node_by_id, glob_match, NodeLink};
+use figma_schema::{Node, NodeType};
+
+use anyhow::{bail, Context, Result};
+use html_escape::{encode_double_quoted_attribute, encode_text};
+use std::{
+    collections::{HashMap, HashSet},
+    io::Write,
+    path::PathBuf,
+};
+
+use crate::to_html;
+
+#[derive(Debug, clap::Args)]
+#[group(id = "BatchOptions")]
+pub struct Options {
+    /// node-ids within the Figma file to build HTML from
+    node_ids: Vec<String>,
+    /// Build HTML from each top level frame of the pages matching this name.
+    /// `*` matches any characters.
+    #[arg(long = "page", value_name = "GLOB")]
+    pages: Vec<String>,
+    /// Build HTML from each top level frame matching this name. `*` matches
+    /// any characters.
+    #[arg(long = "frame", value_name = "GLOB")]
+    frames: Vec<String>,
+    /// Build HTML from every component and component set. Components within
+    /// sets are part of their set's HTML.
+    #[arg(long)]
+    all_components: bool,
+    /// Directory to write the HTML files and index.html to
+    #[arg(long)]
+    output_dir: PathBuf,
+    /// Link the pages to each other where the nodes have hyperlinks or
+    /// prototype interactions going to other selected nodes, or to nodes
+    /// within them, making a clickable prototype
+    #[arg(long)]
+    prototype: bool,
+    #[command(flatten)]
+    html_options: to_html::Options,
+}
+
+/// The nodes to build HTML from, without duplicates
+fn select_nodes<'a>(file: &'a figma_schema::File, options: &Options) -> Result<Vec<&'a Node>> {
+    let mut nodes = vec![];
+    for node_id in options.node_ids.iter() {
+        let node_id = node_id.replace("%3A", ":").replace('-', ":");
+        let (node, _) = find_figma_node_by_id(file, &node_id)
+            .with_context(|| format!("Failed to find node with id {}", node_id))?;
+        nodes.push(node);
+    }
+    for page in file.document.children() {
+        let page_matches = options.pages.iter().any(|g| glob_match(g, &page.name));
+        for frame in page.enabled_children() {
+            if page_matches || options.frames.iter().any(|g| glob_match(g, &frame.name)) {
+                nodes.push(frame);
+            }
+        }
+    }
+    if options.all_components {
+        let mut stack = vec![&file.document];
+        while let Some(node) = stack.pop() {
+            match node.r#type {
+                NodeType::Component | NodeType::ComponentSet => nodes.push(node),
+                _ => stack.extend(node.children().iter().rev()),
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    nodes.retain(|node| seen.insert(node.id.as_str()));
+    if nodes.is_empty() {
+        bail!("No nodes selected. Give node ids, --page, --frame or --all-components");
+    }
+    Ok(nodes)
+}
+
+/// A file name from the node's name, lower case with dashes
+fn file_stem(name: &str) -> String {
+    let mut stem = String::new();
+    for c in name.chars() {
+        if c.is_alphanumeric() {
+            stem.extend(c.to_lowercase());
+        } else if !stem.is_empty() && !stem.ends_with('-') {
+            stem.push('-');
+        }
+    }
+    while stem.ends_with('-') {
+        stem.pop();
+    }
+    if stem.is_empty() {
+        stem.push_str("node");
+    }
+    stem
+}
+
+/// The nodes with unique HTML file names, none of which is the index's
+fn file_names(nodes: Vec<&Node>) -> Vec<(&Node, String)> {
+    let mut used_stems = HashSet::new();
+    nodes
+        .into_iter()
+        .map(|node| {
+            let stem = file_stem(&node.name);
+            let mut unique_stem = stem.clone();
+            let mut suffix = 1;
+            while !used_stems.insert(unique_stem.clone()) || unique_stem == "index" {
+                suffix += 1;
+                unique_stem = format!("{stem}-{suffix}");
+            }
+            (node, format!("{unique_stem}.html"))
+        })
+        .collect()
+}
+
+/// The nodes that the node's hyperlinks and prototype interactions go to
+fn link_destinations<'a>(node: &'a Node, destinations: &mut HashSet<&'a str>) {
+    let hyperlinks = node
+        .style
+        .iter()
+        .filter_map(|style| style.hyperlink.as_ref())
+        .chain(
+            node.style_override_table
+                .iter()
+                .flat_map(|table| table.values())
+                .filter_map(|style| style.hyperlink.as_ref()),
+        );
+    destinations.extend(hyperlinks.filter_map(|hyperlink| hyperlink.node_id.as_deref()));
+    destinations.extend(node.transition_node_id.as_deref());
+    destinations.extend(
+        node.reactions()
+            .iter()
+            .flat_map(|reaction| reaction.actions())
+            .filter_map(|action| action.destination_id.as_deref()),
+    );
+    for child in node.enabled_children() {
+        link_destinations(child, destinations);
+    }
+}
+
+/**
+Where the pages' nodes are, for the nodes that links go to.
+
+Links to a selected node go to its page, and links to a node within one go to
+an anchor on its page. Components aren't destinations, as interactions going
+to them change the variant of an instance rather than the page.
+ */
+fn node_links(pages: &[(&Node, String)]) -> HashMap<String, NodeLink> {
+    let mut destinations = HashSet::new();
+    for (node, _) in pages {
+        link_destinations(node, &mut destinations);
+    }
+    let is_component =
+        |node: &Node| matches!(node.r#type, NodeType::Component | NodeType::ComponentSet);
+
+    let mut links = HashMap::new();
+    for (node, file_name) in pages.iter().filter(|(node, _)| !is_component(node)) {
+        links.insert(
+            node.id.clone(),
+            NodeLink {
+                page: file_name.clone(),
+                anchor: None,
+            },
+        );
+    }
+    for (node, file_name) in pages {
+        let mut stack = node.enabled_children().collect::<Vec<_>>();
+        while let Some(descendant) = stack.pop() {
+            if destinations.contains(descendant.id.as_str()) && !is_component(descendant) {
+                links
+                    .entry(descendant.id.clone())
+                    .or_insert_with(|| NodeLink {
+                        page: file_name.clone(),
+                        anchor: Some(format!("node-{}", file_stem(&descendant.id))),
+                    });
+            }
+            stack.extend(descendant.enabled_children());
+        }
+    }
+    links
+}
+
+fn write_index(
+    index: &mut impl Write,
+    title: &str,
+    pages: &[(&Node, String)],
+) -> std::io::Result<()> {
+    writeln!(
+        index,
+        r#"<!DOCTYPE html>
+<html>
+  <head>
+    <meta charset="utf-8" />
+    <title>{}</title>
+  </head>
+  <body>
+    <h1>{}</h1>
+    <ul>"#,
+        encode_text(title),
+        encode_text(title)
+    )?;
+    for (node, file_name) in pages {
+        writeln!(
+            index,
+            r#"      <li><a href="{}">{}</a> <code>{}</code></li>"#,
+            encode_double_quoted_attribute(file_name),
+            encode_text(&node.name),
+            encode_text(&node.id)
+        )?;
+    }
+    writeln!(
+        index,
+        r#"    </ul>
+  </body>
+</html>"#
+    )
+}
+
+pub fn main(
+    file: &figma_schema::File,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+    options: &Options,
+) -> Result<()> {
+    let nodes = select_nodes(file, options)?;
+    std::fs::create_dir_all(&options.output_dir).with_context(|| {
+        format!(
+            "Failed to create output directory {}",
+            options.output_dir.display()
+        )
+    })?;
+
+    let pages = file_names(nodes);
+    let mut html_options = options.html_options.clone();
+    if options.prototype {
+        html_options.convert_options.node_links = node_links(&pages);
+    }
+    for (node, file_name) in pages.iter() {
+        let path = options.output_dir.join(file_name);
+
+        let mut html = std::fs::File::create(&path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        to_html::write_html(
+            file,
+            &mut html,
+            stderr,
+            &node.id,
+            &html_options,
+            html_options.variants && node.r#type == NodeType::ComponentSet,
+        )
+        .with_context(|| format!("Failed to generate HTML of node {}", node.id))?;
+        writeln!(stdout, "{}", path.display()).context("Failed to write to stdout")?;
+    }
+
+    let index_path = options.output_dir.join("index.html");
+    let mut index = std::fs::File::create(&index_path)
+        .with_context(|| format!("Failed to create {}", index_path.display()))?;
+    write_index(&mut index, &file.name, &pages)
+        .with_context(|| format!("Failed to write {}", index_path.display()))?;
+    writeln!(stdout, "{}", index_path.display()).context("Failed to write to stdout")
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        options: Options,
+    }
+
+    fn options(args: &[&str]) -> Options {
+        Cli::parse_from(["to-html-batch", "--output-dir", "html"].iter().chain(args)).options
+    }
+
+    fn file(pages: serde_json::Value) -> figma_schema::File {
+        serde_json::from_value(serde_json::json!({
+            "name": "Test",
+            "document": { "id": "0:0", "name": "Document", "type": "DOCUMENT", "children": pages },
+            "components": {},
+            "styles": {},
+            "schemaVersion": 0,
+            "version": "1"
+        }))
+        .unwrap()
+    }
+
+    fn frame(
+        id: &str,
+        name: &str,
+        node_type: &str,
+        children: serde_json::Value,
+    ) -> serde_json::Value {
+        serde_json::json!({ "id": id, "name": name, "type": node_type, "children": children })
+    }
+
+    fn ids(nodes: &[&Node]) -> Vec<String> {
+        nodes.iter().map(|node| node.id.clone()).collect()
+    }
+
+    #[test]
+    fn nodes_are_selected_once() {
+        let file = file(serde_json::json!([
+            frame(
+                "1:0",
+                "Pages",
+                "CANVAS",
+                serde_json::json!([
+                    frame("1:1", "Home", "FRAME", serde_json::json!([])),
+                    frame("1:2", "Cookie banner", "FRAME", serde_json::json!([])),
+                ])
+            ),
+            frame(
+                "2:0",
+                "Components",
+                "CANVAS",
+                serde_json::json!([
+                    frame(
+                        "2:1",
+                        "Buttons",
+                        "COMPONENT_SET",
+                        serde_json::json!([frame(
+                            "2:2",
+                            "Type=Primary",
+                            "COMPONENT",
+                            serde_json::json!([])
+                        )])
+                    ),
+                    frame("2:3", "Cookie icon", "COMPONENT", serde_json::json!([])),
+                ])
+            ),
+        ]));
+        let nodes = select_nodes(
+            &file,
+            &options(&[
+                "1-2",
+                "--page",
+                "Pages",
+                "--frame",
+                "Cookie*",
+                "--all-components",
+            ]),
+        )
+        .unwrap();
+        assert_eq!(ids(&nodes), ["1:2", "1:1", "2:3", "2:1"]);
+
+        assert!(select_nodes(&file, &options(&["--frame", "Nothing"])).is_err());
+        assert!(select_nodes(&file, &options(&["9:9"])).is_err());
+    }
+
+    #[test]
+    fn file_names_are_unique() {
+        assert_eq!(file_stem("Cookie banner / Dark"), "cookie-banner-dark");
+        assert_eq!(file_stem("  Ünïcode!  "), "ünïcode");
+        assert_eq!(file_stem("***"), "node");
+
+        let file = file(serde_json::json!([frame(
+            "1:0",
+            "Page",
+            "CANVAS",
+            serde_json::json!([
+                frame("1:1", "Home", "FRAME", serde_json::json!([])),
+                frame("1:2", "home", "FRAME", serde_json::json!([])),
+                frame("1:3", "Index", "FRAME", serde_json::json!([])),
+                frame("1:4", "Home 2", "FRAME", serde_json::json!([])),
+            ])
+        )]));
+        let names = file_names(file.document.children()[0].children().iter().collect())
+            .into_iter()
+            .map(|(_, name)| name)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            ["home.html", "home-2.html", "index-2.html", "home-2-2.html"]
+        );
+    }
+
+    #[test]
+    fn links_go_to_pages_and_anchors_within_them() {
+        let go_to = |destination: &str| {
+            serde_json::json!([{
+                "trigger": { "type": "ON_CLICK" },
+                "actions": [{ "type": "NODE", "destinationId": destination, "navigation": "NAVIGATE" }]
+            }])
+        };
+        let mut button = frame("1:3", "Button", "FRAME", serde_json::json!([]));
+        button["reactions"] = go_to("1:6");
+        let mut icon = frame("1:4", "Icon", "FRAME", serde_json::json!([]));
+        icon["reactions"] = go_to("2:1");
+        let mut back = frame("1:7", "Back", "FRAME", serde_json::json!([]));
+        back["transitionNodeID"] = serde_json::json!("1:1");
+        let file = file(serde_json::json!([frame(
+            "1:0",
+            "Page",
+            "CANVAS",
+            serde_json::json!([
+                frame("1:1", "Home", "FRAME", serde_json::json!([button, icon])),
+                frame(
+                    "1:2",
+                    "About",
+                    "FRAME",
+                    serde_json::json!([
+                        frame("1:5", "Intro", "FRAME", serde_json::json!([])),
+                        frame("1:6", "Contact", "FRAME", serde_json::json!([back])),
+                    ])
+                ),
+                frame("2:1", "Toggle", "COMPONENT", serde_json::json!([])),
+            ])
+        )]));
+        let pages = file_names(file.document.children()[0].children().iter().collect());
+        let links = node_links(&pages);
+        let mut hrefs = links
+            .iter()
+            .map(|(id, link)| (id.as_str(), link.href()))
+            .collect::<Vec<_>>();
+        hrefs.sort();
+        assert_eq!(
+            hrefs,
+            [
+                ("1:1", "home.html".to_string()),
+                ("1:2", "about.html".to_string()),
+                ("1:6", "about.html#node-1-6".to_string()),
+            ]
+        );
+    }
+}
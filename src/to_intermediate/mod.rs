@@ -0,0 +1,53 @@
+use figma_html::intermediate_node::IntermediateTree;
+
+use anyhow::{Context, Result};
+use std::{io::Write, path::PathBuf};
+
+use crate::to_html;
+
+#[derive(Debug, clap::Args)]
+#[group(id = "IntermediateOptions")]
+pub struct Options {
+    /// Write the tree from before the mutators simplify it
+    #[arg(long)]
+    unsimplified: bool,
+    /// Also write the tree from before the mutators simplify it to this file
+    #[arg(long, value_name = "FILE", conflicts_with = "unsimplified")]
+    before: Option<PathBuf>,
+    #[command(flatten)]
+    convert_options: to_html::ConvertOptions,
+}
+
+pub fn main(
+    file: &figma_schema::File,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+    node_id: &str,
+    options: &Options,
+) -> Result<()> {
+    let (node, css_variables) =
+        to_html::convert_unsimplified(file, node_id, &options.convert_options)?;
+    let mut tree = IntermediateTree {
+        node,
+        css_variables,
+    };
+
+    if let Some(path) = options.before.as_ref() {
+        let before_file = std::fs::File::create(path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        serde_json::to_writer_pretty(before_file, &tree)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    if !options.unsimplified {
+        to_html::simplify_and_annotate(
+            &mut tree.node,
+            file,
+            &mut tree.css_variables,
+            &options.convert_options,
+            stderr,
+        )?;
+    }
+
+    serde_json::to_writer_pretty(stdout, &tree).context("Failed to write JSON to stdout")
+}
@@ -0,0 +1,47 @@
+use figma_html::{intermediate_node_to_jsx_writer, jsx_component_name, OutputOptions};
+
+use anyhow::{Context, Result};
+use std::io::Write;
+
+use crate::to_html;
+
+#[derive(Debug, clap::Args)]
+#[group(id = "JsxOptions")]
+pub struct Options {
+    /// Name of the React component. Defaults to the node's name.
+    #[arg(long)]
+    component_name: Option<String>,
+    /// Use classes in a shared stylesheet instead of inline styles
+    #[arg(long)]
+    stylesheet: bool,
+    #[command(flatten)]
+    convert_options: to_html::ConvertOptions,
+}
+
+pub fn main(
+    file: &figma_schema::File,
+    stdout: &mut impl Write,
+    stderr: &mut impl Write,
+    node_id: &str,
+    options: &Options,
+) -> Result<()> {
+    let (node, css_variables) = to_html::convert(file, node_id, &options.convert_options, stderr)?;
+    let font_face_rules = to_html::font_face_rules(file, node_id, &options.convert_options, 0)?;
+
+    let component_name = options.component_name.clone().unwrap_or_else(|| {
+        jsx_component_name(node.figma.as_ref().map(|f| f.name.as_ref()).unwrap_or(""))
+    });
+
+    intermediate_node_to_jsx_writer(
+        stdout,
+        &node,
+        &css_variables,
+        &component_name,
+        &font_face_rules,
+        &OutputOptions {
+            shared_stylesheet: options.stylesheet,
+            ..OutputOptions::default()
+        },
+    )
+    .context("Failed to write JSX to stdout")
+}
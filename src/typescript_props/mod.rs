@@ -220,10 +220,8 @@ pub fn main(
                 parent_nodes: parent_nodes.clone(),
             };
             for instance in node.children() {
-                for key_value in instance.name.split(", ") {
-                    if let Some((key, value)) = key_value.split_once('=') {
-                        interface.types.entry(key).or_default().insert(value);
-                    }
+                for (key, value) in instance.variant_properties() {
+                    interface.types.entry(key).or_default().insert(value);
                 }
             }
             if !insert_by_name(&mut transformed, &parent_nodes[1..], interface) {
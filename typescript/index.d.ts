@@ -2,6 +2,16 @@
  Generated by typeshare 1.5.0
 */
 
+/**
+ * A 2D affine transform, as the top two rows of a 3x3 matrix
+ * 
+ * A point `(x, y)` is transformed to
+ * `(m[0][0] * x + m[0][1] * y + m[0][2], m[1][0] * x + m[1][1] * y + m[1][2])`.
+ * 
+ * [Figma documentation](https://www.figma.com/developers/api#transform-type)
+ */
+export type Transform = [[number, number, number], [number, number, number]];
+
 /** [Figma documentation](https://www.figma.com/developers/api#color-type) */
 export interface Color {
 	r: number;
@@ -10,11 +20,32 @@ export interface Color {
 	a: number;
 }
 
+/**
+ * A position color pair representing a gradient stop
+ * 
+ * [Figma documentation](https://www.figma.com/developers/api#colorstop-type)
+ */
+export interface ColorStop {
+	/** Value between 0 and 1 representing position along gradient axis */
+	position: number;
+	/** Color attached to corresponding position */
+	color: Color;
+}
+
 /** [Figma documentation](https://www.figma.com/developers/api#component-type) */
 export interface Component {
 	key: string;
 	name: string;
 	description: string;
+	/** The id of the component set the component is a variant of */
+	componentSetId?: string;
+}
+
+/** [Figma documentation](https://www.figma.com/developers/api#component-set-type) */
+export interface ComponentSet {
+	key: string;
+	name: string;
+	description: string;
 }
 
 export enum EffectType {
@@ -44,8 +75,12 @@ export interface Effect {
 	color?: Color;
 	/** How far the shadow is projected in the x and y directions */
 	offset?: Vector;
+	/** Radius of the blur effect (applies to shadows as well) */
+	radius?: number;
 	/** How far the shadow spreads */
 	spread?: number;
+	/** Whether to show the shadow behind translucent or transparent pixels (applies only to drop shadows) */
+	showShadowBehindNode?: boolean;
 }
 
 /** Node type indicates what kind of node you are working with: for example, a FRAME node versus a RECTANGLE node. A node can have additional properties associated with it depending on its node type. */
@@ -87,6 +122,7 @@ export enum PaintType {
  * [Figma documentation](https://www.figma.com/developers/api#blendmode-type)
  */
 export enum BlendMode {
+	/** Children of a group blend with the layers below the group, as if it wasn't there */
 	PassThrough = "PASS_THROUGH",
 	Normal = "NORMAL",
 	Darken = "DARKEN",
@@ -108,6 +144,20 @@ export enum BlendMode {
 	Luminosity = "LUMINOSITY",
 }
 
+/** Image scaling mode */
+export enum ScaleMode {
+	/** Scale the image to cover the node, cropping the overflow */
+	Fill = "FILL",
+	/** Scale the image to fit within the node */
+	Fit = "FIT",
+	/** Repeat the image at its natural size multiplied by the scaling factor */
+	Tile = "TILE",
+	/** Position and crop the image with the image transform */
+	Stretch = "STRETCH",
+	/** Plugin API name for `STRETCH` */
+	Crop = "CROP",
+}
+
 /**
  * A solid color, gradient, or image texture that can be applied as fills or strokes
  * 
@@ -121,10 +171,20 @@ export interface Paint {
 	opacity?: number;
 	/** Solid color of the paint */
 	color?: Color;
-	/** How this node blends with nodes behind it in the scene */
-	blend_mode?: BlendMode;
+	/** How this paint blends with the paints below it */
+	blendMode?: BlendMode;
 	/** This field contains three vectors, each of which are a position in normalized object space (normalized object space is if the top left corner of the bounding box of the object is (0, 0) and the bottom right is (1,1)). The first position corresponds to the start of the gradient (value 0 for the purposes of calculating gradient stops), the second position is the end of the gradient (value 1), and the third handle position determines the width of the gradient. See image examples below: */
-	gradient_handle_positions?: [Vector, Vector, Vector];
+	gradientHandlePositions?: [Vector, Vector, Vector];
+	/** Positions of key points along the gradient axis with the colors anchored there. Colors along the gradient are interpolated smoothly between neighboring gradient stops. */
+	gradientStops?: ColorStop[];
+	/** Image scaling mode */
+	scaleMode?: ScaleMode;
+	/** Affine transform applied to the image, only present if scaleMode is `STRETCH` */
+	imageTransform?: Transform;
+	/** Amount image is scaled by in tiling, only present if scaleMode is `TILE` */
+	scalingFactor?: number;
+	/** A reference to an image embedded in this node. To download the image using this reference, use the `GET file images` endpoint to retrieve the mapping from image references to image URLs */
+	imageRef?: string;
 }
 
 /** Individual stroke weights */
@@ -148,6 +208,24 @@ export enum StrokeAlign {
 	Center = "CENTER",
 }
 
+/** Winding rule used to determine the inside of a path */
+export enum WindingRule {
+	Nonzero = "NONZERO",
+	Evenodd = "EVENODD",
+}
+
+/**
+ * A vector svg path
+ * 
+ * [Figma documentation](https://www.figma.com/developers/api#path-type)
+ */
+export interface Path {
+	/** A series of path commands that encodes how to draw the path */
+	path: string;
+	/** The winding rule for the path, either "EVENODD" or "NONZERO" */
+	windingRule: WindingRule;
+}
+
 /**
  * Animation easing curves
  * 
@@ -173,6 +251,92 @@ export enum EasingType {
 	CustomSpring = "CUSTOM_SPRING",
 }
 
+/** What a prototype interaction does */
+export enum ActionType {
+	/** Go back to the previous frame */
+	Back = "BACK",
+	/** Close the overlay */
+	Close = "CLOSE",
+	/** Open a URL */
+	Url = "URL",
+	/** Go to another node */
+	Node = "NODE",
+	UpdateMediaRuntime = "UPDATE_MEDIA_RUNTIME",
+	SetVariable = "SET_VARIABLE",
+	SetVariableMode = "SET_VARIABLE_MODE",
+	Conditional = "CONDITIONAL",
+}
+
+/** How a node action moves to its destination */
+export enum Navigation {
+	/** Replace the current frame with the destination */
+	Navigate = "NAVIGATE",
+	/** Replace the current overlay with the destination */
+	Swap = "SWAP",
+	/** Open the destination as an overlay */
+	Overlay = "OVERLAY",
+	/** Scroll to the destination within the current frame */
+	ScrollTo = "SCROLL_TO",
+	/** Change to another variant of the component */
+	ChangeTo = "CHANGE_TO",
+}
+
+/** [Figma documentation](https://www.figma.com/developers/api#action-type) */
+export interface Action {
+	type: ActionType;
+	/** URL to open, if URL type */
+	url?: string;
+	/** ID of the node to go to, if NODE type */
+	destinationId?: string;
+	/** How to go to the destination, if NODE type */
+	navigation?: Navigation;
+}
+
+/** The event that triggers a prototype interaction */
+export enum TriggerType {
+	OnClick = "ON_CLICK",
+	OnHover = "ON_HOVER",
+	OnPress = "ON_PRESS",
+	OnDrag = "ON_DRAG",
+	AfterTimeout = "AFTER_TIMEOUT",
+	MouseEnter = "MOUSE_ENTER",
+	MouseLeave = "MOUSE_LEAVE",
+	MouseUp = "MOUSE_UP",
+	MouseDown = "MOUSE_DOWN",
+	OnKeyDown = "ON_KEY_DOWN",
+	OnKeyUp = "ON_KEY_UP",
+	OnMediaHit = "ON_MEDIA_HIT",
+	OnMediaEnd = "ON_MEDIA_END",
+}
+
+/** [Figma documentation](https://www.figma.com/developers/api#trigger-type) */
+export interface Trigger {
+	type: TriggerType;
+}
+
+/**
+ * A prototype interaction
+ * 
+ * [Figma documentation](https://www.figma.com/developers/api#reaction-type)
+ */
+export interface Reaction {
+	/** Deprecated in favour of `actions` */
+	action?: Action;
+	/** The actions performed, in order */
+	actions?: Action[];
+	trigger: Trigger;
+}
+
+/** How a mask hides the layers it masks */
+export enum MaskType {
+	/** The mask's opacity, including that of its fills, shows the layers */
+	Alpha = "ALPHA",
+	/** The mask's shape shows the layers, ignoring its fills */
+	Vector = "VECTOR",
+	/** The brightness of the mask's fills shows the layers */
+	Luminance = "LUMINANCE",
+}
+
 /** [Figma documentation](https://www.figma.com/developers/api#rectangle-type) */
 export interface Rectangle {
 	x?: number;
@@ -200,6 +364,23 @@ export enum CounterAxisAlignItems {
 	Baseline = "BASELINE",
 }
 
+export enum LayoutWrap {
+	NoWrap = "NO_WRAP",
+	Wrap = "WRAP",
+}
+
+export enum CounterAxisAlignContent {
+	Auto = "AUTO",
+	SpaceBetween = "SPACE_BETWEEN",
+}
+
+export enum GridChildAlign {
+	Auto = "AUTO",
+	Min = "MIN",
+	Center = "CENTER",
+	Max = "MAX",
+}
+
 export enum LayoutPositioning {
 	Absolute = "ABSOLUTE",
 }
@@ -208,6 +389,7 @@ export enum LayoutMode {
 	None = "NONE",
 	Horizontal = "HORIZONTAL",
 	Vertical = "VERTICAL",
+	Grid = "GRID",
 }
 
 export interface Styles {
@@ -218,6 +400,7 @@ export interface Styles {
 }
 
 export enum TextCase {
+	Original = "ORIGINAL",
 	Upper = "UPPER",
 	Lower = "LOWER",
 	Title = "TITLE",
@@ -226,6 +409,7 @@ export enum TextCase {
 }
 
 export enum TextDecoration {
+	None = "NONE",
 	Strikethrough = "STRIKETHROUGH",
 	Underline = "UNDERLINE",
 }
@@ -237,6 +421,28 @@ export enum TextAutoResize {
 	Truncate = "TRUNCATE",
 }
 
+/** Whether text that doesn't fit is truncated */
+export enum TextTruncation {
+	Disabled = "DISABLED",
+	/** Trailing text is replaced with "…" */
+	Ending = "ENDING",
+}
+
+/** Horizontal text alignment */
+export enum TextAlignHorizontal {
+	Left = "LEFT",
+	Right = "RIGHT",
+	Center = "CENTER",
+	Justified = "JUSTIFIED",
+}
+
+/** Vertical text alignment */
+export enum TextAlignVertical {
+	Top = "TOP",
+	Center = "CENTER",
+	Bottom = "BOTTOM",
+}
+
 /** Type of hyperlink */
 export enum HyperlinkType {
 	Url = "URL",
@@ -251,6 +457,13 @@ export interface Hyperlink {
 	nodeId?: string;
 }
 
+/** The unit of the line height value specified by the user */
+export enum LineHeightUnit {
+	Pixels = "PIXELS",
+	FontSizePercent = "FONT_SIZE_%",
+	IntrinsicPercent = "INTRINSIC_%",
+}
+
 /**
  * Metadata for character formatting
  * 
@@ -259,8 +472,12 @@ export interface Hyperlink {
 export interface TypeStyle {
 	/** Font family of text (standard name) */
 	fontFamily: string;
+	/** PostScript font name */
+	fontPostScriptName?: string;
 	/** Space between paragraphs in px, 0 if not present */
 	paragraphSpacing?: number;
+	/** Paragraph indentation in px, 0 if not present */
+	paragraphIndent?: number;
 	/** Whether or not text is italicized */
 	italic?: boolean;
 	/** Numeric font weight */
@@ -273,10 +490,66 @@ export interface TypeStyle {
 	textDecoration?: TextDecoration;
 	/** Dimensions along which text will auto resize, default is that the text does not auto-resize */
 	textAutoResize?: TextAutoResize;
+	/** Whether text that doesn't fit its bounds is truncated, default is disabled */
+	textTruncation?: TextTruncation;
+	/** The number of lines shown before truncating the text, if truncation is enabled */
+	maxLines?: number;
+	/** Horizontal text alignment as string enum */
+	textAlignHorizontal?: TextAlignHorizontal;
+	/** Vertical text alignment as string enum */
+	textAlignVertical?: TextAlignVertical;
+	/** Space between characters in px */
+	letterSpacing?: number;
+	/** A map of OpenType feature flags to 1 or 0, 1 if it is enabled and 0 if it is disabled. Note that some flags aren't reflected here. For example, SMCP (small caps) is still represented by the textCase field. */
+	opentypeFlags?: Record<string, number>;
 	/** Link to a URL or frame */
 	hyperlink?: Hyperlink;
 	/** Line height in px */
 	lineHeightPx: number;
+	/** Line height as a percentage of normal line height. This is deprecated; in a future version of the API only lineHeightPx and lineHeightPercentFontSize will be returned. */
+	lineHeightPercent?: number;
+	/** Line height as a percentage of the font size. Only returned when lineHeightPercent is not 100 */
+	lineHeightPercentFontSize?: number;
+	/** The unit of the line height value specified by the user. */
+	lineHeightUnit?: LineHeightUnit;
+}
+
+/**
+ * Character formatting that overrides part of a text node's `TypeStyle`. Only the overridden fields are present.
+ * 
+ * [Figma documentation](https://www.figma.com/developers/api#text-props)
+ */
+export interface TypeStyleOverride {
+	/** Font family of text (standard name) */
+	fontFamily?: string;
+	/** Whether or not text is italicized */
+	italic?: boolean;
+	/** Numeric font weight */
+	fontWeight?: number;
+	/** Font size in px */
+	fontSize?: number;
+	/** Text casing applied to the characters */
+	textCase?: TextCase;
+	/** Text decoration applied to the characters */
+	textDecoration?: TextDecoration;
+	/** Link to a URL or frame */
+	hyperlink?: Hyperlink;
+	/** Space between characters in px */
+	letterSpacing?: number;
+	/** A map of OpenType feature flags to 1 or 0 */
+	opentypeFlags?: Record<string, number>;
+	/** Line height in px */
+	lineHeightPx?: number;
+	/** Line height as a percentage of the font size */
+	lineHeightPercentFontSize?: number;
+	/** The unit of the line height value specified by the user. */
+	lineHeightUnit?: LineHeightUnit;
+	/** Paints applied to characters */
+	fills?: Paint[];
+	/** ID of the fill style the paints come from */
+	inheritFillStyleId?: string;
+	/** ID of the text style the font properties come from */
+	inheritTextStyleId?: string;
 }
 
 export enum LayoutConstraintVertical {
@@ -343,6 +616,10 @@ export interface Node {
 	individualStrokeWeights?: StrokeWeights;
 	/** Position of stroke relative to vector outline */
 	strokeAlign?: StrokeAlign;
+	/** An array of paths representing the object fill. Only returned if `geometry=paths` is requested. */
+	fillGeometry?: Path[];
+	/** An array of paths representing the object stroke. Only returned if `geometry=paths` is requested. */
+	strokeGeometry?: Path[];
 	/** An array of floating point numbers describing the pattern of dash length and gap lengths that the vector path follows. For example a value of [1, 2] indicates that the path has a dash of length 1 followed by a gap of length 2, repeated. */
 	strokeDashes?: number[];
 	/** Radius of each corner of the node if a single radius is set for all corners */
@@ -353,8 +630,26 @@ export interface Node {
 	transitionDuration?: number;
 	/** The easing curve used in the prototyping transition on this node */
 	transitionEasing?: EasingType;
+	/** Node ID of node to transition to in prototyping. Deprecated in favour of `reactions`. */
+	transitionNodeID?: string;
+	/** The prototype interactions of this node */
+	reactions?: Reaction[];
 	/** Opacity of the node */
 	opacity?: number;
+	/** How this node blends with nodes behind it in the scene */
+	blendMode?: BlendMode;
+	/** Whether this node is a mask for the sibling nodes above it */
+	isMask?: boolean;
+	/** How this node masks, if it is a mask */
+	maskType?: MaskType;
+	/** Whether or not this node clips content outside of its bounds */
+	clipsContent?: boolean;
+	/** The transform of the node relative to its parent. Only present if `geometry=paths` is requested. */
+	relativeTransform?: Transform;
+	/** The rotation of the node in radians, anticlockwise, if not 0 */
+	rotation?: number;
+	/** Width and height of the node before it is transformed. Only present if `geometry=paths` is requested. */
+	size?: Vector;
 	/** Bounding box of the node in absolute space coordinates */
 	absoluteBoundingBox?: Rectangle;
 	/** The bounds of the rendered node in the file in absolute space coordinates */
@@ -369,6 +664,36 @@ export interface Node {
 	counterAxisAlignItems?: CounterAxisAlignItems;
 	/** The distance between children of the frame. Can be negative. This property is only applicable for auto-layout frames. */
 	itemSpacing?: number;
+	/** Whether the children of a horizontal auto-layout frame wrap onto new lines when there isn't room. This property is only applicable for auto-layout frames. */
+	layoutWrap?: LayoutWrap;
+	/** The distance between wrapped lines of children. Null means it is the same as `itemSpacing`. This property is only applicable for auto-layout frames with wrapping. */
+	counterAxisSpacing?: number;
+	/** Whether wrapped lines of children are packed together or spread apart. This property is only applicable for auto-layout frames with wrapping. */
+	counterAxisAlignContent?: CounterAxisAlignContent;
+	/** The number of rows of a grid auto-layout frame */
+	gridRowCount?: number;
+	/** The number of columns of a grid auto-layout frame */
+	gridColumnCount?: number;
+	/** The distance between rows of a grid auto-layout frame */
+	gridRowGap?: number;
+	/** The distance between columns of a grid auto-layout frame */
+	gridColumnGap?: number;
+	/** The sizes of the rows of a grid auto-layout frame, as a value for CSS grid-template-rows */
+	gridRowsSizing?: string;
+	/** The sizes of the columns of a grid auto-layout frame, as a value for CSS grid-template-columns */
+	gridColumnsSizing?: string;
+	/** The index of the row the node starts in, counting from 0. This property is only applicable for direct children of grid auto-layout frames. */
+	gridRowAnchorIndex?: number;
+	/** The index of the column the node starts in, counting from 0. This property is only applicable for direct children of grid auto-layout frames. */
+	gridColumnAnchorIndex?: number;
+	/** The number of rows the node spans. This property is only applicable for direct children of grid auto-layout frames. */
+	gridRowSpan?: number;
+	/** The number of columns the node spans. This property is only applicable for direct children of grid auto-layout frames. */
+	gridColumnSpan?: number;
+	/** The horizontal alignment of the node within its grid cells. This property is only applicable for direct children of grid auto-layout frames. */
+	gridChildHorizontalAlign?: GridChildAlign;
+	/** The vertical alignment of the node within its grid cells. This property is only applicable for direct children of grid auto-layout frames. */
+	gridChildVerticalAlign?: GridChildAlign;
 	/** Determines whether a layer's size and position should be determined by auto-layout settings or manually adjustable. */
 	layoutPositioning?: LayoutPositioning;
 	/** Whether this layer uses auto-layout to position its children. */
@@ -389,12 +714,18 @@ export interface Node {
 	characters?: string;
 	/** Style of text including font family and weight */
 	style?: TypeStyle;
+	/** Array with same number of elements as characters in text box, each element is a reference to the styleOverrideTable defined below and maps to the corresponding character in the characters field. Elements with value 0 have the default type style */
+	characterStyleOverrides?: number[];
+	/** Map from ID to TypeStyle for looking up style overrides */
+	styleOverrideTable?: Record<string, TypeStyleOverride>;
 	/** Horizontal and vertical layout contraints for node */
 	constraints?: LayoutConstraint;
 	/** Determines if the layer should stretch along the parent’s counter axis. This property is only provided for direct children of auto-layout frames. */
 	layoutAlign?: LayoutAlign;
 	/** This property is applicable only for direct children of auto-layout frames, ignored otherwise. Determines whether a layer should stretch along the parent’s primary axis. A 0 corresponds to a fixed size and 1 corresponds to stretch */
 	layoutGrow?: number;
+	/** ID of component that this instance came from, refers to components table */
+	componentId?: string;
 }
 
 export enum StyleType {
@@ -416,6 +747,8 @@ export interface Style {
 export interface File {
 	document: Node;
 	components: Record<string, Component>;
+	/** Files exported before component sets existed don't have any */
+	componentSets?: Record<string, ComponentSet>;
 	styles: Record<string, Style>;
 	name: string;
 	schemaVersion: number;